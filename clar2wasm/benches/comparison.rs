@@ -2,9 +2,9 @@
 
 use std::hint::black_box;
 
-use clar2wasm::compile;
 use clar2wasm::datastore::{BurnDatastore, Datastore, StacksConstants};
 use clar2wasm::initialize::initialize_contract;
+use clar2wasm::{compile, compile_with_options, CompileOptions};
 use clarity::consts::CHAIN_ID_TESTNET;
 use clarity::types::{PrivateKey, StacksEpochId};
 use clarity::util::hash::Keccak256Hash;
@@ -124,6 +124,19 @@ fn webassembly<M, F>(b: &mut Bencher<M>, fn_name: &str, clarity: &str, init: F)
 where
     M: 'static + Measurement,
     F: FnOnce(&mut Environment) -> Vec<Value>,
+{
+    webassembly_with_options(b, fn_name, clarity, &CompileOptions::default(), init)
+}
+
+fn webassembly_with_options<M, F>(
+    b: &mut Bencher<M>,
+    fn_name: &str,
+    clarity: &str,
+    options: &CompileOptions,
+    init: F,
+) where
+    M: 'static + Measurement,
+    F: FnOnce(&mut Environment) -> Vec<Value>,
 {
     let contract_id = QualifiedContractIdentifier::new(
         StandardPrincipalData::transient(),
@@ -145,13 +158,14 @@ where
     // Create a new analysis database
     let mut analysis_db = AnalysisDatabase::new(&mut clarity_store);
 
-    let mut compilation = compile(
+    let mut compilation = compile_with_options(
         clarity,
         &contract_id,
         cost_tracker,
         ClarityVersion::latest(),
         StacksEpochId::latest(),
         &mut analysis_db,
+        options,
     )
     .expect("Failed compiling clarity to WASM");
 
@@ -200,6 +214,62 @@ where
     global_context.commit().unwrap();
 }
 
+/// Compilation of a contract with many comparisons, which resolve the same stdlib
+/// functions over and over again.
+fn compile_comparisons(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compile_comparisons");
+    for n in (100..=1000).step_by(300) {
+        let clarity = format!(
+            "(define-read-only (cmp (a int) (b int)) (begin {} (< a b)))",
+            "(< a b) (<= a b) (> a b) (>= a b) ".repeat(n)
+        );
+        group.bench_with_input(BenchmarkId::new("webassembly", n), &n, |b, _| {
+            b.iter(|| {
+                compile(
+                    black_box(&clarity),
+                    &QualifiedContractIdentifier::transient(),
+                    LimitedCostTracker::new_free(),
+                    ClarityVersion::latest(),
+                    StacksEpochId::latest(),
+                    &mut AnalysisDatabase::new(&mut MemoryBackingStore::new()),
+                )
+                .expect("Failed compiling clarity to WASM")
+            })
+        });
+    }
+}
+
+/// A tight loop of comparisons, with and without the stdlib inlined.
+fn inline_stdlib(c: &mut Criterion) {
+    let clarity = r#"
+        (define-private (count-less (x int) (acc int))
+            (if (< x 500) (+ acc 1) acc)
+        )
+
+        (define-read-only (inline_stdlib (l (list 1000 int)))
+            (fold count-less l 0)
+        )
+    "#;
+    let list = || Value::cons_list_unsanitized((0..1000).map(Value::Int).collect()).unwrap();
+
+    let mut group = c.benchmark_group("inline_stdlib");
+    group.bench_function("call", |b| {
+        webassembly(b, "inline_stdlib", clarity, |_| vec![list()]);
+    });
+    group.bench_function("inline", |b| {
+        webassembly_with_options(
+            b,
+            "inline_stdlib",
+            clarity,
+            &CompileOptions {
+                inline_stdlib: true,
+                ..Default::default()
+            },
+            |_| vec![list()],
+        );
+    });
+}
+
 fn criterion_config() -> Criterion {
     if cfg!(feature = "flamegraph") {
         Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)))
@@ -299,6 +369,24 @@ decl_benches! {
         "#,
         [Value::Int(42), Value::Int(12345)]
     ),
+    (
+        "buff_to_uint_be_16",
+        r#"
+         (define-read-only (buff_to_uint_be_16 (b (buff 16)))
+             (buff-to-uint-be b)
+         )
+        "#,
+        [Value::buff_from(vec![0xab; 16]).unwrap()]
+    ),
+    (
+        "buff_to_uint_be_15",
+        r#"
+         (define-read-only (buff_to_uint_be_15 (b (buff 16)))
+             (buff-to-uint-be b)
+         )
+        "#,
+        [Value::buff_from(vec![0xab; 15]).unwrap()]
+    ),
 }
 
 decl_benches! {
@@ -491,4 +579,16 @@ fn add_prices_init(n: usize, env: &mut Environment) -> Value {
     Value::cons_list_unsanitized(prices).unwrap()
 }
 
-criterion_main!(single, range);
+criterion_group! {
+    name = compilation;
+    config = criterion_config();
+    targets = compile_comparisons
+}
+
+criterion_group! {
+    name = inlining;
+    config = criterion_config();
+    targets = inline_stdlib
+}
+
+criterion_main!(single, range, compilation, inlining);
@@ -2,7 +2,7 @@ mod utils;
 use std::fs;
 
 use clap::Parser;
-use clar2wasm::CompileError;
+use clar2wasm::{CompileError, CompileOptions, WasmFeatures};
 use clarity::vm::costs::LimitedCostTracker;
 use clarity::vm::database::MemoryBackingStore;
 use clarity::vm::types::QualifiedContractIdentifier;
@@ -23,6 +23,27 @@ struct Args {
     /// Output file to write compiled WebAssembly to
     #[arg(short, long)]
     output: Option<String>,
+    /// Inline small stdlib functions at their call sites
+    #[arg(long)]
+    inline_stdlib: bool,
+    /// Merge the generated functions with identical bodies
+    #[arg(long)]
+    deduplicate_functions: bool,
+    /// Copy memory with loops instead of bulk memory instructions
+    #[arg(long)]
+    no_bulk_memory: bool,
+    /// Export a wrapper taking serialized arguments for each public function
+    #[arg(long)]
+    entry_wrappers: bool,
+    /// Embed the hash of the source in a custom section
+    #[arg(long)]
+    source_hash: bool,
+    /// Maximum size of the call stack in bytes, beyond which execution traps
+    #[arg(long)]
+    max_call_stack: Option<u32>,
+    /// Maximum number of instructions in the generated functions
+    #[arg(long)]
+    instruction_budget: Option<usize>,
 }
 
 fn main() {
@@ -53,13 +74,25 @@ fn main() {
     let cost_track = LimitedCostTracker::new_free();
 
     // Pass the source code to the compiler.
-    let result = clar2wasm::compile(
+    let options = CompileOptions {
+        inline_stdlib: args.inline_stdlib,
+        deduplicate_functions: args.deduplicate_functions,
+        features: WasmFeatures {
+            bulk_memory: !args.no_bulk_memory,
+        },
+        entry_wrappers: args.entry_wrappers,
+        source_hash: args.source_hash,
+        max_call_stack: args.max_call_stack,
+        instruction_budget: args.instruction_budget,
+    };
+    let result = clar2wasm::compile_with_options(
         &source,
         &contract_id,
         cost_track,
         clarity_version,
         epoch,
         &mut datastore.as_analysis_db(),
+        &options,
     )
     .unwrap_or_else(|err| match err {
         CompileError::Generic {
@@ -7,7 +7,7 @@
 //! mock implementations that do nothing.
 #![allow(clippy::expect_used, clippy::unwrap_used)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use clarity::consts::PEER_VERSION_EPOCH_2_5;
 use clarity::types::chainstate::{
@@ -336,7 +336,11 @@ impl BurnDatastore {
         let bytes = height_to_hashed_bytes(0);
         let id = StacksBlockId(bytes);
         let sortition_id = SortitionId(bytes);
+        // Without `std`, the chain starts at the Unix epoch.
+        #[cfg(feature = "std")]
         let genesis_time = chrono::Utc::now().timestamp() as u64;
+        #[cfg(not(feature = "std"))]
+        let genesis_time = 0;
 
         let genesis_block = BlockInfo {
             block_header_hash: BlockHeaderHash([0x00; 32]),
@@ -420,6 +424,26 @@ impl BurnDatastore {
         self.open_chain_tip = height_to_id(self.chain_height);
         self.current_chain_tip = self.open_chain_tip;
     }
+
+    /// Replaces the PoX payout addresses and payout of the block at `height`.
+    pub fn set_pox_payout_addrs(&mut self, height: u32, addrs: Vec<TupleData>, payout: u128) {
+        let block = self
+            .store
+            .get_mut(&height_to_id(height))
+            .expect("Block must exist to set its PoX payout addresses.");
+        block.pox_payout_addrs = (addrs, payout);
+    }
+
+    /// Replaces the genesis time (taken from the wall clock by default) with
+    /// `genesis_time`, and recomputes the time of every known block from it.
+    /// Blocks added afterwards are also derived from the frozen time.
+    pub fn freeze_time(&mut self, genesis_time: u64) {
+        self.genesis_time = genesis_time;
+        for block in self.store.values_mut() {
+            let time_since_genesis = u64::from(block.burn_block_height) * 1800;
+            block.burn_block_time = genesis_time + time_since_genesis;
+        }
+    }
 }
 
 impl HeadersDB for BurnDatastore {
@@ -649,6 +673,20 @@ impl Datastore {
         self.current_chain_tip = *bhh;
     }
 
+    /// Returns a copy of the data written up to the open chain tip, sorted
+    /// by key.
+    pub fn data_snapshot(&self) -> BTreeMap<String, String> {
+        self.store
+            .get(&self.open_chain_tip)
+            .or_else(|| {
+                self.block_id_lookup
+                    .get(&self.open_chain_tip)
+                    .and_then(|lookup_id| self.store.get(lookup_id))
+            })
+            .map(|data| data.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default()
+    }
+
     pub fn put(&mut self, key: &str, value: &str) {
         let lookup_id = self
             .block_id_lookup
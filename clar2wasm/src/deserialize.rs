@@ -207,8 +207,8 @@ impl WasmGenerator {
                         .local_get(offset_local)
                         .i32_const(1)
                         .binop(BinaryOp::I32Add)
-                        .i32_const(PRINCIPAL_BYTES as i32)
-                        .memory_copy(memory, memory);
+                        .i32_const(PRINCIPAL_BYTES as i32);
+                    self.emit_memory_copy(then, memory);
 
                     // Write the contract name length (0)
                     then.local_get(principal_offset).i32_const(0).store(
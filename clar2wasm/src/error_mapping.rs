@@ -78,6 +78,17 @@ pub enum ErrorMap {
     /// Indicates an attempt to use a function with too many arguments
     ArgumentCountAtMost = 15,
 
+    /// Indicates that the call stack grew beyond its configured maximum size.
+    StackOverflow = 16,
+
+    /// Indicates that an entry wrapper received arguments that do not
+    /// deserialize to the declared types of the function parameters.
+    ArgumentTypeMismatch = 17,
+
+    /// Indicates that the serialized result of an entry wrapper does not fit
+    /// in the buffer provided by the caller.
+    ResultTooLarge = 18,
+
     /// A catch-all for errors that are not mapped to specific error codes.
     /// This might be used for unexpected or unclassified errors.
     NotMapped = 99,
@@ -103,6 +114,9 @@ impl From<i32> for ErrorMap {
             13 => ErrorMap::ArgumentCountMismatch,
             14 => ErrorMap::ArgumentCountAtLeast,
             15 => ErrorMap::ArgumentCountAtMost,
+            16 => ErrorMap::StackOverflow,
+            17 => ErrorMap::ArgumentTypeMismatch,
+            18 => ErrorMap::ResultTooLarge,
             _ => ErrorMap::NotMapped,
         }
     }
@@ -282,6 +296,13 @@ fn from_runtime_error_code(
             let (expected, got) = get_runtime_error_arg_lengths(&instance, &mut store);
             Error::Unchecked(CheckErrors::RequiresAtMostArguments(expected, got))
         }
+        ErrorMap::StackOverflow => {
+            Error::Runtime(RuntimeErrorType::MaxStackDepthReached, Some(Vec::new()))
+        }
+        ErrorMap::ArgumentTypeMismatch => Error::Wasm(WasmError::ValueTypeMismatch),
+        ErrorMap::ResultTooLarge => Error::Wasm(WasmError::UnableToWriteMemory(
+            wasmtime::Error::msg("the result does not fit in the buffer of the caller").into(),
+        )),
         _ => panic!("Runtime error code {} not supported", runtime_error_code),
     }
 }
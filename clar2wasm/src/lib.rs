@@ -1,16 +1,22 @@
 extern crate lazy_static;
 
+use std::collections::{HashMap, HashSet};
+
 use clarity::types::StacksEpochId;
 use clarity::vm::analysis::{run_analysis, AnalysisDatabase, ContractAnalysis};
 use clarity::vm::ast::{build_ast_with_diagnostics, ContractAST};
 use clarity::vm::costs::{ExecutionCost, LimitedCostTracker};
 use clarity::vm::diagnostic::Diagnostic;
 use clarity::vm::types::{
-    FixedFunction, ListTypeData, QualifiedContractIdentifier, SequenceSubtype, TypeSignature,
+    FixedFunction, ListTypeData, PrincipalData, QualifiedContractIdentifier, SequenceSubtype,
+    TypeSignature,
 };
-use clarity::vm::ClarityVersion;
+use clarity::vm::{ClarityName, ClarityVersion, SymbolicExpression, SymbolicExpressionType, Value};
+use sha2::{Digest, Sha256};
 pub use walrus::Module;
+pub use wasm_generator::WasmFeatures;
 use wasm_generator::{GeneratorError, WasmGenerator};
+pub use words::{supported_words, supported_words_at_version, SupportedWord};
 
 mod deserialize;
 pub mod initialize;
@@ -42,6 +48,8 @@ pub struct CompileResult {
     pub diagnostics: Vec<Diagnostic>,
     pub module: Module,
     pub contract_analysis: ContractAnalysis,
+    /// The type of the value returned by the top-level expressions, if any.
+    pub top_level_type: Option<TypeSignature>,
 }
 
 #[derive(Debug)]
@@ -53,13 +61,59 @@ pub enum CompileError {
     },
 }
 
+/// Options controlling the generated code.
+#[derive(Debug, Clone, Default)]
+pub struct CompileOptions {
+    /// Inline the bodies of small stdlib functions at their call sites,
+    /// instead of calling them.
+    pub inline_stdlib: bool,
+    /// Merge the generated functions with identical bodies, keeping the
+    /// exported ones.
+    pub deduplicate_functions: bool,
+    /// The WebAssembly features that the generated code may use.
+    pub features: WasmFeatures,
+    /// Export a `<name>.entry` wrapper for each public function, taking and
+    /// returning consensus serialized values.
+    pub entry_wrappers: bool,
+    /// Embed the SHA256 of the source and the compiler version in a
+    /// `clarity.srchash` custom section.
+    pub source_hash: bool,
+    /// The maximum size, in bytes, of the call stack. A reservation going
+    /// beyond it traps with a stack overflow. Without a limit, the memory
+    /// grows as needed.
+    pub max_call_stack: Option<u32>,
+    /// The maximum number of instructions in the generated functions, beyond
+    /// which the compilation fails. Unlimited by default.
+    pub instruction_budget: Option<usize>,
+}
+
 pub fn compile(
+    source: &str,
+    contract_id: &QualifiedContractIdentifier,
+    cost_tracker: LimitedCostTracker,
+    clarity_version: ClarityVersion,
+    epoch: StacksEpochId,
+    analysis_db: &mut AnalysisDatabase,
+) -> Result<CompileResult, CompileError> {
+    compile_with_options(
+        source,
+        contract_id,
+        cost_tracker,
+        clarity_version,
+        epoch,
+        analysis_db,
+        &CompileOptions::default(),
+    )
+}
+
+pub fn compile_with_options(
     source: &str,
     contract_id: &QualifiedContractIdentifier,
     mut cost_tracker: LimitedCostTracker,
     clarity_version: ClarityVersion,
     epoch: StacksEpochId,
     analysis_db: &mut AnalysisDatabase,
+    options: &CompileOptions,
 ) -> Result<CompileResult, CompileError> {
     // Parse the contract
     let (ast, mut diagnostics, success) = build_ast_with_diagnostics(
@@ -78,55 +132,97 @@ pub fn compile(
         });
     }
 
-    // Run the analysis passes
-    let mut contract_analysis = match run_analysis(
+    // Run the analysis passes, so that every expression is typed before the
+    // code generation.
+    let mut contract_analysis = match type_check(
+        &ast,
         contract_id,
-        &ast.expressions,
-        analysis_db,
-        false,
         cost_tracker,
-        epoch,
         clarity_version,
-        true,
+        epoch,
+        analysis_db,
     ) {
         Ok(contract_analysis) => contract_analysis,
-        Err((e, cost_track)) => {
-            diagnostics.push(Diagnostic::err(&e.err));
+        Err((diagnostic, cost_tracker)) => {
+            diagnostics.push(diagnostic);
             return Err(CompileError::Generic {
                 ast: Box::new(ast),
                 diagnostics,
-                cost_tracker: Box::new(cost_track),
+                cost_tracker: Box::new(cost_tracker),
             });
         }
     };
 
-    typechecker_workaround(&ast, &mut contract_analysis);
+    // Load the signatures of the functions called with a static
+    // `contract-call?`, so that the generator can pass the arguments with
+    // the types the callee declares.
+    let mut static_calls = HashSet::new();
+    collect_static_contract_calls(&ast.expressions, &mut static_calls);
+    let contract_call_signatures: Result<HashMap<_, _>, GeneratorError> = static_calls
+        .into_iter()
+        .filter_map(|(contract_id, function)| {
+            analysis_db
+                .execute(|db| {
+                    match db.get_public_function_type(&contract_id, function.as_str(), &epoch)? {
+                        Some(signature) => Ok(Some(signature)),
+                        None => {
+                            db.get_read_only_function_type(&contract_id, function.as_str(), &epoch)
+                        }
+                    }
+                })
+                .map_err(|e| {
+                    GeneratorError::InternalError(format!(
+                        "failed to load the signature of {contract_id}.{function}: {e}"
+                    ))
+                })
+                .transpose()
+                .map(|signature| signature.map(|signature| ((contract_id, function), signature)))
+        })
+        .collect();
 
-    // Now that the typechecker pass is done, we can concretize the expressions types which
-    // might contain `ListUnionType` or `CallableType`
-    #[allow(clippy::expect_used)]
-    if let Err(e) = utils::concretize(&mut contract_analysis) {
-        diagnostics.push(e.diagnostic);
-        return Err(CompileError::Generic {
-            ast: Box::new(ast),
-            diagnostics: diagnostics.clone(),
-            cost_tracker: Box::new(
-                contract_analysis
-                    .cost_track
-                    .take()
-                    .expect("Failed to take cost tracker from contract analysis"),
-            ),
-        });
-    }
+    // Load the definitions of the implemented traits, so that the generator
+    // can check that the contract conforms to them.
+    let implemented_traits: Result<HashMap<_, _>, GeneratorError> = contract_analysis
+        .implemented_traits
+        .iter()
+        .filter_map(|trait_id| {
+            analysis_db
+                .execute(|db| {
+                    db.get_defined_trait(&trait_id.contract_identifier, trait_id.name.as_str())
+                })
+                .map_err(|e| {
+                    GeneratorError::InternalError(format!("failed to load trait {trait_id}: {e}"))
+                })
+                .transpose()
+                .map(|methods| methods.map(|methods| (trait_id.clone(), methods)))
+        })
+        .collect();
 
     #[allow(clippy::expect_used)]
-    match WasmGenerator::new(contract_analysis.clone()).and_then(WasmGenerator::generate) {
-        Ok(module) => Ok(CompileResult {
-            ast,
-            diagnostics,
-            module,
-            contract_analysis,
-        }),
+    match WasmGenerator::new(contract_analysis.clone()).and_then(|mut generator| {
+        generator.implemented_traits = implemented_traits?;
+        generator.contract_call_signatures = contract_call_signatures?;
+        generator.inline_stdlib = options.inline_stdlib;
+        generator.deduplicate_functions = options.deduplicate_functions;
+        generator.features = options.features;
+        generator.entry_wrappers = options.entry_wrappers;
+        generator.max_call_stack = options.max_call_stack;
+        generator.instruction_budget = options.instruction_budget;
+        generator.source_hash = options
+            .source_hash
+            .then(|| Sha256::digest(source.as_bytes()).into());
+        generator.generate()
+    }) {
+        Ok(generated) => {
+            diagnostics.extend(generated.diagnostics);
+            Ok(CompileResult {
+                ast,
+                diagnostics,
+                module: generated.module,
+                contract_analysis,
+                top_level_type: generated.top_level_type,
+            })
+        }
         Err(e) => {
             diagnostics.push(Diagnostic::err(&e));
             Err(CompileError::Generic {
@@ -143,6 +239,35 @@ pub fn compile(
     }
 }
 
+/// Collects the contract and function names of the `contract-call?`s to a
+/// literal contract in `exprs`.
+fn collect_static_contract_calls(
+    exprs: &[SymbolicExpression],
+    calls: &mut HashSet<(QualifiedContractIdentifier, ClarityName)>,
+) {
+    for expr in exprs {
+        let Some(list) = expr.match_list() else {
+            continue;
+        };
+        if let [word, contract, function, ..] = list {
+            if let (
+                Some("contract-call?"),
+                SymbolicExpressionType::LiteralValue(Value::Principal(PrincipalData::Contract(
+                    contract_id,
+                ))),
+                Some(function),
+            ) = (
+                word.match_atom().map(|atom| atom.as_str()),
+                &contract.expr,
+                function.match_atom(),
+            ) {
+                calls.insert((contract_id.clone(), function.clone()));
+            }
+        }
+        collect_static_contract_calls(list, calls);
+    }
+}
+
 // Workarounds to make filter/fold work in cases where it would not otherwise. see issue #488
 fn typechecker_workaround(ast: &ContractAST, contract_analysis: &mut ContractAnalysis) {
     for expr in ast.expressions.iter() {
@@ -236,9 +361,55 @@ fn typechecker_workaround(ast: &ContractAST, contract_analysis: &mut ContractAna
     }
 }
 
+/// Runs the Clarity analysis passes, including the type checker, on a parsed
+/// contract. The type map of the resulting analysis holds the type of every
+/// expression traversed by the generator, with the list union and callable
+/// types made concrete.
+pub fn type_check(
+    ast: &ContractAST,
+    contract_id: &QualifiedContractIdentifier,
+    cost_tracker: LimitedCostTracker,
+    clarity_version: ClarityVersion,
+    epoch: StacksEpochId,
+    analysis_db: &mut AnalysisDatabase,
+) -> Result<ContractAnalysis, (Diagnostic, LimitedCostTracker)> {
+    let mut contract_analysis = run_analysis(
+        contract_id,
+        &ast.expressions,
+        analysis_db,
+        false,
+        cost_tracker,
+        epoch,
+        clarity_version,
+        true,
+    )
+    .map_err(|(e, cost_tracker)| (Diagnostic::err(&e.err), cost_tracker))?;
+
+    typechecker_workaround(ast, &mut contract_analysis);
+
+    // Now that the typechecker pass is done, we can concretize the expressions types which
+    // might contain `ListUnionType` or `CallableType`
+    if let Err(e) = utils::concretize(&mut contract_analysis) {
+        #[allow(clippy::expect_used)]
+        let cost_tracker = contract_analysis
+            .cost_track
+            .take()
+            .expect("Failed to take cost tracker from contract analysis");
+        return Err((e.diagnostic, cost_tracker));
+    }
+
+    Ok(contract_analysis)
+}
+
 pub fn compile_contract(contract_analysis: ContractAnalysis) -> Result<Module, GeneratorError> {
+    // Analyses loaded from the datastore no longer have their type map.
+    if contract_analysis.type_map.is_none() {
+        return Err(GeneratorError::TypeError(
+            "the contract must be type-checked before code generation, see `type_check`".to_owned(),
+        ));
+    }
     let generator = WasmGenerator::new(contract_analysis)?;
-    generator.generate()
+    generator.generate().map(|generated| generated.module)
 }
 
 mod utils {
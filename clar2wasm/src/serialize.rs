@@ -137,8 +137,8 @@ impl WasmGenerator {
             .i32_const(1)
             .binop(BinaryOp::I32Add)
             .local_get(poffset)
-            .i32_const(PRINCIPAL_BYTES as i32)
-            .memory_copy(memory, memory);
+            .i32_const(PRINCIPAL_BYTES as i32);
+        self.emit_memory_copy(builder, memory);
 
         // If `plength` is greater than STANDARD_PRINCIPAL_BYTES, then
         // this is a contract principal, else, it's a standard
@@ -183,9 +183,9 @@ impl WasmGenerator {
                         // Compute the length
                         .local_get(plength)
                         .i32_const(STANDARD_PRINCIPAL_BYTES as i32)
-                        .binop(BinaryOp::I32Sub)
-                        // Copy the data
-                        .memory_copy(memory, memory);
+                        .binop(BinaryOp::I32Sub);
+                    // Copy the data
+                    self.emit_memory_copy(then, memory);
 
                     // Push the total length written onto the data stack.
                     // It is the same as plength, plus 1 (the type prefix).
@@ -620,10 +620,8 @@ impl WasmGenerator {
             .local_tee(write_ptr);
 
         // Copy the buffer
-        builder
-            .local_get(read_ptr)
-            .local_get(length)
-            .memory_copy(memory, memory);
+        builder.local_get(read_ptr).local_get(length);
+        self.emit_memory_copy(builder, memory);
 
         // Push the length written to the data stack:
         //  length    +    1    +    4
@@ -687,10 +685,8 @@ impl WasmGenerator {
             .local_tee(write_ptr);
 
         // Copy the string
-        builder
-            .local_get(read_ptr)
-            .local_get(length)
-            .memory_copy(memory, memory);
+        builder.local_get(read_ptr).local_get(length);
+        self.emit_memory_copy(builder, memory);
 
         // Push the length written to the data stack:
         //  length    +    1    +    4
@@ -847,10 +843,8 @@ impl WasmGenerator {
 
             // Serialize the key name
             let (offset, length) = self.add_string_literal(key)?;
-            builder
-                .i32_const(offset as i32)
-                .i32_const(length as i32)
-                .memory_copy(memory, memory);
+            builder.i32_const(offset as i32).i32_const(length as i32);
+            self.emit_memory_copy(builder, memory);
 
             // Adjust the write pointer
             builder
@@ -330,6 +330,12 @@
     ;;
     ;; Global definitions
     (global $stack-pointer (mut i32) (i32.const 0))
+    ;; Size of the work space used by the stdlib functions above the stack
+    ;; pointer, set by the generator.
+    (global $work-space i32 (i32.const 0))
+    ;; Highest address the stack pointer may reach, set by the generator when
+    ;; the call stack size is limited (unsigned, so -1 is no limit).
+    (global $stack-limit i32 (i32.const -1))
     (global $runtime-error-code (mut i32) (i32.const -1))
     (global $runtime-error-arg-offset (mut i32) (i32.const -1))
     (global $runtime-error-arg-len (mut i32) (i32.const -1))
@@ -391,6 +397,47 @@
         (unreachable)
     )
 
+    ;; Grows the memory, if needed, so that it holds a frame of $frame-size bytes
+    ;; above the stack pointer and the work space above it. Called once in the
+    ;; prelude of each function. Traps if the memory cannot grow.
+    (func $stdlib.ensure-stack-memory (param $frame-size i32)
+        (local $frame-end i64)
+        (local $pages i32)
+        ;; End of the frame, computed in 64 bits so that it cannot overflow.
+        (local.set $frame-end
+            (i64.add
+                (i64.extend_i32_u (global.get $stack-pointer))
+                (i64.extend_i32_u (local.get $frame-size))
+            )
+        )
+        (if (i64.gt_u (local.get $frame-end) (i64.extend_i32_u (global.get $stack-limit)))
+            (then (call $stdlib.runtime-error (i32.const 16)))
+        )
+
+        ;; Number of pages needed, rounded up.
+        (local.set $pages
+            (i32.wrap_i64
+                (i64.shr_u
+                    (i64.add
+                        (i64.add
+                            (local.get $frame-end)
+                            (i64.extend_i32_u (global.get $work-space))
+                        )
+                        (i64.const 0xffff)
+                    )
+                    (i64.const 16)
+                )
+            )
+        )
+        (if (i32.gt_u (local.get $pages) (memory.size))
+            (then
+                (if (i32.eq (memory.grow (i32.sub (local.get $pages) (memory.size))) (i32.const -1))
+                    (then (unreachable))
+                )
+            )
+        )
+    )
+
     ;; This function can be used to add either signed or unsigned integers
     (func $stdlib.add-int128 (param $a_lo i64) (param $a_hi i64) (param $b_lo i64) (param $b_hi i64) (result i64 i64)
         ;; Add the lower 64 bits
@@ -1421,6 +1468,11 @@
             (then (return (i64.const 1) (i64.const 0)))
         )
 
+        ;; if b < 0 => runtime error, whatever the value of a
+        (if (i64.lt_s (local.get $b_hi) (i64.const 0))
+            (then (call $stdlib.runtime-error (i32.const 8)))
+        )
+
         ;; (a == 0 => 0) & (a == 1 => 1) ==> (a < 2 => a)
         ;; also, (b == 1 => a)
         (if (i32.or
@@ -1430,11 +1482,6 @@
             (then (return (local.get $a_lo) (local.get $a_hi)))
         )
 
-        ;; otherwise, if b < 0 => runtime error
-        (if (i64.lt_s (local.get $b_hi) (i64.const 0))
-            (then (call $stdlib.runtime-error (i32.const 8)))
-        )
-
         ;; if b > (a >= 0 ? 126 : 127) -> runtime error: overflow (since the biggest b that doesn't
         ;; overflow is in 2^126 and -2^127, and this is an edge case)
         (if (i32.or
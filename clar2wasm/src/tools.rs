@@ -3,26 +3,34 @@
 //! in production.
 #![allow(clippy::expect_used, clippy::unwrap_used)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
 use std::sync::LazyLock;
 
 use clarity::consts::{CHAIN_ID_MAINNET, CHAIN_ID_TESTNET};
 use clarity::types::StacksEpochId;
-use clarity::vm::analysis::run_analysis;
+use clarity::vm::analysis::{run_analysis, ContractAnalysis};
 use clarity::vm::ast::build_ast;
-use clarity::vm::contexts::{EventBatch, GlobalContext};
+use clarity::vm::callables::DefinedFunction;
+use clarity::vm::contexts::{CallStack, EventBatch, GlobalContext};
 use clarity::vm::contracts::Contract;
 use clarity::vm::costs::LimitedCostTracker;
-use clarity::vm::database::ClarityDatabase;
+use clarity::vm::database::{ClarityDatabase, MemoryBackingStore};
 use clarity::vm::errors::{CheckErrors, Error, WasmError};
 use clarity::vm::events::{SmartContractEventData, StacksTransactionEvent};
-use clarity::vm::types::{PrincipalData, QualifiedContractIdentifier, StandardPrincipalData};
+use clarity::vm::types::{
+    PrincipalData, QualifiedContractIdentifier, StandardPrincipalData, TupleData,
+};
 use clarity::vm::{eval_all, ClarityVersion, ContractContext, ContractName, Value};
 use regex::Regex;
+use walrus::ir::{dfs_in_order, Block, Call, IfElse, Instr, InstrSeqId, Loop, Visitor};
+use walrus::{FunctionId, FunctionKind, LocalFunction, Module};
 
-use crate::compile;
 use crate::datastore::{BurnDatastore, Datastore, StacksConstants};
 use crate::initialize::initialize_contract;
+use crate::wasm_generator::{GeneratedModule, WasmGenerator};
+use crate::wasm_utils::call_function;
+use crate::{compile, compile_with_options, CompileError, CompileOptions, CompileResult};
 
 #[derive(Clone)]
 pub struct TestEnvironment {
@@ -254,11 +262,31 @@ impl TestEnvironment {
         &self.events
     }
 
+    /// Returns the data stored by the contracts, such as data-vars, map
+    /// entries and token balances, as raw key-value pairs.
+    pub fn data_snapshot(&self) -> BTreeMap<String, String> {
+        self.datastore.data_snapshot()
+    }
+
     pub fn advance_chain_tip(&mut self, count: u32) -> u32 {
         self.burn_datastore.advance_chain_tip(count);
         self.datastore.advance_chain_tip(count)
     }
 
+    /// Makes the simulated block times deterministic: the genesis block will
+    /// have time `ts`, and each following block 1800 seconds more than its
+    /// parent.
+    pub fn freeze_time(&mut self, ts: u128) {
+        let ts = u64::try_from(ts).expect("Frozen time must fit in a u64.");
+        self.burn_datastore.freeze_time(ts);
+    }
+
+    /// Sets the PoX payout addresses and payout of the burn block at `height`.
+    pub fn set_pox_payout_addrs(&mut self, height: u32, addrs: Vec<TupleData>, payout: u128) {
+        self.burn_datastore
+            .set_pox_payout_addrs(height, addrs, payout);
+    }
+
     pub fn interpret_contract_with_snippet(
         &mut self,
         contract_name: &str,
@@ -362,6 +390,147 @@ impl TestEnvironment {
     pub fn interpret(&mut self, snippet: &str) -> Result<Option<Value>, Error> {
         self.interpret_contract_with_snippet("snippet", snippet)
     }
+
+    /// Compiles and deploys `source` as the contract `name`, returning a handle
+    /// that can be used to call its functions afterwards.
+    pub fn deploy(&mut self, name: &str, source: &str) -> Result<ContractHandle, Error> {
+        self.init_contract_with_snippet(name, source)?;
+        Ok(ContractHandle {
+            name: name.to_string(),
+        })
+    }
+
+    /// Calls the public function `func` of a deployed contract. State changes
+    /// are committed, unless it returns an `err`, and calling a function
+    /// which is not public is an error.
+    pub fn call_public(
+        &mut self,
+        handle: &ContractHandle,
+        func: &str,
+        args: &[Value],
+    ) -> Result<Value, Error> {
+        let (contract_context, function) = self.deployed_function(handle, func)?;
+        if !function.is_public() || function.is_read_only() {
+            return Err(CheckErrors::NoSuchPublicFunction(
+                contract_context.contract_identifier.to_string(),
+                func.to_string(),
+            )
+            .into());
+        }
+
+        self.call_contract_function(handle, func, args, true)
+    }
+
+    /// Calls the read-only function `func` of a deployed contract. Any state
+    /// change is discarded, and calling a function which is not read-only is
+    /// an error.
+    pub fn call_read_only(
+        &mut self,
+        handle: &ContractHandle,
+        func: &str,
+        args: &[Value],
+    ) -> Result<Value, Error> {
+        let (contract_context, function) = self.deployed_function(handle, func)?;
+        if !function.is_read_only() {
+            return Err(CheckErrors::PublicFunctionNotReadOnly(
+                contract_context.contract_identifier.to_string(),
+                func.to_string(),
+            )
+            .into());
+        }
+
+        self.call_contract_function(handle, func, args, false)
+    }
+
+    /// Looks up the function `func` of a deployed contract.
+    fn deployed_function(
+        &self,
+        handle: &ContractHandle,
+        func: &str,
+    ) -> Result<(&ContractContext, &DefinedFunction), Error> {
+        let contract_context = self
+            .contract_contexts
+            .get(&handle.name)
+            .ok_or_else(|| CheckErrors::NoSuchContract(handle.name.clone()))?;
+        let function = contract_context
+            .lookup_function(func)
+            .ok_or_else(|| CheckErrors::UndefinedFunction(func.to_string()))?;
+        Ok((contract_context, function))
+    }
+
+    fn call_contract_function(
+        &mut self,
+        handle: &ContractHandle,
+        func: &str,
+        args: &[Value],
+        commit: bool,
+    ) -> Result<Value, Error> {
+        let contract_context = self
+            .contract_contexts
+            .get(&handle.name)
+            .ok_or_else(|| CheckErrors::NoSuchContract(handle.name.clone()))?
+            .clone();
+
+        let mut cost_tracker = LimitedCostTracker::new_free();
+        std::mem::swap(&mut self.cost_tracker, &mut cost_tracker);
+
+        let conn = ClarityDatabase::new(
+            &mut self.datastore,
+            &self.burn_datastore,
+            &self.burn_datastore,
+        );
+
+        let (is_mainnet, chain_id) = match self.network {
+            Network::Mainnet => (true, CHAIN_ID_MAINNET),
+            Network::Testnet => (false, CHAIN_ID_TESTNET),
+        };
+
+        let mut global_context =
+            GlobalContext::new(is_mainnet, chain_id, conn, cost_tracker, self.epoch);
+        global_context.begin();
+
+        let mut call_stack = CallStack::new();
+        let result = call_function(
+            func,
+            args,
+            &mut global_context,
+            &contract_context,
+            &mut call_stack,
+            Some(StandardPrincipalData::transient().into()),
+            Some(StandardPrincipalData::transient().into()),
+            None,
+        );
+
+        match &result {
+            Ok(Value::Response(response)) if commit && !response.committed => {
+                global_context.roll_back().expect("Failed to roll back.");
+            }
+            Ok(_) if commit => {
+                let (_, events) = global_context.commit().unwrap();
+                if let Some(events) = events {
+                    self.events.push(events);
+                }
+            }
+            _ => {
+                global_context.roll_back().expect("Failed to roll back.");
+            }
+        }
+        self.cost_tracker = global_context.cost_track;
+
+        result
+    }
+}
+
+/// A contract deployed in a [`TestEnvironment`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractHandle {
+    name: String,
+}
+
+impl ContractHandle {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 impl Default for TestEnvironment {
@@ -440,6 +609,204 @@ pub fn interpret(snippet: &str) -> Result<Option<Value>, Error> {
     interpret_at(snippet, StacksEpochId::latest(), ClarityVersion::latest())
 }
 
+/// Compiles a Clarity snippet at a specific epoch and version, and lists the
+/// instructions of its functions, each followed by the Clarity word which
+/// emitted it. The stdlib functions are not listed.
+pub fn disassemble_at(
+    snippet: &str,
+    epoch: StacksEpochId,
+    version: ClarityVersion,
+) -> Result<String, Error> {
+    let to_error = |e: String| Error::Wasm(WasmError::WasmGeneratorError(e));
+
+    let contract_analysis = compile_snippet_at(snippet, epoch, version)
+        .map_err(|e| to_error(format!("{e:?}")))?
+        .contract_analysis;
+
+    let mut generator =
+        WasmGenerator::new(contract_analysis).map_err(|e| to_error(e.to_string()))?;
+    generator.annotate_words = true;
+    let generated = generator.generate().map_err(|e| to_error(e.to_string()))?;
+
+    let mut listing = String::new();
+    for func in generated.module.funcs.iter() {
+        let FunctionKind::Local(local) = &func.kind else {
+            continue;
+        };
+        if generated.is_standard_function(func.id()) {
+            continue;
+        }
+        let name = func.name.as_deref().or_else(|| {
+            generated
+                .module
+                .exports
+                .get_exported_func(func.id())
+                .map(|export| export.name.as_str())
+        });
+        let _ = writeln!(listing, "{}:", name.unwrap_or("?"));
+        write_instructions(&generated, local, local.entry_block(), 1, &mut listing);
+    }
+    Ok(listing)
+}
+
+/// Compiles a Clarity snippet at the latest epoch and clarity version, and
+/// lists its instructions, see [`disassemble_at`].
+pub fn disassemble(snippet: &str) -> Result<String, Error> {
+    disassemble_at(snippet, StacksEpochId::latest(), ClarityVersion::latest())
+}
+
+/// Compiles a Clarity snippet at a specific epoch and version, as the
+/// transient contract.
+pub fn compile_snippet_at(
+    snippet: &str,
+    epoch: StacksEpochId,
+    version: ClarityVersion,
+) -> Result<CompileResult, CompileError> {
+    compile(
+        snippet,
+        &QualifiedContractIdentifier::transient(),
+        LimitedCostTracker::new_free(),
+        version,
+        epoch,
+        &mut MemoryBackingStore::new().as_analysis_db(),
+    )
+}
+
+/// Compiles a Clarity snippet in Clarity 2 at epoch 2.5, see
+/// [`compile_snippet_at`].
+pub fn compile_snippet(snippet: &str) -> Result<CompileResult, CompileError> {
+    compile_snippet_at(snippet, StacksEpochId::Epoch25, ClarityVersion::Clarity2)
+}
+
+/// Compiles a Clarity snippet as [`compile_snippet`] does, with `options`.
+pub fn compile_snippet_with_options(
+    snippet: &str,
+    options: &CompileOptions,
+) -> Result<CompileResult, CompileError> {
+    compile_with_options(
+        snippet,
+        &QualifiedContractIdentifier::transient(),
+        LimitedCostTracker::new_free(),
+        ClarityVersion::Clarity2,
+        StacksEpochId::Epoch25,
+        &mut MemoryBackingStore::new().as_analysis_db(),
+        options,
+    )
+}
+
+/// Compiles a Clarity snippet as [`compile_snippet`] does, and returns its
+/// analysis.
+pub fn compile_analysis(snippet: &str) -> ContractAnalysis {
+    compile_snippet(snippet)
+        .expect("Failed to compile the snippet.")
+        .contract_analysis
+}
+
+/// Instantiates `module` in a new store, with its imports trapping when
+/// called.
+pub fn instantiate(module: &mut Module) -> (wasmtime::Store<()>, wasmtime::Instance) {
+    instantiate_with_config(module, &wasmtime::Config::default())
+}
+
+/// Instantiates `module` as [`instantiate`] does, with an engine built from
+/// `config`.
+pub fn instantiate_with_config(
+    module: &mut Module,
+    config: &wasmtime::Config,
+) -> (wasmtime::Store<()>, wasmtime::Instance) {
+    let engine = wasmtime::Engine::new(config).expect("Failed to create the engine.");
+    let wasm_module =
+        wasmtime::Module::new(&engine, module.emit_wasm()).expect("Failed to load the module.");
+    let mut store = wasmtime::Store::new(&engine, ());
+    let mut linker = wasmtime::Linker::new(&engine);
+    linker
+        .define_unknown_imports_as_traps(&wasm_module)
+        .expect("Failed to define the imports.");
+    let instance = linker
+        .instantiate(&mut store, &wasm_module)
+        .expect("Failed to instantiate the module.");
+    (store, instance)
+}
+
+/// Lists the functions called by `func`, once per call.
+pub fn function_calls(func: &LocalFunction) -> Vec<FunctionId> {
+    struct Calls(Vec<FunctionId>);
+
+    impl<'instr> Visitor<'instr> for Calls {
+        fn visit_call(&mut self, instr: &Call) {
+            self.0.push(instr.func);
+        }
+    }
+
+    let mut calls = Calls(vec![]);
+    dfs_in_order(&mut calls, func, func.entry_block());
+    calls.0
+}
+
+/// Compiles a Clarity snippet, and returns the module with the functions
+/// called by its top-level function, see [`function_calls`].
+pub fn top_level_calls(snippet: &str) -> (Module, Vec<FunctionId>) {
+    let module = compile_snippet(snippet)
+        .expect("Failed to compile the snippet.")
+        .module;
+
+    let top_level = module
+        .exports
+        .get_func(".top-level")
+        .expect(".top-level should be exported");
+    let FunctionKind::Local(func) = &module.funcs.get(top_level).kind else {
+        panic!(".top-level should be a local function");
+    };
+    let calls = function_calls(func);
+    (module, calls)
+}
+
+fn write_instructions(
+    generated: &GeneratedModule,
+    func: &LocalFunction,
+    seq: InstrSeqId,
+    depth: usize,
+    listing: &mut String,
+) {
+    for (instr, loc) in &func.block(seq).instrs {
+        let text = match instr {
+            Instr::Call(Call { func }) => format!(
+                "call {}",
+                generated
+                    .module
+                    .funcs
+                    .get(*func)
+                    .name
+                    .as_deref()
+                    .unwrap_or("?")
+            ),
+            _ => format!("{instr:?}"),
+        };
+        let word = generated.word_at(loc).map_or("", |word| word.as_str());
+        let _ = writeln!(
+            listing,
+            "{:indent$}{text} ;; {word}",
+            "",
+            indent = depth * 2
+        );
+
+        match instr {
+            Instr::Block(Block { seq }) | Instr::Loop(Loop { seq }) => {
+                write_instructions(generated, func, *seq, depth + 1, listing);
+            }
+            Instr::IfElse(IfElse {
+                consequent,
+                alternative,
+            }) => {
+                write_instructions(generated, func, *consequent, depth + 1, listing);
+                let _ = writeln!(listing, "{:indent$}else", "", indent = depth * 2);
+                write_instructions(generated, func, *alternative, depth + 1, listing);
+            }
+            _ => {}
+        }
+    }
+}
+
 pub struct TestConfig;
 
 impl TestConfig {
@@ -575,6 +942,27 @@ pub fn crosscheck(snippet: &str, expected: Result<Option<Value>, Error>) {
     }
 }
 
+/// Same as [`crosscheck`], and also checks that the interpreter and the
+/// compiled contract leave the same data in the datastore.
+pub fn crosscheck_with_state(snippet: &str, expected: Result<Option<Value>, Error>) {
+    if let Some(eval) = execute_crosscheck(
+        TestEnvironment::new(TestConfig::latest_epoch(), TestConfig::clarity_version()),
+        snippet,
+        |_| {},
+    ) {
+        assert_eq!(
+            eval.compiled, expected,
+            "value is not the expected {:?}",
+            eval.compiled
+        );
+        assert_eq!(
+            eval.env_compiled.data_snapshot(),
+            eval.env_interpreted.data_snapshot(),
+            "Compiled and interpreted states diverge! {snippet}"
+        );
+    }
+}
+
 pub fn crosscheck_with_amount(snippet: &str, amount: u128, expected: Result<Option<Value>, Error>) {
     if let Some(eval) = execute_crosscheck(
         TestEnvironment::new_with_amount(
@@ -692,6 +1080,27 @@ pub fn crosscheck_with_epoch(
     }
 }
 
+/// Runs [`crosscheck_with_epoch`] in each of the listed epochs, expecting the
+/// result paired with the epoch.
+pub fn crosscheck_all_epochs(
+    snippet: &str,
+    expected_per_epoch: &[(StacksEpochId, Result<Option<Value>, Error>)],
+) {
+    for (epoch, expected) in expected_per_epoch {
+        if let Some(eval) = execute_crosscheck(
+            TestEnvironment::new(*epoch, TestConfig::clarity_version()),
+            snippet,
+            |_| {},
+        ) {
+            assert_eq!(
+                &eval.compiled, expected,
+                "value is not the expected {:?} in epoch {epoch}",
+                eval.compiled
+            );
+        }
+    }
+}
+
 pub fn crosscheck_with_clarity_version(
     snippet: &str,
     expected: Result<Option<Value>, Error>,
@@ -710,6 +1119,39 @@ pub fn crosscheck_with_clarity_version(
     }
 }
 
+/// Same as [`crosscheck`], but instead of comparing with an exact value,
+/// checks that the result of both the interpreter and the compiled contract
+/// satisfies `predicate`. Useful for values depending on the wall clock.
+pub fn crosscheck_approx<P: Fn(&Value) -> bool>(snippet: &str, predicate: P) {
+    crosscheck_approx_with_env(
+        snippet,
+        TestEnvironment::new(TestConfig::latest_epoch(), TestConfig::clarity_version()),
+        predicate,
+    );
+}
+
+/// Same as [`crosscheck_approx`], with a custom [`TestEnvironment`].
+pub fn crosscheck_approx_with_env<P: Fn(&Value) -> bool>(
+    snippet: &str,
+    env: TestEnvironment,
+    predicate: P,
+) {
+    execute_crosscheck(env, snippet, |result| {
+        for (engine, value) in [
+            ("interpreted", &result.interpreted),
+            ("compiled", &result.compiled),
+        ] {
+            match value {
+                Ok(Some(value)) => assert!(
+                    predicate(value),
+                    "{engine} value {value} does not satisfy the predicate"
+                ),
+                _ => panic!("{engine} snippet did not return a value: {value:?}"),
+            }
+        }
+    });
+}
+
 pub fn crosscheck_validate<V: Fn(Value)>(snippet: &str, validator: V) {
     if let Some(eval) = execute_crosscheck(
         TestEnvironment::new(TestConfig::latest_epoch(), TestConfig::clarity_version()),
@@ -868,6 +1310,110 @@ mod tests {
         assert_eq!(evaluate("(+ 1 2)"), Ok(Some(Value::Int(3))));
     }
 
+    #[test]
+    fn disassemble_attributes_words() {
+        let listing = disassemble_at(
+            "(+ u1 (unwrap-panic (get-block-info? time u0)))",
+            StacksEpochId::Epoch25,
+            ClarityVersion::Clarity2,
+        )
+        .unwrap();
+        assert!(listing.contains(".top-level:"), "{listing}");
+        assert!(
+            listing.lines().any(|line| line.ends_with(";; +")),
+            "{listing}"
+        );
+        assert!(
+            listing
+                .lines()
+                .any(|line| line.ends_with(";; get-block-info?")),
+            "{listing}"
+        );
+    }
+
+    #[test]
+    fn deploy_and_call_through_handle() {
+        let mut env = TestEnvironment::default();
+        let handle = env
+            .deploy(
+                "counter",
+                "(define-data-var count uint u41) (define-read-only (get-next) (+ (var-get count) u1))",
+            )
+            .expect("Failed to deploy contract.");
+
+        assert_eq!(handle.name(), "counter");
+        for _ in 0..2 {
+            assert_eq!(
+                env.call_read_only(&handle, "get-next", &[]),
+                Ok(Value::UInt(42))
+            );
+        }
+    }
+
+    #[test]
+    fn call_read_only_getter() {
+        let mut env = TestEnvironment::default();
+        let handle = env
+            .deploy(
+                "getter",
+                "(define-data-var value int 7) (define-read-only (get-value) (var-get value))",
+            )
+            .expect("Failed to deploy contract.");
+
+        assert_eq!(
+            env.call_read_only(&handle, "get-value", &[]),
+            Ok(Value::Int(7))
+        );
+    }
+
+    #[test]
+    fn call_read_only_rejects_public_function() {
+        let mut env = TestEnvironment::default();
+        let handle = env
+            .deploy(
+                "setter",
+                "(define-data-var value int 7) (define-public (set-value (v int)) (ok (var-set value v)))",
+            )
+            .expect("Failed to deploy contract.");
+
+        let err = env
+            .call_read_only(&handle, "set-value", &[Value::Int(8)])
+            .expect_err("Public function should be rejected.");
+        assert!(matches!(
+            err,
+            Error::Unchecked(CheckErrors::PublicFunctionNotReadOnly(_, f)) if f == "set-value"
+        ));
+    }
+
+    #[test]
+    fn call_public_rejects_read_only_function() {
+        let mut env = TestEnvironment::default();
+        let handle = env
+            .deploy(
+                "setter",
+                "(define-data-var value int 7)
+(define-public (set-value (v int)) (ok (var-set value v)))
+(define-read-only (get-value) (var-get value))",
+            )
+            .expect("Failed to deploy contract.");
+
+        assert_eq!(
+            env.call_public(&handle, "set-value", &[Value::Int(8)]),
+            Ok(Value::okay_true())
+        );
+        let err = env
+            .call_public(&handle, "get-value", &[])
+            .expect_err("Read-only function should be rejected.");
+        assert!(matches!(
+            err,
+            Error::Unchecked(CheckErrors::NoSuchPublicFunction(_, f)) if f == "get-value"
+        ));
+        assert_eq!(
+            env.call_read_only(&handle, "get-value", &[]),
+            Ok(Value::Int(8))
+        );
+    }
+
     #[cfg(not(feature = "test-clarity-v1"))]
     #[test]
     fn test_compare_events() {
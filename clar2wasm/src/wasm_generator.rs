@@ -2,37 +2,51 @@ use core::panic;
 use std::borrow::BorrowMut;
 use std::cell::RefCell;
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
 use std::ops::Deref;
 use std::rc::Rc;
 
 use clarity::vm::analysis::ContractAnalysis;
-use clarity::vm::diagnostic::DiagnosableError;
+use clarity::vm::diagnostic::{DiagnosableError, Diagnostic, Level};
 use clarity::vm::types::signatures::{CallableSubtype, StringUTF8Length, BUFF_1};
 use clarity::vm::types::{
-    ASCIIData, CharType, FixedFunction, FunctionType, ListTypeData, PrincipalData, SequenceData,
-    SequenceSubtype, StringSubtype, TupleTypeSignature, TypeSignature,
+    ASCIIData, BufferLength, CharType, FixedFunction, FunctionArg, FunctionSignature, FunctionType,
+    ListTypeData, PrincipalData, QualifiedContractIdentifier, SequenceData, SequenceSubtype,
+    StringSubtype, TraitIdentifier, TupleTypeSignature, TypeSignature,
 };
 use clarity::vm::variables::NativeVariables;
 use clarity::vm::{functions, variables, ClarityName, SymbolicExpression, SymbolicExpressionType};
 use walrus::ir::{
-    BinaryOp, IfElse, InstrSeqId, InstrSeqType, LoadKind, Loop, MemArg, StoreKind, UnaryOp,
+    dfs_in_order, dfs_pre_order_mut, BinaryOp, Binop, Block, Br, BrIf, BrTable, Call, Const,
+    ExtendedLoad, GlobalGet, GlobalSet, IfElse, Instr, InstrLocId, InstrSeq, InstrSeqId,
+    InstrSeqType, LoadKind, LocalGet, LocalSet, LocalTee, Loop, MemArg, RefNull, StoreKind,
+    UnaryOp, Visitor, VisitorMut,
 };
 use walrus::{
-    ActiveData, DataKind, FunctionBuilder, FunctionId, GlobalId, InstrSeqBuilder, LocalId,
-    MemoryId, Module, ValType,
+    ActiveData, DataKind, FunctionBuilder, FunctionId, GlobalId, InstrSeqBuilder, LocalFunction,
+    LocalId, MemoryId, Module, ModuleLocals, RawCustomSection, ValType,
 };
 
 use crate::error_mapping::ErrorMap;
 use crate::wasm_utils::{
     check_argument_count, get_type_in_memory_size, get_type_size, signature_from_string,
-    ArgumentCountCheck, PRINCIPAL_BYTES_MAX,
+    ArgumentCountCheck, Arity, PRINCIPAL_BYTES_MAX,
 };
 use crate::{check_args, debug_msg, words};
 
 // First free position after data directly defined in standard.wat
 pub const END_OF_STANDARD_DATA: u32 = 1352;
 
+// Name of the custom section holding the SHA256 of the Clarity source and the
+// version of the compiler.
+pub const SOURCE_HASH_SECTION: &str = "clarity.srchash";
+
+// Instruction location tags from this value are indices of word tags. The
+// instructions parsed from standard.wasm are tagged with their much smaller
+// code offsets.
+const WORD_TAG_BASE: u32 = 0x8000_0000;
+
 /// WasmGenerator is a Clarity AST visitor that generates a WebAssembly module
 /// as it traverses the AST.
 pub struct WasmGenerator {
@@ -51,23 +65,197 @@ pub struct WasmGenerator {
     pub(crate) constants: HashMap<String, u32>,
     /// The current function body block, used for early exit
     early_return_block_id: Option<InstrSeqId>,
-    /// The type of the current function.
-    pub(crate) current_function_type: Option<FixedFunction>,
+    /// The functions whose body is being generated, innermost last, see
+    /// [`Self::current_function`].
+    function_stack: Vec<FunctionContext>,
     /// The types of defined data-vars
     pub(crate) datavars_types: HashMap<ClarityName, TypeSignature>,
     /// The types of (key, value) in defined maps
     pub(crate) maps_types: HashMap<ClarityName, (TypeSignature, TypeSignature)>,
     /// The type of defined NFTs
     pub(crate) nft_types: HashMap<ClarityName, TypeSignature>,
+    /// The method signatures of defined traits
+    pub(crate) traits_types: HashMap<ClarityName, BTreeMap<ClarityName, FunctionSignature>>,
+    /// The method signatures of the traits implemented with `impl-trait`
+    pub(crate) implemented_traits:
+        HashMap<TraitIdentifier, BTreeMap<ClarityName, FunctionSignature>>,
+    /// The signatures of the public and read-only functions called with a
+    /// static `contract-call?`, by contract and function name
+    pub(crate) contract_call_signatures:
+        HashMap<(QualifiedContractIdentifier, ClarityName), FunctionSignature>,
 
     /// The locals for the current function.
     pub(crate) bindings: Bindings,
     /// Size of the current function's stack frame.
     frame_size: i32,
+    /// Size of the frame of the function being generated, checked once in
+    /// its prelude, see [`Self::emit_ensure_stack_memory`].
+    function_frame_size: i32,
+    /// Size and alignment of the call stack space reserved for each local
+    /// created with `create_call_stack_local`.
+    call_stack_locals: HashMap<LocalId, (i32, i32)>,
     /// Size of the maximum extra work space required by the stdlib functions
     /// to be available on the stack.
     max_work_space: u32,
     local_pool: Rc<RefCell<HashMap<ValType, Vec<LocalId>>>>,
+    /// Function ids already resolved by `func_by_name`, keyed by name.
+    func_ids: RefCell<HashMap<String, FunctionId>>,
+    /// Warnings collected during the traversal.
+    diagnostics: Vec<Diagnostic>,
+    /// Inline the small stdlib functions at their call sites.
+    pub(crate) inline_stdlib: bool,
+    /// Merge the generated functions with identical bodies, see
+    /// [`Self::merge_duplicate_functions`].
+    pub(crate) deduplicate_functions: bool,
+    /// The WebAssembly features that the generated code may use.
+    pub(crate) features: WasmFeatures,
+    /// Export an entry wrapper for each public function, see
+    /// [`Self::add_entry_wrapper`].
+    pub(crate) entry_wrappers: bool,
+    /// SHA256 of the Clarity source, embedded in the [`SOURCE_HASH_SECTION`]
+    /// custom section when set.
+    pub(crate) source_hash: Option<[u8; 32]>,
+    /// The maximum size of the call stack, see `CompileOptions`.
+    pub(crate) max_call_stack: Option<u32>,
+    /// The maximum number of instructions, see `CompileOptions`.
+    pub(crate) instruction_budget: Option<usize>,
+    /// Number of instructions in the functions finished so far, counted
+    /// only with an instruction budget.
+    finished_instructions: usize,
+    /// Record the memory layout of each function, see
+    /// [`Self::memory_layout`].
+    pub(crate) record_memory_layouts: bool,
+    /// Name of the function whose memory placements are being recorded.
+    layout_function: String,
+    /// The literals and call stack locals used by each function.
+    memory_layouts: HashMap<String, FunctionLayout>,
+    /// The functions of the standard library, including the host imports.
+    standard_functions: HashSet<FunctionId>,
+    /// Tag the instructions with the word that emitted them.
+    pub(crate) annotate_words: bool,
+    /// The words of the instruction tags, see [`GeneratedModule::word_at`].
+    word_tags: Vec<ClarityName>,
+    /// The word of each list traversed, with the function it was traversed
+    /// in.
+    #[cfg(test)]
+    traversed_contexts: Vec<(ClarityName, Option<FunctionContext>)>,
+}
+
+/// The WebAssembly proposals that the generated code may use, for runtimes
+/// which don't support all of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmFeatures {
+    /// Use the bulk memory instructions. Without them, every `memory.copy`
+    /// and `memory.fill`, including those of the stdlib, is replaced with a
+    /// loop.
+    pub bulk_memory: bool,
+}
+
+impl Default for WasmFeatures {
+    fn default() -> Self {
+        Self { bulk_memory: true }
+    }
+}
+
+/// A value placed in the linear memory by a function, as listed in
+/// [`WasmGenerator::memory_layout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryLayoutEntry {
+    /// The part of the memory holding the value.
+    pub region: MemoryRegion,
+    /// Offset of the value. Literals have an absolute offset, while call stack
+    /// locals are relative to the frame pointer of the function.
+    pub offset: u32,
+    /// Size of the value in bytes.
+    pub size: u32,
+    /// Description of the value.
+    pub description: String,
+}
+
+/// The part of the linear memory holding a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryRegion {
+    /// The data literals, placed before the call stack.
+    Literal,
+    /// The call stack frame of the function.
+    CallStack,
+}
+
+/// Statistics about the generated code, as returned by
+/// [`WasmGenerator::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CodegenStats {
+    /// Number of instructions in the functions generated from the contract,
+    /// excluding the stdlib functions.
+    pub instructions: usize,
+    /// Number of bytes reserved on the call stack.
+    pub stack_bytes: u32,
+    /// Number of bytes in the data segments.
+    pub data_bytes: usize,
+    /// Number of calls to stdlib functions from the generated functions.
+    pub stdlib_calls: usize,
+}
+
+/// The literals and call stack locals used by a function.
+#[derive(Debug, Default)]
+struct FunctionLayout {
+    /// The records, in order.
+    records: Vec<LayoutRecord>,
+    /// The index in `records` of each literal, by offset.
+    literals: HashMap<u32, usize>,
+}
+
+#[derive(Debug, Clone)]
+enum LayoutRecord {
+    Literal {
+        offset: u32,
+        size: u32,
+        description: String,
+    },
+    CallStackLocal {
+        local: LocalId,
+        ty: TypeSignature,
+        include_repr: bool,
+    },
+}
+
+/// The result of a successful generation.
+#[derive(Debug)]
+pub struct GeneratedModule {
+    /// The generated WebAssembly module.
+    pub module: Module,
+    /// The type of the value returned by the top-level expressions, if any.
+    pub top_level_type: Option<TypeSignature>,
+    /// Warnings collected during the traversal.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The memory layout of each function, keyed by name, when the generator
+    /// records them. The top-level expressions are listed as `.top-level`.
+    pub memory_layouts: HashMap<String, Vec<MemoryLayoutEntry>>,
+    /// Statistics about the finalized module.
+    pub stats: CodegenStats,
+    /// The words of the instruction tags, when the generator annotates the
+    /// instructions with their words.
+    word_tags: Vec<ClarityName>,
+    /// The functions of the standard library, including the host imports.
+    standard_functions: HashSet<FunctionId>,
+}
+
+impl GeneratedModule {
+    /// Returns the word that emitted the instruction at `loc`, if the
+    /// instructions were annotated with their words.
+    pub fn word_at(&self, loc: &InstrLocId) -> Option<&ClarityName> {
+        if loc.is_default() {
+            return None;
+        }
+        let index = loc.data().checked_sub(WORD_TAG_BASE)?;
+        self.word_tags.get(index as usize)
+    }
+
+    /// Returns whether `func` comes from the standard library, rather than
+    /// from the contract.
+    pub fn is_standard_function(&self, func: FunctionId) -> bool {
+        self.standard_functions.contains(&func)
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -77,6 +265,8 @@ pub(crate) struct Bindings(HashMap<ClarityName, InnerBindings>);
 struct InnerBindings {
     locals: Vec<LocalId>,
     ty: TypeSignature,
+    /// The value of a binding known at compile time, which needs no locals.
+    constant: Option<clarity::vm::Value>,
 }
 
 impl Bindings {
@@ -85,7 +275,36 @@ impl Bindings {
     }
 
     pub(crate) fn insert(&mut self, name: ClarityName, ty: TypeSignature, locals: Vec<LocalId>) {
-        self.0.insert(name, InnerBindings { locals, ty });
+        self.0.insert(
+            name,
+            InnerBindings {
+                locals,
+                ty,
+                constant: None,
+            },
+        );
+    }
+
+    /// Binds `name` to a value known at compile time, so that its uses can be
+    /// folded like literals.
+    pub(crate) fn insert_constant(
+        &mut self,
+        name: ClarityName,
+        ty: TypeSignature,
+        value: clarity::vm::Value,
+    ) {
+        self.0.insert(
+            name,
+            InnerBindings {
+                locals: vec![],
+                ty,
+                constant: Some(value),
+            },
+        );
+    }
+
+    pub(crate) fn get_constant(&self, name: &ClarityName) -> Option<&clarity::vm::Value> {
+        self.0.get(name).and_then(|b| b.constant.as_ref())
     }
 
     pub(crate) fn contains(&mut self, name: &ClarityName) -> bool {
@@ -117,6 +336,18 @@ pub enum GeneratorError {
     InternalError(String),
     TypeError(String),
     ArgumentCountMismatch,
+    /// A word was called with a number of arguments it does not accept.
+    ArityMismatch {
+        name: String,
+        expected: Arity,
+        got: usize,
+    },
+    /// The generated code has more instructions than the budget, see
+    /// `CompileOptions::instruction_budget`.
+    InstructionBudgetExceeded {
+        instructions: usize,
+        budget: usize,
+    },
 }
 
 pub enum FunctionKind {
@@ -125,6 +356,15 @@ pub enum FunctionKind {
     ReadOnly,
 }
 
+/// The function whose body is being generated.
+#[derive(Debug, Clone)]
+pub struct FunctionContext {
+    pub name: ClarityName,
+    /// The declared return type, used by the early returns of the body.
+    pub returns: TypeSignature,
+    pub args: Vec<FunctionArg>,
+}
+
 impl DiagnosableError for GeneratorError {
     fn message(&self) -> String {
         match self {
@@ -132,6 +372,15 @@ impl DiagnosableError for GeneratorError {
             GeneratorError::InternalError(msg) => format!("Internal error: {}", msg),
             GeneratorError::TypeError(msg) => format!("Type error: {}", msg),
             GeneratorError::ArgumentCountMismatch => "Argument count mismatch".to_string(),
+            GeneratorError::ArityMismatch {
+                name,
+                expected,
+                got,
+            } => format!("{name}: expecting {expected} arguments, got {got}"),
+            GeneratorError::InstructionBudgetExceeded {
+                instructions,
+                budget,
+            } => format!("{instructions} instructions generated, exceeding the budget of {budget}"),
         }
     }
 
@@ -140,6 +389,30 @@ impl DiagnosableError for GeneratorError {
     }
 }
 
+impl GeneratorError {
+    /// A stable identifier of the error category, for tools consuming the
+    /// compiler's errors.
+    pub fn code(&self) -> &'static str {
+        match self {
+            GeneratorError::NotImplemented => "E_NOT_IMPLEMENTED",
+            GeneratorError::InternalError(_) => "E_INTERNAL",
+            GeneratorError::TypeError(_) => "E_TYPE",
+            GeneratorError::ArgumentCountMismatch | GeneratorError::ArityMismatch { .. } => {
+                "E_ARGUMENT_COUNT"
+            }
+            GeneratorError::InstructionBudgetExceeded { .. } => "E_INSTRUCTION_BUDGET",
+        }
+    }
+}
+
+impl std::fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for GeneratorError {}
+
 pub trait ArgumentsExt {
     fn get_expr(&self, n: usize) -> Result<&SymbolicExpression, GeneratorError>;
     fn get_name(&self, n: usize) -> Result<&ClarityName, GeneratorError>;
@@ -241,7 +514,84 @@ pub enum SequenceElementType {
     Other(TypeSignature),
 }
 
-/// Drop a value of type `ty` from the data stack.
+/// Lists the fields of the tuples in the representation of a value of type
+/// `ty` written at `offset`, naming them by their path from `path`.
+fn push_fields_layout(
+    entries: &mut Vec<MemoryLayoutEntry>,
+    ty: &TypeSignature,
+    offset: u32,
+    path: &str,
+) {
+    match ty {
+        TypeSignature::TupleType(tuple) => {
+            let mut field_offset = offset;
+            for (name, field_ty) in tuple.get_type_map() {
+                let path = if path.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{path}.{name}")
+                };
+                let size = get_type_size(field_ty) as u32;
+                entries.push(MemoryLayoutEntry {
+                    region: MemoryRegion::CallStack,
+                    offset: field_offset,
+                    size,
+                    description: format!("field {path} of type {field_ty}"),
+                });
+                push_fields_layout(entries, field_ty, field_offset, &path);
+                field_offset += size;
+            }
+        }
+        // The variant indicator comes before the inner values.
+        TypeSignature::OptionalType(inner) => push_fields_layout(entries, inner, offset + 4, path),
+        TypeSignature::ResponseType(inner) => {
+            push_fields_layout(entries, &inner.0, offset + 4, path);
+            let err_offset = offset + 4 + get_type_size(&inner.0) as u32;
+            push_fields_layout(entries, &inner.1, err_offset, path);
+        }
+        _ => {}
+    }
+}
+
+/// Returns true if the last instruction of `builder` never falls through,
+/// which makes the instructions following it in the sequence unreachable.
+pub(crate) fn ends_in_divergence(builder: &InstrSeqBuilder) -> bool {
+    matches!(
+        builder.instrs().last(),
+        Some((
+            Instr::Unreachable(_) | Instr::Br(_) | Instr::BrTable(_) | Instr::Return(_),
+            _
+        ))
+    )
+}
+
+/// Alignment of the call stack space reserved for a value of type `ty`: 8
+/// bytes if it holds 64-bit integers, which are read with `i64.load`, and 4
+/// bytes otherwise.
+pub(crate) fn call_stack_alignment(ty: &TypeSignature) -> i32 {
+    fn has_i64(ty: &TypeSignature) -> bool {
+        match ty {
+            TypeSignature::IntType | TypeSignature::UIntType => true,
+            TypeSignature::OptionalType(some) => has_i64(some),
+            TypeSignature::ResponseType(ok_err) => has_i64(&ok_err.0) || has_i64(&ok_err.1),
+            TypeSignature::TupleType(tuple) => tuple.get_type_map().values().any(has_i64),
+            TypeSignature::SequenceType(SequenceSubtype::ListType(list)) => {
+                has_i64(list.get_list_item_type())
+            }
+            _ => false,
+        }
+    }
+
+    if has_i64(ty) {
+        8
+    } else {
+        4
+    }
+}
+
+/// Drop a value of type `ty` from the data stack. A value may take several
+/// slots on the stack, e.g. an `int` takes two `i64`s and a tuple takes the
+/// slots of all its fields, and each of them is dropped.
 pub(crate) fn drop_value(builder: &mut InstrSeqBuilder, ty: &TypeSignature) {
     let wasm_types = clar2wasm_ty(ty);
     (0..wasm_types.len()).for_each(|_| {
@@ -311,6 +661,7 @@ impl WasmGenerator {
         })?;
         // Get the stack-pointer global ID
         let global_id = get_global(&module, "stack-pointer")?;
+        let standard_functions = module.funcs.iter().map(|func| func.id()).collect();
 
         Ok(WasmGenerator {
             contract_analysis,
@@ -321,13 +672,36 @@ impl WasmGenerator {
             constants: HashMap::new(),
             bindings: Bindings::new(),
             early_return_block_id: None,
-            current_function_type: None,
+            function_stack: Vec::new(),
             frame_size: 0,
+            function_frame_size: 0,
+            call_stack_locals: HashMap::new(),
             max_work_space: 0,
             datavars_types: HashMap::new(),
             maps_types: HashMap::new(),
             local_pool: Rc::new(RefCell::new(HashMap::new())),
+            func_ids: RefCell::new(HashMap::new()),
             nft_types: HashMap::new(),
+            traits_types: HashMap::new(),
+            implemented_traits: HashMap::new(),
+            contract_call_signatures: HashMap::new(),
+            diagnostics: vec![],
+            inline_stdlib: false,
+            deduplicate_functions: false,
+            features: WasmFeatures::default(),
+            entry_wrappers: false,
+            source_hash: None,
+            max_call_stack: None,
+            instruction_budget: None,
+            finished_instructions: 0,
+            layout_function: ".top-level".to_owned(),
+            record_memory_layouts: false,
+            memory_layouts: HashMap::new(),
+            standard_functions,
+            annotate_words: false,
+            word_tags: vec![],
+            #[cfg(test)]
+            traversed_contexts: vec![],
         })
     }
 
@@ -349,29 +723,64 @@ impl WasmGenerator {
         Ok(())
     }
 
-    pub fn generate(mut self) -> Result<Module, GeneratorError> {
+    pub fn generate(mut self) -> Result<GeneratedModule, GeneratorError> {
         let expressions = std::mem::take(&mut self.contract_analysis.expressions);
 
         // Get the type of the last top-level expression with a return value
         // or default to `None`.
-        let return_ty = expressions
+        let top_level_type = expressions
             .iter()
             .rev()
             .find_map(|expr| self.get_expr_type(expr))
-            .map_or_else(Vec::new, clar2wasm_ty);
+            .cloned();
+        let return_ty = top_level_type.as_ref().map_or_else(Vec::new, clar2wasm_ty);
 
         let mut current_function = FunctionBuilder::new(&mut self.module.types, &[], &return_ty);
 
         if !expressions.is_empty() {
             self.traverse_statement_list(&mut current_function.func_body(), &expressions)?;
         }
+        let frame_size = std::mem::take(&mut self.function_frame_size);
+        self.emit_ensure_stack_memory(&mut current_function.func_body(), 0, frame_size);
 
         self.contract_analysis.expressions = expressions;
 
         let top_level = current_function.finish(vec![], &mut self.module.funcs);
+        self.finish_instruction_budget(top_level)?;
         self.module.exports.add(".top-level", top_level);
 
+        if self.entry_wrappers {
+            self.add_entry_wrappers()?;
+        }
+
+        self.eliminate_dead_call_stack_locals();
+        if self.deduplicate_functions {
+            self.merge_duplicate_functions();
+        }
+        if self.inline_stdlib {
+            self.inline_small_stdlib_functions();
+        }
+        if !self.features.bulk_memory {
+            self.lower_bulk_memory()?;
+        }
         self.set_memory_pages()?;
+        self.add_source_hash_section();
+
+        // The memory grown for the call stack must also hold the work space
+        // of the stdlib functions.
+        let work_space = get_global(&self.module, "work-space")?;
+        self.module.globals.get_mut(work_space).kind = walrus::GlobalKind::Local(
+            walrus::InitExpr::Value(walrus::ir::Value::I32(self.max_work_space as i32)),
+        );
+
+        // The call stack starts after the literal memory.
+        if let Some(max_call_stack) = self.max_call_stack {
+            let stack_limit = get_global(&self.module, "stack-limit")?;
+            let limit = self.literal_memory_end.saturating_add(max_call_stack);
+            self.module.globals.get_mut(stack_limit).kind = walrus::GlobalKind::Local(
+                walrus::InitExpr::Value(walrus::ir::Value::I32(limit as i32)),
+            );
+        }
 
         // Update the initial value of the stack-pointer to point beyond the
         // literal memory.
@@ -379,7 +788,219 @@ impl WasmGenerator {
             walrus::InitExpr::Value(walrus::ir::Value::I32(self.literal_memory_end as i32)),
         );
 
-        Ok(self.module)
+        let memory_layouts = self
+            .memory_layouts
+            .keys()
+            .map(|function| (function.clone(), self.memory_layout(function)))
+            .collect();
+
+        let stats = self.stats();
+
+        Ok(GeneratedModule {
+            module: self.module,
+            top_level_type,
+            diagnostics: self.diagnostics,
+            memory_layouts,
+            stats,
+            word_tags: self.word_tags,
+            standard_functions: self.standard_functions,
+        })
+    }
+
+    /// Embeds the source hash, followed by the compiler version, in a
+    /// [`SOURCE_HASH_SECTION`] custom section, if a hash was provided.
+    fn add_source_hash_section(&mut self) {
+        if let Some(hash) = self.source_hash {
+            let mut data = hash.to_vec();
+            data.extend_from_slice(env!("CARGO_PKG_VERSION").as_bytes());
+            self.module.customs.add(RawCustomSection {
+                name: SOURCE_HASH_SECTION.to_owned(),
+                data,
+            });
+        }
+    }
+
+    /// Adds the instructions of the finished function `func` to the running
+    /// count, and checks the budget.
+    fn finish_instruction_budget(&mut self, func: FunctionId) -> Result<(), GeneratorError> {
+        #[derive(Default)]
+        struct Counter(usize);
+
+        impl<'instr> Visitor<'instr> for Counter {
+            fn visit_instr(&mut self, _instr: &'instr Instr, _instr_loc: &'instr InstrLocId) {
+                self.0 += 1;
+            }
+        }
+
+        if self.instruction_budget.is_none() {
+            return Ok(());
+        }
+        if let walrus::FunctionKind::Local(local) = &self.module.funcs.get(func).kind {
+            let mut counter = Counter::default();
+            dfs_in_order(&mut counter, local, local.entry_block());
+            self.finished_instructions += counter.0;
+        }
+        self.check_instruction_budget(0)
+    }
+
+    /// Returns an error if the functions finished so far, with `pending`
+    /// instructions of the function being generated, have more instructions
+    /// than the budget. Checked as each function is finished and as each
+    /// expression is traversed, so that a pathological contract stops before
+    /// the whole module is generated.
+    fn check_instruction_budget(&self, pending: usize) -> Result<(), GeneratorError> {
+        let Some(budget) = self.instruction_budget else {
+            return Ok(());
+        };
+        let instructions = self.finished_instructions + pending;
+        if instructions > budget {
+            return Err(GeneratorError::InstructionBudgetExceeded {
+                instructions,
+                budget,
+            });
+        }
+        Ok(())
+    }
+
+    /// Counts the instructions, stdlib calls and reserved memory of the code
+    /// generated so far. The counts are final once the module is generated,
+    /// see [`GeneratedModule::stats`].
+    pub fn stats(&self) -> CodegenStats {
+        struct Counter<'a> {
+            stdlib: &'a HashSet<FunctionId>,
+            instructions: usize,
+            stdlib_calls: usize,
+        }
+
+        impl<'instr> Visitor<'instr> for Counter<'_> {
+            fn visit_instr(&mut self, _instr: &'instr Instr, _instr_loc: &'instr InstrLocId) {
+                self.instructions += 1;
+            }
+
+            fn visit_call(&mut self, instr: &Call) {
+                if self.stdlib.contains(&instr.func) {
+                    self.stdlib_calls += 1;
+                }
+            }
+        }
+
+        let mut counter = Counter {
+            stdlib: &self.standard_functions,
+            instructions: 0,
+            stdlib_calls: 0,
+        };
+        for func in self
+            .module
+            .funcs
+            .iter()
+            .filter(|func| !self.standard_functions.contains(&func.id()))
+        {
+            if let walrus::FunctionKind::Local(local) = &func.kind {
+                dfs_in_order(&mut counter, local, local.entry_block());
+            }
+        }
+
+        CodegenStats {
+            instructions: counter.instructions,
+            stack_bytes: self.frame_size.max(0) as u32,
+            data_bytes: self.module.data.iter().map(|data| data.value.len()).sum(),
+            stdlib_calls: counter.stdlib_calls,
+        }
+    }
+
+    /// Describes where the literals and call stack locals used by `function`
+    /// were placed in the linear memory, with the offsets of the tuple fields
+    /// in the locals holding a value representation.
+    ///
+    /// The call stack locals are listed in the order of their reservation,
+    /// which is the order of allocation when every branch is taken.
+    pub fn memory_layout(&self, function: &str) -> Vec<MemoryLayoutEntry> {
+        let mut entries = Vec::new();
+        let mut frame_offset = 0;
+        let records = self
+            .memory_layouts
+            .get(function)
+            .map_or(&[][..], |layout| &layout.records);
+        for record in records {
+            match record {
+                LayoutRecord::Literal {
+                    offset,
+                    size,
+                    description,
+                } => entries.push(MemoryLayoutEntry {
+                    region: MemoryRegion::Literal,
+                    offset: *offset,
+                    size: *size,
+                    description: description.clone(),
+                }),
+                LayoutRecord::CallStackLocal {
+                    local,
+                    ty,
+                    include_repr,
+                } => {
+                    // Dead reservations were removed from the frame.
+                    let Some(&(size, align)) = self.call_stack_locals.get(local) else {
+                        continue;
+                    };
+                    // The frame pointer is assumed to be aligned too.
+                    let align = align as u32;
+                    frame_offset = frame_offset.div_ceil(align) * align;
+                    entries.push(MemoryLayoutEntry {
+                        region: MemoryRegion::CallStack,
+                        offset: frame_offset,
+                        size: size as u32,
+                        description: format!("local of type {ty}"),
+                    });
+                    if *include_repr {
+                        push_fields_layout(&mut entries, ty, frame_offset, "");
+                    }
+                    frame_offset += size as u32;
+                }
+            }
+        }
+        entries
+    }
+
+    /// Records that the current function uses the literal at `offset`, and
+    /// returns its offset and size.
+    fn record_literal(&mut self, offset: u32, size: u32, description: String) -> (u32, u32) {
+        if self.record_memory_layouts {
+            let layout = self
+                .memory_layouts
+                .entry(self.layout_function.clone())
+                .or_default();
+            if let Entry::Vacant(entry) = layout.literals.entry(offset) {
+                entry.insert(layout.records.len());
+                layout.records.push(LayoutRecord::Literal {
+                    offset,
+                    size,
+                    description,
+                });
+            }
+        }
+        (offset, size)
+    }
+
+    /// Records a warning about `expr`, which will be part of the compilation
+    /// diagnostics.
+    pub(crate) fn warn(&mut self, expr: &SymbolicExpression, message: String) {
+        self.diagnostics.push(Diagnostic {
+            level: Level::Warning,
+            message,
+            spans: vec![expr.span().clone()],
+            suggestion: None,
+        });
+    }
+
+    /// Records a warning which can't be attributed to an expression, e.g. in
+    /// a simple word, which only knows the types of its operands.
+    pub(crate) fn warn_unattributed(&mut self, message: String) {
+        self.diagnostics.push(Diagnostic {
+            level: Level::Warning,
+            message,
+            spans: vec![],
+            suggestion: None,
+        });
     }
 
     pub fn get_memory(&self) -> Result<MemoryId, GeneratorError> {
@@ -397,6 +1018,9 @@ impl WasmGenerator {
         builder: &mut InstrSeqBuilder,
         expr: &SymbolicExpression,
     ) -> Result<(), GeneratorError> {
+        #[cfg(debug_assertions)]
+        let start = builder.instrs().len();
+
         match &expr.expr {
             SymbolicExpressionType::Atom(name) => self.visit_atom(builder, expr, name),
             SymbolicExpressionType::List(exprs) => self.traverse_list(builder, expr, exprs),
@@ -404,7 +1028,125 @@ impl WasmGenerator {
                 self.visit_literal_value(builder, expr, value)
             }
             _ => Ok(()),
+        }?;
+
+        #[cfg(debug_assertions)]
+        self.debug_assert_stack_shape(builder, start, expr);
+
+        // Only the instructions of the sequence being built are counted, the
+        // nested blocks are counted with the whole function.
+        self.check_instruction_budget(builder.instrs().len())
+    }
+
+    /// Checks that the instructions emitted for `expr`, starting at index
+    /// `start` of `builder`, leave as many values on the stack as its type
+    /// needs. The check is skipped when the expression has no type, or when
+    /// the stack height can't be determined (e.g. the code diverges).
+    #[cfg(debug_assertions)]
+    pub(crate) fn debug_assert_stack_shape(
+        &self,
+        builder: &mut InstrSeqBuilder,
+        start: usize,
+        expr: &SymbolicExpression,
+    ) {
+        let Some(ty) = self.get_expr_type(expr) else {
+            return;
+        };
+        let seq = builder.id();
+        if let Some(delta) = self.stack_delta(builder, seq, start) {
+            let expected = clar2wasm_ty(ty).len() as isize;
+            assert_eq!(
+                delta, expected,
+                "expression {expr} of type {ty} leaves {delta} values on the stack instead of {expected}"
+            );
+        }
+    }
+
+    /// Computes the net number of values pushed onto the stack by the
+    /// instructions of `seq` from index `start`, or `None` if it can't be
+    /// determined.
+    #[cfg(debug_assertions)]
+    fn stack_delta(
+        &self,
+        builder: &mut InstrSeqBuilder,
+        seq: InstrSeqId,
+        start: usize,
+    ) -> Option<isize> {
+        let instrs: Vec<Instr> = builder.instr_seq(seq).instrs()[start..]
+            .iter()
+            .map(|(instr, _)| instr.clone())
+            .collect();
+
+        let mut delta = 0;
+        for instr in instrs {
+            delta += match instr {
+                Instr::Block(Block { seq }) | Instr::Loop(Loop { seq }) => {
+                    self.stack_delta(builder, seq, 0)?
+                }
+                Instr::IfElse(IfElse {
+                    consequent,
+                    alternative,
+                }) => {
+                    // Either branch gives the stack height of the `if`, unless
+                    // it diverges.
+                    let branch = self
+                        .stack_delta(builder, consequent, 0)
+                        .or_else(|| self.stack_delta(builder, alternative, 0))?;
+                    branch - 1
+                }
+                Instr::Call(Call { func }) => {
+                    let ty = self.module.types.get(self.module.funcs.get(func).ty());
+                    ty.results().len() as isize - ty.params().len() as isize
+                }
+                Instr::CallIndirect(call) => {
+                    let ty = self.module.types.get(call.ty);
+                    ty.results().len() as isize - ty.params().len() as isize - 1
+                }
+                Instr::LocalGet(_)
+                | Instr::GlobalGet(_)
+                | Instr::Const(_)
+                | Instr::MemorySize(_)
+                | Instr::TableSize(_)
+                | Instr::RefNull(_)
+                | Instr::RefFunc(_) => 1,
+                Instr::LocalTee(_)
+                | Instr::Unop(_)
+                | Instr::Load(_)
+                | Instr::MemoryGrow(_)
+                | Instr::TableGet(_)
+                | Instr::RefIsNull(_)
+                | Instr::DataDrop(_)
+                | Instr::ElemDrop(_)
+                | Instr::AtomicFence(_) => 0,
+                Instr::LocalSet(_)
+                | Instr::GlobalSet(_)
+                | Instr::Drop(_)
+                | Instr::BrIf(_)
+                | Instr::Binop(_)
+                | Instr::TableGrow(_)
+                | Instr::AtomicRmw(_)
+                | Instr::AtomicNotify(_)
+                | Instr::I8x16Swizzle(_)
+                | Instr::I8x16Shuffle(_) => -1,
+                Instr::Store(_)
+                | Instr::Select(_)
+                | Instr::TableSet(_)
+                | Instr::Cmpxchg(_)
+                | Instr::AtomicWait(_)
+                | Instr::V128Bitselect(_) => -2,
+                Instr::MemoryCopy(_)
+                | Instr::MemoryFill(_)
+                | Instr::MemoryInit(_)
+                | Instr::TableFill(_)
+                | Instr::TableInit(_)
+                | Instr::TableCopy(_) => -3,
+                // Diverging instructions make the rest of the sequence
+                // unreachable, and SIMD loads depend on their kind.
+                _ => return None,
+            };
         }
+
+        Some(delta)
     }
 
     fn traverse_list(
@@ -421,6 +1163,12 @@ impl WasmGenerator {
                 },
                 args,
             )) => {
+                let start = builder.instrs().len();
+
+                #[cfg(test)]
+                self.traversed_contexts
+                    .push((function_name.clone(), self.current_function().cloned()));
+
                 // Extract the types from the args and return
                 let get_types = || {
                     let arg_types: Result<Vec<TypeSignature>, GeneratorError> = args
@@ -444,8 +1192,10 @@ impl WasmGenerator {
                 // since we need to have a slight overlap for the words `and` and `or`
                 // which exist in both complex and simple forms
                 if let Some(word) = words::lookup_complex(function_name) {
+                    word.arity().check_minimum(function_name, args.len())?;
                     word.traverse(self, builder, expr, args)?;
                 } else if let Some(simpleword) = words::lookup_simple(function_name) {
+                    simpleword.arity().check(function_name, args.len())?;
                     let (arg_types, return_type) = get_types()?;
 
                     // traverse arguments
@@ -455,6 +1205,7 @@ impl WasmGenerator {
 
                     simpleword.visit(self, builder, &arg_types, &return_type)?;
                 } else if let Some(variadic) = words::lookup_variadic_simple(function_name) {
+                    variadic.arity().check(function_name, args.len())?;
                     let (arg_types, return_type) = get_types()?;
 
                     let mut args_enumerated = args.iter().enumerate();
@@ -483,12 +1234,50 @@ impl WasmGenerator {
                 } else {
                     self.traverse_call_user_defined(builder, expr, function_name, args)?;
                 }
+
+                if self.annotate_words {
+                    self.tag_word_instructions(builder, start, function_name);
+                }
             }
             _ => return Err(GeneratorError::InternalError("Invalid list".into())),
         }
         Ok(())
     }
 
+    /// Tags the instructions emitted from index `start` of `builder`, and
+    /// those of the blocks they contain, with `word`. The instructions are
+    /// already tagged with the nested words, which are kept.
+    fn tag_word_instructions(
+        &mut self,
+        builder: &mut InstrSeqBuilder,
+        start: usize,
+        word: &ClarityName,
+    ) {
+        let tag = InstrLocId::new(WORD_TAG_BASE + self.word_tags.len() as u32);
+        self.word_tags.push(word.clone());
+
+        let mut seqs = vec![(builder.id(), start)];
+        while let Some((seq, start)) = seqs.pop() {
+            let mut seq_builder = builder.instr_seq(seq);
+            for (instr, loc) in seq_builder.instrs_mut().iter_mut().skip(start) {
+                if loc.is_default() {
+                    *loc = tag;
+                }
+                match instr {
+                    Instr::Block(Block { seq }) | Instr::Loop(Loop { seq }) => seqs.push((*seq, 0)),
+                    Instr::IfElse(IfElse {
+                        consequent,
+                        alternative,
+                    }) => {
+                        seqs.push((*consequent, 0));
+                        seqs.push((*alternative, 0));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
     pub fn traverse_define_function(
         &mut self,
         builder: &mut InstrSeqBuilder,
@@ -521,7 +1310,9 @@ impl WasmGenerator {
             }));
         };
 
-        self.current_function_type = Some(function_type.clone());
+        // The return type of the function is needed by the early returns of
+        // its body, e.g. in `try!`. It is popped after the traversal.
+        self.enter_function(name, &function_type);
 
         // Call the host interface to save this function
         // Arguments are kind (already pushed) and name (offset, length)
@@ -571,10 +1362,14 @@ impl WasmGenerator {
         func_body
             .global_get(self.stack_pointer)
             .local_set(frame_pointer);
+        let prelude_end = func_body.instrs().len();
 
         // Setup the locals map for this function, saving the top-level map to
         // restore after.
         let top_level_locals = std::mem::replace(&mut self.bindings, bindings);
+        let top_level_frame_size = std::mem::take(&mut self.function_frame_size);
+        let top_level_layout =
+            std::mem::replace(&mut self.layout_function, name.as_str().to_owned());
 
         let mut block = func_body.dangling_instr_seq(InstrSeqType::new(
             &mut self.module.types,
@@ -620,14 +1415,21 @@ impl WasmGenerator {
             .local_get(frame_pointer)
             .global_set(self.stack_pointer);
 
+        // Grow the memory for the whole frame, now that its size is known.
+        let frame_size = std::mem::replace(&mut self.function_frame_size, top_level_frame_size);
+        self.emit_ensure_stack_memory(&mut func_body, prelude_end, frame_size);
+
         // Restore the top-level locals map.
         self.bindings = top_level_locals;
+        self.layout_function = top_level_layout;
 
-        // Reset the return type and early block to None
-        self.current_function_type = None;
+        // Restore the outer function, and reset the early block to None
+        self.exit_function();
         self.early_return_block_id = None;
 
-        Ok(func_builder.finish(param_locals, &mut self.module.funcs))
+        let function = func_builder.finish(param_locals, &mut self.module.funcs);
+        self.finish_instruction_budget(function)?;
+        Ok(function)
     }
 
     /// Handles early return scenarios in the code generation process.
@@ -681,7 +1483,7 @@ impl WasmGenerator {
                     })?
                     .clone();
 
-                let (val_offset, _) = self.create_call_stack_local(builder, &ty, false, true);
+                let (val_offset, _) = self.create_call_stack_local(builder, &ty, false, true)?;
                 self.write_to_memory(builder, val_offset, 0, &ty)?;
 
                 let serialized_ty = self.type_for_serialization(&ty).to_string();
@@ -806,13 +1608,17 @@ impl WasmGenerator {
     ) -> Result<(u32, u32), GeneratorError> {
         // If this string has already been saved in the literal memory,
         // just return the offset and length.
-        let (data, entry) = match s {
+        let (data, entry, description) = match s {
             CharType::ASCII(s) => {
                 let entry = LiteralMemoryEntry::Ascii(s.to_string());
-                if let Some(offset) = self.literal_memory_offset.get(&entry) {
-                    return Ok((*offset, s.data.len() as u32));
+                if let Some(&offset) = self.literal_memory_offset.get(&entry) {
+                    return Ok(self.record_literal(
+                        offset,
+                        s.data.len() as u32,
+                        format!("string-ascii literal {s}"),
+                    ));
                 }
-                (s.data.clone(), entry)
+                (s.data.clone(), entry, format!("string-ascii literal {s}"))
             }
             CharType::UTF8(u) => {
                 let data_str = String::from_utf8(u.data.iter().flatten().cloned().collect())
@@ -820,15 +1626,19 @@ impl WasmGenerator {
                         GeneratorError::InternalError("Invalid UTF-8 sequence".to_owned())
                     })?;
                 let entry = LiteralMemoryEntry::Utf8(data_str.clone());
-                if let Some(offset) = self.literal_memory_offset.get(&entry) {
-                    return Ok((*offset, u.data.len() as u32 * 4));
+                if let Some(&offset) = self.literal_memory_offset.get(&entry) {
+                    return Ok(self.record_literal(
+                        offset,
+                        u.data.len() as u32 * 4,
+                        format!("string-utf8 literal u\"{data_str}\""),
+                    ));
                 }
                 // Convert the string into 4-byte big-endian unicode scalar values.
                 let data = data_str
                     .chars()
                     .flat_map(|c| (c as u32).to_be_bytes())
                     .collect();
-                (data, entry)
+                (data, entry, format!("string-utf8 literal u\"{data_str}\""))
             }
         };
         let memory = self.get_memory()?;
@@ -846,7 +1656,7 @@ impl WasmGenerator {
         // Save the offset in the literal memory for this string
         self.literal_memory_offset.insert(entry, offset);
 
-        Ok((offset, len))
+        Ok(self.record_literal(offset, len, description))
     }
 
     /// Adds a new string literal into the memory for an identifier
@@ -854,8 +1664,12 @@ impl WasmGenerator {
         // If this identifier has already been saved in the literal memory,
         // just return the offset and length.
         let entry = LiteralMemoryEntry::Ascii(name.to_string());
-        if let Some(offset) = self.literal_memory_offset.get(&entry) {
-            return Ok((*offset, name.len() as u32));
+        if let Some(&offset) = self.literal_memory_offset.get(&entry) {
+            return Ok(self.record_literal(
+                offset,
+                name.len() as u32,
+                format!("identifier {name}"),
+            ));
         }
 
         let memory = self.get_memory()?;
@@ -873,13 +1687,17 @@ impl WasmGenerator {
         // Save the offset in the literal memory for this identifier
         self.literal_memory_offset.insert(entry, offset);
 
-        Ok((offset, len))
+        Ok(self.record_literal(offset, len, format!("identifier {name}")))
     }
 
     pub(crate) fn add_bytes_literal(&mut self, bytes: &[u8]) -> Result<(u32, u32), GeneratorError> {
         let entry = LiteralMemoryEntry::Bytes(bytes.into());
-        if let Some(offset) = self.literal_memory_offset.get(&entry) {
-            return Ok((*offset, bytes.len() as u32));
+        if let Some(&offset) = self.literal_memory_offset.get(&entry) {
+            return Ok(self.record_literal(
+                offset,
+                bytes.len() as u32,
+                format!("bytes literal {bytes:02x?}"),
+            ));
         }
 
         let memory = self.get_memory()?;
@@ -896,7 +1714,7 @@ impl WasmGenerator {
 
         self.literal_memory_offset.insert(entry, offset);
 
-        Ok((offset, len))
+        Ok(self.record_literal(offset, len, format!("bytes literal {bytes:02x?}")))
     }
 
     pub(crate) fn get_string_literal(&self, name: &str) -> Option<(u32, u32)> {
@@ -972,7 +1790,7 @@ impl WasmGenerator {
         );
         self.literal_memory_end += data.len() as u32;
 
-        Ok((offset, len))
+        Ok(self.record_literal(offset, len, format!("literal {value}")))
     }
 
     pub(crate) fn block_from_expr(
@@ -994,6 +1812,45 @@ impl WasmGenerator {
         Ok(block.id())
     }
 
+    /// Emits a check that the `size` bytes starting at `offset` are within the
+    /// sequence of `seq_length` bytes starting at `seq_offset`, and traps
+    /// otherwise. This protects the reads of an element from a malformed
+    /// length. The data stack is left unchanged.
+    pub(crate) fn emit_memory_bounds_check(
+        &mut self,
+        builder: &mut InstrSeqBuilder,
+        seq_offset: LocalId,
+        seq_length: LocalId,
+        offset: LocalId,
+        size: LocalId,
+    ) {
+        // The range must not start before the sequence.
+        builder
+            .local_get(offset)
+            .local_get(seq_offset)
+            .binop(BinaryOp::I32LtU);
+
+        // Nor end after it. The ends are computed in 64 bits, so that they
+        // cannot overflow.
+        for (start, length) in [(offset, size), (seq_offset, seq_length)] {
+            builder
+                .local_get(start)
+                .unop(UnaryOp::I64ExtendUI32)
+                .local_get(length)
+                .unop(UnaryOp::I64ExtendUI32)
+                .binop(BinaryOp::I64Add);
+        }
+        builder.binop(BinaryOp::I64GtU).binop(BinaryOp::I32Or);
+
+        builder.if_else(
+            None,
+            |then| {
+                then.unreachable();
+            },
+            |_| {},
+        );
+    }
+
     /// Push a new local onto the call stack, adjusting the stack pointer and
     /// tracking the current function's frame size accordingly.
     /// - `include_repr` indicates if space should be reserved for the
@@ -1001,15 +1858,44 @@ impl WasmGenerator {
     ///   type)
     /// - `include_value` indicates if space should be reserved for the value
     ///
+    /// The space is aligned to the natural alignment of the type, see
+    /// [`call_stack_alignment`], and the frame size accounts for the padding.
+    ///
     /// Returns a local which is a pointer to the beginning of the allocated
-    /// stack space and the size of the allocated space.
+    /// stack space and the size of the allocated space, or an error if `ty`
+    /// has no fixed size in memory.
     pub(crate) fn create_call_stack_local(
         &mut self,
         builder: &mut InstrSeqBuilder,
         ty: &TypeSignature,
         include_repr: bool,
         include_value: bool,
-    ) -> (LocalId, i32) {
+    ) -> Result<(LocalId, i32), GeneratorError> {
+        // A list union type only exists during type checking, and describes
+        // no value.
+        fn has_fixed_size(ty: &TypeSignature) -> bool {
+            match ty {
+                TypeSignature::ListUnionType(_) => false,
+                TypeSignature::OptionalType(some) => has_fixed_size(some),
+                TypeSignature::ResponseType(ok_err) => {
+                    has_fixed_size(&ok_err.0) && has_fixed_size(&ok_err.1)
+                }
+                TypeSignature::TupleType(tuple) => {
+                    tuple.get_type_map().values().all(has_fixed_size)
+                }
+                TypeSignature::SequenceType(SequenceSubtype::ListType(list)) => {
+                    has_fixed_size(list.get_list_item_type())
+                }
+                _ => true,
+            }
+        }
+
+        if !has_fixed_size(ty) {
+            return Err(GeneratorError::TypeError(format!(
+                "type {ty} has no fixed size in memory, no call stack space can be reserved for it"
+            )));
+        }
+
         let size = match (include_value, include_repr) {
             (true, true) => get_type_in_memory_size(ty, include_repr) + get_type_size(ty),
             (true, false) => get_type_in_memory_size(ty, include_repr),
@@ -1017,14 +1903,22 @@ impl WasmGenerator {
             (false, false) => unreachable!("must include either repr or value"),
         };
 
-        // Save the offset (current stack pointer) into a local
+        let align = call_stack_alignment(ty);
+
+        // Save the offset (current stack pointer, rounded up to the
+        // alignment) into a local
         let offset = self.module.locals.add(ValType::I32);
         builder
             // []
             .global_get(self.stack_pointer)
             // [ stack_ptr ]
+            .i32_const(align - 1)
+            .binop(BinaryOp::I32Add)
+            .i32_const(-align)
+            .binop(BinaryOp::I32And)
+            // [ aligned_stack_ptr ]
             .local_tee(offset);
-        // [ stack_ptr ]
+        // [ aligned_stack_ptr ]
 
         // TODO: The frame stack size can be computed at compile time, so we
         //       should be able to increment the stack pointer once in the function
@@ -1038,46 +1932,571 @@ impl WasmGenerator {
             // [ new_stack_ptr ]
             .global_set(self.stack_pointer);
         // [  ]
-        self.frame_size += size;
+        // The padding depends on the stack pointer at runtime, so the worst
+        // case is reserved.
+        self.frame_size += size + align - 1;
+        self.function_frame_size += size + align - 1;
+        self.call_stack_locals.insert(offset, (size, align));
+        if self.record_memory_layouts {
+            self.memory_layouts
+                .entry(self.layout_function.clone())
+                .or_default()
+                .records
+                .push(LayoutRecord::CallStackLocal {
+                    local: offset,
+                    ty: ty.clone(),
+                    include_repr,
+                });
+        }
 
-        (offset, size)
+        Ok((offset, size))
     }
 
-    pub(crate) fn borrow_local(&mut self, ty: ValType) -> BorrowedLocal {
-        let reuse = (*self.local_pool)
-            .borrow_mut()
-            .get_mut(&ty)
-            .and_then(Vec::pop);
-        BorrowedLocal {
-            id: reuse.unwrap_or_else(|| self.module.locals.add(ty)),
-            ty,
-            pool: self.local_pool.clone(),
+    /// Grows the memory, if needed, to hold a frame of `frame_size` bytes
+    /// above the stack pointer, with instructions inserted at `position` of
+    /// the prelude of a function once its frame is complete. The frames of
+    /// calls made in loops, e.g. in the body of a `map`, can go beyond the
+    /// initial memory computed from the frame sizes. Traps with a stack
+    /// overflow past the configured maximum call stack size. Nothing is
+    /// emitted for an empty frame, which neither grows the stack nor
+    /// touches the memory.
+    fn emit_ensure_stack_memory(
+        &mut self,
+        builder: &mut InstrSeqBuilder,
+        position: usize,
+        frame_size: i32,
+    ) {
+        if frame_size == 0 {
+            return;
         }
+        builder.instr_at(
+            position,
+            Const {
+                value: walrus::ir::Value::I32(frame_size),
+            },
+        );
+        builder.instr_at(
+            position + 1,
+            Call {
+                func: self.func_by_name("stdlib.ensure-stack-memory"),
+            },
+        );
     }
 
-    /// Write the value that is on the top of the data stack, which has type
-    /// `ty`, to the memory, at offset stored in local variable,
-    /// `offset_local`, plus constant offset `offset`. Returns the number of
-    /// bytes written.
-    pub(crate) fn write_to_memory(
-        &mut self,
-        builder: &mut InstrSeqBuilder,
-        offset_local: LocalId,
-        offset: u32,
-        ty: &TypeSignature,
-    ) -> Result<u32, GeneratorError> {
-        let memory = self.get_memory()?;
-        match ty {
-            TypeSignature::IntType | TypeSignature::UIntType => {
-                // Data stack: TOP | High | Low | ...
-                // Save the high/low to locals.
-                let high = self.borrow_local(ValType::I64);
-                let low = self.borrow_local(ValType::I64);
-                builder.local_set(*high).local_set(*low);
+    /// Copies memory like `memory.copy`, with the destination, source and
+    /// length on the top of the stack. Without the bulk memory feature, the
+    /// copy is lowered to a loop by [`Self::lower_bulk_memory`].
+    pub(crate) fn emit_memory_copy(&mut self, builder: &mut InstrSeqBuilder, memory: MemoryId) {
+        builder.memory_copy(memory, memory);
+    }
 
-                // Store the high/low to memory.
-                builder.local_get(offset_local).local_get(*low).store(
-                    memory,
+    /// Replaces the `memory.copy` and `memory.fill` instructions of every
+    /// function, including the stdlib, with calls to functions doing the
+    /// same with loops, for runtimes without the bulk memory proposal.
+    fn lower_bulk_memory(&mut self) -> Result<(), GeneratorError> {
+        let memory = self.get_memory()?;
+        let mut lowering = BulkMemoryLowering {
+            copy: self.add_memory_copy_loop(memory),
+            fill: self.add_memory_fill_loop(memory),
+        };
+        for (_, func) in self.module.funcs.iter_local_mut() {
+            let entry = func.entry_block();
+            dfs_pre_order_mut(&mut lowering, func, entry);
+        }
+        Ok(())
+    }
+
+    /// Adds a function `(destination, source, length)` copying the bytes one
+    /// by one, like `memory.copy`.
+    fn add_memory_copy_loop(&mut self, memory: MemoryId) -> FunctionId {
+        let destination = self.module.locals.add(ValType::I32);
+        let source = self.module.locals.add(ValType::I32);
+        let remaining = self.module.locals.add(ValType::I32);
+        let mut func = FunctionBuilder::new(
+            &mut self.module.types,
+            &[ValType::I32, ValType::I32, ValType::I32],
+            &[],
+        );
+        func.name("memory-copy-loop".to_owned());
+        let mut builder = func.func_body();
+
+        let load_byte = LoadKind::I32_8 {
+            kind: ExtendedLoad::ZeroExtend,
+        };
+        let store_byte = StoreKind::I32_8 { atomic: false };
+        let mem_arg = MemArg {
+            align: 1,
+            offset: 0,
+        };
+
+        // Like `memory.copy`, overlapping ranges are copied backwards when
+        // the destination is after the source.
+        builder
+            .local_get(destination)
+            .local_get(source)
+            .binop(BinaryOp::I32GtU)
+            .if_else(
+                None,
+                |backward| {
+                    backward.block(None, |done| {
+                        let done_id = done.id();
+                        done.loop_(None, |copy| {
+                            let copy_id = copy.id();
+                            copy.local_get(remaining)
+                                .unop(UnaryOp::I32Eqz)
+                                .br_if(done_id);
+                            copy.local_get(remaining)
+                                .i32_const(1)
+                                .binop(BinaryOp::I32Sub)
+                                .local_set(remaining);
+                            copy.local_get(destination)
+                                .local_get(remaining)
+                                .binop(BinaryOp::I32Add)
+                                .local_get(source)
+                                .local_get(remaining)
+                                .binop(BinaryOp::I32Add)
+                                .load(memory, load_byte, mem_arg)
+                                .store(memory, store_byte, mem_arg)
+                                .br(copy_id);
+                        });
+                    });
+                },
+                |forward| {
+                    forward.block(None, |done| {
+                        let done_id = done.id();
+                        done.loop_(None, |copy| {
+                            let copy_id = copy.id();
+                            copy.local_get(remaining)
+                                .unop(UnaryOp::I32Eqz)
+                                .br_if(done_id);
+                            copy.local_get(destination)
+                                .local_get(source)
+                                .load(memory, load_byte, mem_arg)
+                                .store(memory, store_byte, mem_arg);
+                            for local in [destination, source] {
+                                copy.local_get(local)
+                                    .i32_const(1)
+                                    .binop(BinaryOp::I32Add)
+                                    .local_set(local);
+                            }
+                            copy.local_get(remaining)
+                                .i32_const(1)
+                                .binop(BinaryOp::I32Sub)
+                                .local_set(remaining)
+                                .br(copy_id);
+                        });
+                    });
+                },
+            );
+
+        func.finish(vec![destination, source, remaining], &mut self.module.funcs)
+    }
+
+    /// Adds a function `(destination, value, length)` storing the byte
+    /// `value` one by one, like `memory.fill`.
+    fn add_memory_fill_loop(&mut self, memory: MemoryId) -> FunctionId {
+        let destination = self.module.locals.add(ValType::I32);
+        let value = self.module.locals.add(ValType::I32);
+        let remaining = self.module.locals.add(ValType::I32);
+        let mut func = FunctionBuilder::new(
+            &mut self.module.types,
+            &[ValType::I32, ValType::I32, ValType::I32],
+            &[],
+        );
+        func.name("memory-fill-loop".to_owned());
+
+        func.func_body().block(None, |done| {
+            let done_id = done.id();
+            done.loop_(None, |fill| {
+                let fill_id = fill.id();
+                fill.local_get(remaining)
+                    .unop(UnaryOp::I32Eqz)
+                    .br_if(done_id);
+                fill.local_get(destination).local_get(value).store(
+                    memory,
+                    StoreKind::I32_8 { atomic: false },
+                    MemArg {
+                        align: 1,
+                        offset: 0,
+                    },
+                );
+                fill.local_get(destination)
+                    .i32_const(1)
+                    .binop(BinaryOp::I32Add)
+                    .local_set(destination);
+                fill.local_get(remaining)
+                    .i32_const(1)
+                    .binop(BinaryOp::I32Sub)
+                    .local_set(remaining)
+                    .br(fill_id);
+            });
+        });
+
+        func.finish(vec![destination, value, remaining], &mut self.module.funcs)
+    }
+
+    /// Removes the call stack reservations made by
+    /// [`Self::create_call_stack_local`] whose local is never read, and
+    /// shrinks the frame size accordingly. Reservations whose code was
+    /// discarded after traversal are reclaimed as well.
+    fn eliminate_dead_call_stack_locals(&mut self) {
+        let mut reads = LocalReads::default();
+        for (_, func) in self.module.funcs.iter_local() {
+            dfs_in_order(&mut reads, func, func.entry_block());
+        }
+
+        let dead: HashSet<LocalId> = self
+            .call_stack_locals
+            .keys()
+            .filter(|local| !reads.0.contains(local))
+            .copied()
+            .collect();
+        if dead.is_empty() {
+            return;
+        }
+
+        let mut remover = DeadReservationRemover {
+            stack_pointer: self.stack_pointer,
+            dead: &dead,
+        };
+        for (_, func) in self.module.funcs.iter_local_mut() {
+            let entry = func.entry_block();
+            dfs_pre_order_mut(&mut remover, func, entry);
+        }
+
+        for local in dead {
+            if let Some((size, align)) = self.call_stack_locals.remove(&local) {
+                self.frame_size -= size + align - 1;
+            }
+        }
+    }
+
+    /// Adds an entry wrapper for each public function of the contract.
+    fn add_entry_wrappers(&mut self) -> Result<(), GeneratorError> {
+        let public_functions: Vec<_> = self
+            .contract_analysis
+            .public_function_types
+            .iter()
+            .filter_map(|(name, ty)| match ty {
+                FunctionType::Fixed(fixed) => Some((name.clone(), fixed.clone())),
+                _ => None,
+            })
+            .collect();
+        for (name, function_type) in public_functions {
+            // A function whose definition failed has no body to wrap.
+            let Some(function) = self.try_func_by_name(name.as_str()) else {
+                continue;
+            };
+            self.add_entry_wrapper(&name, function, &function_type)?;
+        }
+        Ok(())
+    }
+
+    /// Adds a function exported as `<name>.entry`, with a stable signature
+    /// for runtimes calling the public function `name` with serialized
+    /// values: `(args_offset: i32, args_length: i32, result_offset: i32,
+    /// result_length: i32) -> i32`.
+    ///
+    /// The arguments are the consensus serialization of each argument of the
+    /// function, one after the other. The consensus serialization of the
+    /// result is written in the `result_length` bytes at `result_offset`, and
+    /// its length is returned. If the arguments do not deserialize to the
+    /// declared types of the parameters, or do not use the whole buffer, the
+    /// wrapper traps with [`ErrorMap::ArgumentTypeMismatch`] before calling
+    /// the function. If the result does not fit in its buffer, the wrapper
+    /// traps with [`ErrorMap::ResultTooLarge`], leaving the buffer untouched.
+    ///
+    /// Like the function itself, the wrapper does not commit or roll back
+    /// the changes made by the call, which is left to the runtime.
+    fn add_entry_wrapper(
+        &mut self,
+        name: &ClarityName,
+        function: FunctionId,
+        function_type: &FixedFunction,
+    ) -> Result<(), GeneratorError> {
+        let wrapper_name = format!("{name}.entry");
+        let args_offset = self.module.locals.add(ValType::I32);
+        let args_length = self.module.locals.add(ValType::I32);
+        let result_offset = self.module.locals.add(ValType::I32);
+        let result_length = self.module.locals.add(ValType::I32);
+        let mut wrapper =
+            FunctionBuilder::new(&mut self.module.types, &[ValType::I32; 4], &[ValType::I32]);
+        wrapper.name(wrapper_name.clone());
+        let mut body = wrapper.func_body();
+
+        let stack_pointer = self.stack_pointer;
+        let frame_pointer = self.module.locals.add(ValType::I32);
+        body.global_get(stack_pointer).local_set(frame_pointer);
+        let prelude_end = body.instrs().len();
+        let top_level_layout = std::mem::replace(&mut self.layout_function, wrapper_name.clone());
+        let top_level_frame_size = std::mem::take(&mut self.function_frame_size);
+
+        let args_end = self.module.locals.add(ValType::I32);
+        body.local_get(args_offset)
+            .local_get(args_length)
+            .binop(BinaryOp::I32Add)
+            .local_set(args_end);
+
+        // Restores the stack pointer and traps with `error`.
+        let runtime_error = self.func_by_name("stdlib.runtime-error");
+        let fail = |error: ErrorMap| {
+            move |builder: &mut InstrSeqBuilder| {
+                builder
+                    .local_get(frame_pointer)
+                    .global_set(stack_pointer)
+                    .i32_const(error as i32)
+                    .call(runtime_error)
+                    .unreachable();
+            }
+        };
+
+        let mut arg_locals = Vec::new();
+        for arg in &function_type.args {
+            let (offset_result, _) =
+                self.create_call_stack_local(&mut body, &arg.signature, true, true)?;
+            // Leaves `(some value)`, or `none` if the argument is invalid.
+            self.deserialize_from_memory(
+                &mut body,
+                args_offset,
+                args_end,
+                offset_result,
+                &arg.signature,
+            )?;
+
+            let locals: Vec<_> = clar2wasm_ty(&arg.signature)
+                .into_iter()
+                .map(|ty| self.module.locals.add(ty))
+                .collect();
+            for local in locals.iter().rev() {
+                body.local_set(*local);
+            }
+            body.unop(UnaryOp::I32Eqz)
+                .if_else(None, fail(ErrorMap::ArgumentTypeMismatch), |_| {});
+            arg_locals.extend(locals);
+        }
+
+        // The whole buffer must be used by the arguments.
+        body.local_get(args_offset)
+            .local_get(args_end)
+            .binop(BinaryOp::I32Ne)
+            .if_else(None, fail(ErrorMap::ArgumentTypeMismatch), |_| {});
+
+        for local in arg_locals {
+            body.local_get(local);
+        }
+        body.call(function);
+
+        // The result is serialized on the call stack first, so that a result
+        // too large for its buffer does not overwrite the memory after it.
+        let max_size = function_type
+            .returns
+            .max_serialized_size()
+            .ok()
+            .and_then(|size| BufferLength::try_from(size).ok())
+            .ok_or_else(|| {
+                GeneratorError::TypeError(format!(
+                    "the result of {name} has no maximum serialized size"
+                ))
+            })?;
+        let (serialized, _) = self.create_call_stack_local(
+            &mut body,
+            &TypeSignature::SequenceType(SequenceSubtype::BufferType(max_size)),
+            false,
+            true,
+        )?;
+        let serialized_length = self.module.locals.add(ValType::I32);
+        self.serialize_to_memory(&mut body, serialized, 0, &function_type.returns)?;
+        body.local_tee(serialized_length)
+            .local_get(result_length)
+            .binop(BinaryOp::I32GtU)
+            .if_else(None, fail(ErrorMap::ResultTooLarge), |_| {});
+
+        let memory = self.get_memory()?;
+        body.local_get(result_offset)
+            .local_get(serialized)
+            .local_get(serialized_length);
+        self.emit_memory_copy(&mut body, memory);
+        body.local_get(frame_pointer)
+            .global_set(stack_pointer)
+            .local_get(serialized_length);
+
+        let frame_size = std::mem::replace(&mut self.function_frame_size, top_level_frame_size);
+        self.emit_ensure_stack_memory(&mut body, prelude_end, frame_size);
+        self.layout_function = top_level_layout;
+
+        let wrapper = wrapper.finish(
+            vec![args_offset, args_length, result_offset, result_length],
+            &mut self.module.funcs,
+        );
+        self.finish_instruction_budget(wrapper)?;
+        self.module.exports.add(&wrapper_name, wrapper);
+        Ok(())
+    }
+
+    /// Merges the generated functions with identical bodies: calls to a
+    /// duplicate are redirected to the first function with the same body,
+    /// and the duplicate is removed from the module. Exported duplicates are
+    /// kept for their exports. Redirected calls can make their callers
+    /// identical too, so this is repeated until no duplicate is left.
+    fn merge_duplicate_functions(&mut self) {
+        let exported: HashSet<FunctionId> = self
+            .module
+            .exports
+            .iter()
+            .filter_map(|export| match export.item {
+                walrus::ExportItem::Function(func) => Some(func),
+                _ => None,
+            })
+            .collect();
+        let mut kept = HashSet::new();
+        loop {
+            let mut first_by_body = HashMap::new();
+            let mut duplicates = HashMap::new();
+            for (id, func) in self.module.funcs.iter_local() {
+                if self.standard_functions.contains(&id) || kept.contains(&id) {
+                    continue;
+                }
+                let key = (func.ty(), canonical_body(func, &self.module.locals));
+                match first_by_body.entry(key) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(id);
+                    }
+                    Entry::Occupied(entry) => {
+                        duplicates.insert(id, *entry.get());
+                    }
+                }
+            }
+            if duplicates.is_empty() {
+                return;
+            }
+
+            let mut redirector = CallRedirector(&duplicates);
+            for (_, func) in self.module.funcs.iter_local_mut() {
+                let entry = func.entry_block();
+                dfs_pre_order_mut(&mut redirector, func, entry);
+            }
+            for duplicate in duplicates.into_keys() {
+                if exported.contains(&duplicate) {
+                    kept.insert(duplicate);
+                } else {
+                    self.module.funcs.delete(duplicate);
+                }
+            }
+        }
+    }
+
+    /// Replaces the calls to small stdlib functions in the generated functions
+    /// by a copy of their body, saving the overhead of the calls.
+    fn inline_small_stdlib_functions(&mut self) {
+        let mut bodies = HashMap::new();
+        for func in self.module.funcs.iter() {
+            let (walrus::FunctionKind::Local(local), Some(name)) = (&func.kind, &func.name) else {
+                continue;
+            };
+            if !name.starts_with("stdlib.") || local.size() > STDLIB_INLINE_THRESHOLD {
+                continue;
+            }
+
+            let mut seq_ids = InstrSeqIds::default();
+            dfs_in_order(&mut seq_ids, local, local.entry_block());
+            let seqs: HashMap<_, _> = seq_ids
+                .0
+                .into_iter()
+                .map(|id| {
+                    let seq = local.block(id);
+                    let instrs = seq.instrs.iter().map(|(instr, _)| instr.clone()).collect();
+                    (id, (seq.ty, instrs))
+                })
+                .collect();
+
+            // A recursive function cannot be inlined.
+            let is_recursive = seqs.values().flat_map(|(_, instrs)| instrs).any(
+                |instr| matches!(instr, Instr::Call(Call { func: callee }) if *callee == func.id()),
+            );
+            if is_recursive {
+                continue;
+            }
+
+            let ty = self.module.types.get(local.ty());
+            let (params, results) = (ty.params().to_vec(), ty.results().to_vec());
+            bodies.insert(
+                func.id(),
+                InlinedBody {
+                    ty: InstrSeqType::new(&mut self.module.types, &params, &results),
+                    args: local.args.clone(),
+                    entry: local.entry_block(),
+                    seqs,
+                },
+            );
+        }
+        if bodies.is_empty() {
+            return;
+        }
+
+        for func in self.module.funcs.iter_mut() {
+            if func
+                .name
+                .as_deref()
+                .map_or(false, |name| name.starts_with("stdlib."))
+            {
+                continue;
+            }
+            let walrus::FunctionKind::Local(local) = &mut func.kind else {
+                continue;
+            };
+
+            let mut seq_ids = InstrSeqIds::default();
+            dfs_in_order(&mut seq_ids, local, local.entry_block());
+            for seq_id in seq_ids.0 {
+                for i in 0..local.block(seq_id).instrs.len() {
+                    let Instr::Call(Call { func: callee }) = local.block(seq_id).instrs[i].0 else {
+                        continue;
+                    };
+                    let Some(body) = bodies.get(&callee) else {
+                        continue;
+                    };
+                    let block = body.inline_into(local.builder_mut(), &mut self.module.locals);
+                    local.block_mut(seq_id).instrs[i].0 = Instr::Block(Block { seq: block });
+                }
+            }
+        }
+    }
+
+    pub(crate) fn borrow_local(&mut self, ty: ValType) -> BorrowedLocal {
+        let reuse = (*self.local_pool)
+            .borrow_mut()
+            .get_mut(&ty)
+            .and_then(Vec::pop);
+        BorrowedLocal {
+            id: reuse.unwrap_or_else(|| self.module.locals.add(ty)),
+            ty,
+            pool: self.local_pool.clone(),
+        }
+    }
+
+    /// Write the value that is on the top of the data stack, which has type
+    /// `ty`, to the memory, at offset stored in local variable,
+    /// `offset_local`, plus constant offset `offset`. Returns the number of
+    /// bytes written.
+    pub(crate) fn write_to_memory(
+        &mut self,
+        builder: &mut InstrSeqBuilder,
+        offset_local: LocalId,
+        offset: u32,
+        ty: &TypeSignature,
+    ) -> Result<u32, GeneratorError> {
+        let memory = self.get_memory()?;
+        match ty {
+            TypeSignature::IntType | TypeSignature::UIntType => {
+                // Data stack: TOP | High | Low | ...
+                // Save the high/low to locals.
+                let high = self.borrow_local(ValType::I64);
+                let low = self.borrow_local(ValType::I64);
+                builder.local_set(*high).local_set(*low);
+
+                // Store the high/low to memory.
+                builder.local_get(offset_local).local_get(*low).store(
+                    memory,
                     StoreKind::I64 { atomic: false },
                     MemArg { align: 8, offset },
                 );
@@ -1224,6 +2643,131 @@ impl WasmGenerator {
         }
     }
 
+    /// Write the constant `value`, which has type `ty`, to the memory, at
+    /// offset stored in local variable `offset_local`, plus constant offset
+    /// `offset`. The layout is the one expected by `read_from_memory`.
+    /// Returns the number of bytes written.
+    pub fn write_value_to_memory(
+        &mut self,
+        builder: &mut InstrSeqBuilder,
+        offset_local: LocalId,
+        offset: u32,
+        value: &clarity::vm::Value,
+        ty: &TypeSignature,
+    ) -> Result<u32, GeneratorError> {
+        let memory = self.get_memory()?;
+
+        // The representation of the value is stored in the literal memory,
+        // and copied to the destination.
+        let repr = self.value_repr_bytes(value, ty)?;
+        let (repr_offset, repr_len) = self.add_bytes_literal(&repr)?;
+        builder
+            .local_get(offset_local)
+            .i32_const(offset as i32)
+            .binop(BinaryOp::I32Add)
+            .i32_const(repr_offset as i32)
+            .i32_const(repr_len as i32);
+        self.emit_memory_copy(builder, memory);
+
+        Ok(repr_len)
+    }
+
+    /// Returns the bytes representing `value`, with type `ty`, in memory.
+    /// In-memory values are added to the literal memory, and represented by
+    /// their offset and length.
+    fn value_repr_bytes(
+        &mut self,
+        value: &clarity::vm::Value,
+        ty: &TypeSignature,
+    ) -> Result<Vec<u8>, GeneratorError> {
+        use clarity::vm::Value;
+
+        let mismatch =
+            || GeneratorError::TypeError(format!("value {value} does not have the type {ty}"));
+        // Placeholder for a value absent from an optional or response.
+        let placeholder = |ty: &TypeSignature| vec![0u8; get_type_size(ty) as usize];
+
+        let bytes = match (ty, value) {
+            (TypeSignature::IntType, Value::Int(i)) => i.to_le_bytes().to_vec(),
+            (TypeSignature::UIntType, Value::UInt(u)) => u.to_le_bytes().to_vec(),
+            (TypeSignature::BoolType, Value::Bool(b)) => (*b as i32).to_le_bytes().to_vec(),
+            (TypeSignature::NoType, _) => placeholder(ty),
+            (TypeSignature::OptionalType(inner_ty), Value::Optional(opt)) => {
+                match opt.data.as_deref() {
+                    Some(inner) => {
+                        let mut bytes = 1i32.to_le_bytes().to_vec();
+                        bytes.extend(self.value_repr_bytes(inner, inner_ty)?);
+                        bytes
+                    }
+                    None => {
+                        let mut bytes = 0i32.to_le_bytes().to_vec();
+                        bytes.extend(placeholder(inner_ty));
+                        bytes
+                    }
+                }
+            }
+            (TypeSignature::ResponseType(inner_tys), Value::Response(resp)) => {
+                let (ok_ty, err_ty) = inner_tys.as_ref();
+                let mut bytes = (resp.committed as i32).to_le_bytes().to_vec();
+                if resp.committed {
+                    bytes.extend(self.value_repr_bytes(&resp.data, ok_ty)?);
+                    bytes.extend(placeholder(err_ty));
+                } else {
+                    bytes.extend(placeholder(ok_ty));
+                    bytes.extend(self.value_repr_bytes(&resp.data, err_ty)?);
+                }
+                bytes
+            }
+            (TypeSignature::TupleType(tuple_ty), Value::Tuple(tuple)) => {
+                let mut bytes = vec![];
+                for (name, field_ty) in tuple_ty.get_type_map() {
+                    let field = tuple.get(name).map_err(|_| mismatch())?;
+                    bytes.extend(self.value_repr_bytes(field, field_ty)?);
+                }
+                bytes
+            }
+            (
+                TypeSignature::SequenceType(SequenceSubtype::ListType(list_ty)),
+                Value::Sequence(SequenceData::List(list)),
+            ) => {
+                let elem_ty = list_ty.get_list_item_type();
+                let mut elements = vec![];
+                for elem in &list.data {
+                    elements.extend(self.value_repr_bytes(elem, elem_ty)?);
+                }
+                let (offset, len) = self.add_bytes_literal(&elements)?;
+                [(offset as i32).to_le_bytes(), (len as i32).to_le_bytes()].concat()
+            }
+            (
+                TypeSignature::PrincipalType
+                | TypeSignature::CallableType(_)
+                | TypeSignature::TraitReferenceType(_),
+                Value::CallableContract(callable),
+            ) => {
+                let principal = Value::Principal(PrincipalData::Contract(
+                    callable.contract_identifier.clone(),
+                ));
+                let (offset, len) = self.add_literal(&principal)?;
+                [(offset as i32).to_le_bytes(), (len as i32).to_le_bytes()].concat()
+            }
+            (
+                TypeSignature::PrincipalType
+                | TypeSignature::CallableType(_)
+                | TypeSignature::TraitReferenceType(_),
+                Value::Principal(_),
+            )
+            | (
+                TypeSignature::SequenceType(_),
+                Value::Sequence(SequenceData::Buffer(_) | SequenceData::String(_)),
+            ) => {
+                let (offset, len) = self.add_literal(value)?;
+                [(offset as i32).to_le_bytes(), (len as i32).to_le_bytes()].concat()
+            }
+            _ => return Err(mismatch()),
+        };
+        Ok(bytes)
+    }
+
     /// Read a value from memory at offset stored in local variable `offset`,
     /// with type `ty`, and push it onto the top of the data stack.
     pub(crate) fn read_from_memory(
@@ -1354,10 +2898,56 @@ impl WasmGenerator {
         }
     }
 
+    /// Lists the operands of a variadic form named `name`, with the operands
+    /// of the nested forms of the same name spliced in their place. The
+    /// nested forms are unfolded with an explicit work stack instead of
+    /// recursing through `traverse_expr`, after being passed to `unfold`.
+    pub(crate) fn flatten_variadic<'a>(
+        &mut self,
+        name: &str,
+        args: &'a [SymbolicExpression],
+        mut unfold: impl FnMut(
+            &mut Self,
+            &'a SymbolicExpression,
+            &'a [SymbolicExpression],
+        ) -> Result<(), GeneratorError>,
+    ) -> Result<Vec<&'a SymbolicExpression>, GeneratorError> {
+        let mut operands = Vec::with_capacity(args.len());
+        let mut work = vec![args.iter()];
+        while let Some(pending) = work.last_mut() {
+            let Some(arg) = pending.next() else {
+                work.pop();
+                continue;
+            };
+            match arg.match_list() {
+                Some([head, nested @ ..])
+                    if !nested.is_empty()
+                        && head.match_atom().is_some_and(|atom| atom.as_str() == name) =>
+                {
+                    unfold(self, arg, nested)?;
+                    work.push(nested.iter());
+                }
+                _ => operands.push(arg),
+            }
+        }
+        Ok(operands)
+    }
+
     pub(crate) fn traverse_statement_list(
         &mut self,
         builder: &mut InstrSeqBuilder,
         statements: &[SymbolicExpression],
+    ) -> Result<(), GeneratorError> {
+        let statements: Vec<_> = statements.iter().collect();
+        self.traverse_statements(builder, &statements)
+    }
+
+    /// Traverses `statements` in order, dropping the value of each one but
+    /// the last.
+    pub(crate) fn traverse_statements(
+        &mut self,
+        builder: &mut InstrSeqBuilder,
+        statements: &[&SymbolicExpression],
     ) -> Result<(), GeneratorError> {
         if statements.is_empty() {
             return Err(GeneratorError::InternalError(
@@ -1367,7 +2957,7 @@ impl WasmGenerator {
 
         let mut last_ty = None;
         // Traverse the statements, saving the last non-none value.
-        for stmt in statements {
+        for &stmt in statements {
             // If stmt has a type, save that type. If there was a previous type
             // saved, then drop that value.
             if let Some(ty) = self.get_expr_type(stmt) {
@@ -1377,6 +2967,11 @@ impl WasmGenerator {
                 last_ty = Some(ty.clone());
             }
             self.traverse_expr(builder, stmt)?;
+
+            // The statements after a diverging one are dead code.
+            if ends_in_divergence(builder) {
+                break;
+            }
         }
 
         Ok(())
@@ -1402,7 +2997,7 @@ impl WasmGenerator {
                         &TypeSignature::PrincipalType,
                         false,
                         true,
-                    );
+                    )?;
 
                     // Push the offset and size to the data stack
                     builder.local_get(offset).i32_const(size);
@@ -1419,7 +3014,7 @@ impl WasmGenerator {
                         &TypeSignature::PrincipalType,
                         false,
                         true,
-                    );
+                    )?;
 
                     // Push the offset and size to the data stack
                     builder.local_get(offset).i32_const(size);
@@ -1436,7 +3031,7 @@ impl WasmGenerator {
                         &TypeSignature::PrincipalType,
                         false,
                         true,
-                    );
+                    )?;
 
                     // Push the offset and size to the data stack
                     builder.local_get(offset).i32_const(size);
@@ -1446,26 +3041,6 @@ impl WasmGenerator {
                     builder.call(self.func_by_name("stdlib.tx_sponsor"));
                     Ok(true)
                 }
-                NativeVariables::BlockHeight => {
-                    // Call the host interface function, `block_height`
-                    builder.call(self.func_by_name("stdlib.block_height"));
-                    Ok(true)
-                }
-                NativeVariables::StacksBlockHeight => {
-                    // Call the host interface function, `stacks_block_height`
-                    builder.call(self.func_by_name("stdlib.stacks_block_height"));
-                    Ok(true)
-                }
-                NativeVariables::TenureHeight => {
-                    // Call the host interface function, `tenure_height`
-                    builder.call(self.func_by_name("stdlib.tenure_height"));
-                    Ok(true)
-                }
-                NativeVariables::BurnBlockHeight => {
-                    // Call the host interface function, `burn_block_height`
-                    builder.call(self.func_by_name("stdlib.burn_block_height"));
-                    Ok(true)
-                }
                 NativeVariables::NativeNone => {
                     let ty = self.get_expr_type(expr).ok_or_else(|| {
                         GeneratorError::TypeError("'none' must be typed".to_owned())
@@ -1481,24 +3056,18 @@ impl WasmGenerator {
                     builder.i32_const(0);
                     Ok(true)
                 }
-                NativeVariables::TotalLiquidMicroSTX => {
-                    // Call the host interface function, `stx_liquid_supply`
-                    builder.call(self.func_by_name("stdlib.stx_liquid_supply"));
-                    Ok(true)
-                }
-                NativeVariables::Regtest => {
-                    // Call the host interface function, `is_in_regtest`
-                    builder.call(self.func_by_name("stdlib.is_in_regtest"));
-                    Ok(true)
-                }
-                NativeVariables::Mainnet => {
-                    // Call the host interface function, `is_in_mainnet`
-                    builder.call(self.func_by_name("stdlib.is_in_mainnet"));
-                    Ok(true)
-                }
-                NativeVariables::ChainId => {
-                    // Call the host interface function, `chain_id`
-                    builder.call(self.func_by_name("stdlib.chain_id"));
+                NativeVariables::BlockHeight
+                | NativeVariables::StacksBlockHeight
+                | NativeVariables::TenureHeight
+                | NativeVariables::BurnBlockHeight
+                | NativeVariables::TotalLiquidMicroSTX
+                | NativeVariables::Regtest
+                | NativeVariables::Mainnet
+                | NativeVariables::ChainId => {
+                    let keyword = words::lookup_keyword(name).ok_or_else(|| {
+                        GeneratorError::InternalError(format!("missing handler for {name}"))
+                    })?;
+                    keyword.visit(self, builder, expr)?;
                     Ok(true)
                 }
             }
@@ -1522,7 +3091,7 @@ impl WasmGenerator {
 
             // Reserve stack space for the constant copy
             let (result_local, result_size) =
-                self.create_call_stack_local(builder, &ty, true, true);
+                self.create_call_stack_local(builder, &ty, true, true)?;
 
             let (name_offset, name_length) = self.add_string_literal(name)?;
 
@@ -1573,13 +3142,35 @@ impl WasmGenerator {
         locals
     }
 
+    /// Returns the signature of `method` in the trait `trait_name` defined in
+    /// this contract.
+    #[cfg(test)]
+    pub(crate) fn trait_method_signature(
+        &self,
+        trait_name: &str,
+        method: &str,
+    ) -> Option<&FunctionSignature> {
+        self.traits_types.get(trait_name)?.get(method)
+    }
+
     pub fn func_by_name(&self, name: &str) -> FunctionId {
-        self.module
-            .funcs
-            .by_name(name)
+        self.try_func_by_name(name)
             .unwrap_or_else(|| panic!("function not found: {name}"))
     }
 
+    /// Returns the id of the function named `name`, if it exists.
+    ///
+    /// The stdlib functions are looked up over and over again while traversing
+    /// a contract, so the ids are cached after the first lookup in the module.
+    pub fn try_func_by_name(&self, name: &str) -> Option<FunctionId> {
+        if let Some(id) = self.func_ids.borrow().get(name) {
+            return Some(*id);
+        }
+        let id = self.module.funcs.by_name(name)?;
+        self.func_ids.borrow_mut().insert(name.to_owned(), id);
+        Some(id)
+    }
+
     pub fn get_function_type(&self, name: &str) -> Option<&FunctionType> {
         let analysis = &self.contract_analysis;
 
@@ -1646,6 +3237,11 @@ impl WasmGenerator {
             return Ok(());
         }
 
+        // Handle bindings to constants, which are pushed as literals
+        if let Some(value) = self.bindings.get_constant(atom).cloned() {
+            return self.visit_literal_value(builder, expr, &value);
+        }
+
         // Handle parameters and local bindings
         let values = self.bindings.get_locals(atom).ok_or_else(|| {
             GeneratorError::InternalError(format!("unable to find local for {}", atom.as_str()))
@@ -1715,6 +3311,7 @@ impl WasmGenerator {
             // in case there is an in-memory type to copy, we reserve some space in memory
             let return_size = count_in_memory_space(return_ty) as i32;
             self.frame_size += return_size;
+            self.function_frame_size += return_size;
 
             builder
                 .global_get(self.stack_pointer)
@@ -1793,8 +3390,8 @@ impl WasmGenerator {
                 builder
                     .local_get(copy_offset)
                     .local_get(*offset)
-                    .local_get(*len)
-                    .memory_copy(memory, memory);
+                    .local_get(*len);
+                self.emit_memory_copy(builder, memory);
 
                 // update the offset to copy_offset, then move copy_offset to point after the list
                 builder.local_get(copy_offset).local_set(*offset);
@@ -1864,8 +3461,8 @@ impl WasmGenerator {
                 builder
                     .local_get(copy_offset)
                     .local_get(*offset)
-                    .local_get(*len)
-                    .memory_copy(memory, memory);
+                    .local_get(*len);
+                self.emit_memory_copy(builder, memory);
                 // Set the new offset
                 builder.local_get(copy_offset).local_set(*offset);
                 // Increment the copy offset
@@ -2138,41 +3735,420 @@ impl WasmGenerator {
         self.max_work_space = self.max_work_space.max(bytes_len);
     }
 
+    /// The function whose body is being generated, or `None` at the top
+    /// level.
+    pub fn current_function(&self) -> Option<&FunctionContext> {
+        self.function_stack.last()
+    }
+
+    /// Makes `name` the current function until [`Self::exit_function`].
+    pub(crate) fn enter_function(&mut self, name: &ClarityName, function_type: &FixedFunction) {
+        self.function_stack.push(FunctionContext {
+            name: name.clone(),
+            returns: function_type.returns.clone(),
+            args: function_type.args.clone(),
+        });
+    }
+
+    /// Restores the function which was current before the last
+    /// [`Self::enter_function`].
+    pub(crate) fn exit_function(&mut self) {
+        self.function_stack.pop();
+    }
+
     pub(crate) fn get_current_function_return_type(&self) -> Option<&TypeSignature> {
-        self.current_function_type.as_ref().map(|f| &f.returns)
+        self.current_function().map(|f| &f.returns)
     }
 
     pub(crate) fn get_current_function_arg_type(
         &self,
         arg_name: &ClarityName,
     ) -> Option<&TypeSignature> {
-        self.current_function_type
-            .as_ref()
-            .map(|f| &f.args)
-            .and_then(|args| {
-                args.iter()
-                    .find_map(|arg| (&arg.name == arg_name).then_some(&arg.signature))
-            })
+        self.current_function().and_then(|f| {
+            f.args
+                .iter()
+                .find_map(|arg| (&arg.name == arg_name).then_some(&arg.signature))
+        })
     }
 }
 
-/// Returns true if a composed type has an inner in-memory type.
-fn has_in_memory_type(ty: &TypeSignature) -> bool {
-    match ty {
-        TypeSignature::OptionalType(opt) => has_in_memory_type(opt),
-        TypeSignature::ResponseType(resp) => {
-            has_in_memory_type(&resp.0) || has_in_memory_type(&resp.1)
-        }
-        TypeSignature::TupleType(tup) => tup.get_type_map().values().any(has_in_memory_type),
-        TypeSignature::NoType
-        | TypeSignature::IntType
-        | TypeSignature::UIntType
-        | TypeSignature::BoolType => false,
-        TypeSignature::SequenceType(_)
-        | TypeSignature::PrincipalType
-        | TypeSignature::CallableType(_)
-        | TypeSignature::TraitReferenceType(_) => true,
-        TypeSignature::ListUnionType(_) => unreachable!("not a value type"),
+/// Collects every local read by a `local.get`.
+#[derive(Default)]
+struct LocalReads(HashSet<LocalId>);
+
+impl<'instr> Visitor<'instr> for LocalReads {
+    fn visit_local_get(&mut self, instr: &LocalGet) {
+        self.0.insert(instr.local);
+    }
+}
+
+/// Removes the instructions emitted by `create_call_stack_local` for the
+/// `dead` locals, which align the stack pointer before reserving the space:
+/// `global.get $sp, i32.const <align - 1>, i32.add, i32.const <-align>,
+/// i32.and, local.tee $local, i32.const <size>, i32.add, global.set $sp`
+struct DeadReservationRemover<'a> {
+    stack_pointer: GlobalId,
+    dead: &'a HashSet<LocalId>,
+}
+
+impl DeadReservationRemover<'_> {
+    const PATTERN_LEN: usize = 9;
+
+    fn is_dead_reservation(&self, instrs: &[(Instr, InstrLocId)]) -> bool {
+        let [(get, _), (pad, _), (pad_add, _), (mask, _), (and, _), (tee, _), (size, _), (add, _), (set, _)] =
+            instrs
+        else {
+            return false;
+        };
+        let is_i32_const = |instr: &Instr| {
+            matches!(
+                instr,
+                Instr::Const(Const {
+                    value: walrus::ir::Value::I32(_)
+                })
+            )
+        };
+        matches!(get, Instr::GlobalGet(GlobalGet { global }) if *global == self.stack_pointer)
+            && is_i32_const(pad)
+            && matches!(
+                pad_add,
+                Instr::Binop(Binop {
+                    op: BinaryOp::I32Add
+                })
+            )
+            && is_i32_const(mask)
+            && matches!(
+                and,
+                Instr::Binop(Binop {
+                    op: BinaryOp::I32And
+                })
+            )
+            && matches!(tee, Instr::LocalTee(LocalTee { local }) if self.dead.contains(local))
+            && matches!(
+                size,
+                Instr::Const(Const {
+                    value: walrus::ir::Value::I32(_)
+                })
+            )
+            && matches!(
+                add,
+                Instr::Binop(Binop {
+                    op: BinaryOp::I32Add
+                })
+            )
+            && matches!(set, Instr::GlobalSet(GlobalSet { global }) if *global == self.stack_pointer)
+    }
+}
+
+impl VisitorMut for DeadReservationRemover<'_> {
+    fn start_instr_seq_mut(&mut self, seq: &mut InstrSeq) {
+        let mut i = 0;
+        while i + Self::PATTERN_LEN <= seq.instrs.len() {
+            if self.is_dead_reservation(&seq.instrs[i..i + Self::PATTERN_LEN]) {
+                seq.instrs.drain(i..i + Self::PATTERN_LEN);
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Stdlib functions with at most this many instructions are inlined when
+/// `inline_stdlib` is enabled.
+const STDLIB_INLINE_THRESHOLD: u64 = 32;
+
+/// Collects the ids of the instruction sequences of a function.
+#[derive(Default)]
+struct InstrSeqIds(Vec<InstrSeqId>);
+
+impl<'instr> Visitor<'instr> for InstrSeqIds {
+    fn start_instr_seq(&mut self, seq: &'instr InstrSeq) {
+        self.0.push(seq.id());
+    }
+}
+
+/// A copy of the body of a stdlib function, which can be inlined in other
+/// functions.
+struct InlinedBody {
+    /// The type of the block replacing a call, taking the arguments of the
+    /// function and leaving its results.
+    ty: InstrSeqType,
+    args: Vec<LocalId>,
+    entry: InstrSeqId,
+    seqs: HashMap<InstrSeqId, (InstrSeqType, Vec<Instr>)>,
+}
+
+impl InlinedBody {
+    /// Copies the body in a new block of `func`, with fresh locals, and
+    /// returns the id of the block.
+    fn inline_into(&self, func: &mut FunctionBuilder, locals: &mut ModuleLocals) -> InstrSeqId {
+        let block = func.dangling_instr_seq(self.ty).id();
+        let mut copier = BodyCopier {
+            body: self,
+            block,
+            seqs: HashMap::from([(self.entry, block)]),
+            locals: HashMap::new(),
+        };
+
+        // The arguments are on the stack when entering the block.
+        for arg in self.args.iter().rev() {
+            let local = copier.local(*arg, locals);
+            func.instr_seq(block).local_set(local);
+        }
+        copier.copy(func, locals, self.entry, block);
+
+        // The other locals of a function are zero on each call, but the
+        // copies keep their values when the block runs again, e.g. in the
+        // loop of a `fold`, so they are reset after the arguments.
+        let mut copied: Vec<LocalId> = copier
+            .locals
+            .iter()
+            .filter(|(local, _)| !self.args.contains(local))
+            .map(|(_, copy)| *copy)
+            .collect();
+        copied.sort();
+        let mut position = self.args.len();
+        for local in copied {
+            let zero = match locals.get(local).ty() {
+                ValType::I32 => Instr::Const(Const {
+                    value: walrus::ir::Value::I32(0),
+                }),
+                ValType::I64 => Instr::Const(Const {
+                    value: walrus::ir::Value::I64(0),
+                }),
+                ValType::F32 => Instr::Const(Const {
+                    value: walrus::ir::Value::F32(0.0),
+                }),
+                ValType::F64 => Instr::Const(Const {
+                    value: walrus::ir::Value::F64(0.0),
+                }),
+                ValType::V128 => Instr::Const(Const {
+                    value: walrus::ir::Value::V128(0),
+                }),
+                ty @ (ValType::Externref | ValType::Funcref) => Instr::RefNull(RefNull { ty }),
+            };
+            func.instr_seq(block)
+                .instr_at(position, zero)
+                .instr_at(position + 1, LocalSet { local });
+            position += 2;
+        }
+        block
+    }
+}
+
+/// Copies the instruction sequences of an [`InlinedBody`], remapping the
+/// sequences and locals to the copies.
+struct BodyCopier<'a> {
+    body: &'a InlinedBody,
+    block: InstrSeqId,
+    seqs: HashMap<InstrSeqId, InstrSeqId>,
+    locals: HashMap<LocalId, LocalId>,
+}
+
+impl BodyCopier<'_> {
+    fn local(&mut self, local: LocalId, locals: &mut ModuleLocals) -> LocalId {
+        *self.locals.entry(local).or_insert_with(|| {
+            let ty = locals.get(local).ty();
+            locals.add(ty)
+        })
+    }
+
+    fn nested(
+        &mut self,
+        func: &mut FunctionBuilder,
+        locals: &mut ModuleLocals,
+        seq: InstrSeqId,
+    ) -> InstrSeqId {
+        let copy = func.dangling_instr_seq(self.body.seqs[&seq].0).id();
+        self.seqs.insert(seq, copy);
+        self.copy(func, locals, seq, copy);
+        copy
+    }
+
+    fn copy(
+        &mut self,
+        func: &mut FunctionBuilder,
+        locals: &mut ModuleLocals,
+        from: InstrSeqId,
+        to: InstrSeqId,
+    ) {
+        let body = self.body;
+        for instr in &body.seqs[&from].1 {
+            let instr = match instr {
+                Instr::Block(Block { seq }) => Instr::Block(Block {
+                    seq: self.nested(func, locals, *seq),
+                }),
+                Instr::Loop(Loop { seq }) => Instr::Loop(Loop {
+                    seq: self.nested(func, locals, *seq),
+                }),
+                Instr::IfElse(IfElse {
+                    consequent,
+                    alternative,
+                }) => Instr::IfElse(IfElse {
+                    consequent: self.nested(func, locals, *consequent),
+                    alternative: self.nested(func, locals, *alternative),
+                }),
+                Instr::Br(Br { block }) => Instr::Br(Br {
+                    block: self.seqs[block],
+                }),
+                Instr::BrIf(BrIf { block }) => Instr::BrIf(BrIf {
+                    block: self.seqs[block],
+                }),
+                Instr::BrTable(BrTable { blocks, default }) => Instr::BrTable(BrTable {
+                    blocks: blocks.iter().map(|block| self.seqs[block]).collect(),
+                    default: self.seqs[default],
+                }),
+                // Returning from the inlined function exits its block.
+                Instr::Return(_) => Instr::Br(Br { block: self.block }),
+                Instr::LocalGet(LocalGet { local }) => Instr::LocalGet(LocalGet {
+                    local: self.local(*local, locals),
+                }),
+                Instr::LocalSet(LocalSet { local }) => Instr::LocalSet(LocalSet {
+                    local: self.local(*local, locals),
+                }),
+                Instr::LocalTee(LocalTee { local }) => Instr::LocalTee(LocalTee {
+                    local: self.local(*local, locals),
+                }),
+                instr => instr.clone(),
+            };
+            func.instr_seq(to).instr(instr);
+        }
+    }
+}
+
+/// Returns true if a composed type has an inner in-memory type.
+fn has_in_memory_type(ty: &TypeSignature) -> bool {
+    match ty {
+        TypeSignature::OptionalType(opt) => has_in_memory_type(opt),
+        TypeSignature::ResponseType(resp) => {
+            has_in_memory_type(&resp.0) || has_in_memory_type(&resp.1)
+        }
+        TypeSignature::TupleType(tup) => tup.get_type_map().values().any(has_in_memory_type),
+        TypeSignature::NoType
+        | TypeSignature::IntType
+        | TypeSignature::UIntType
+        | TypeSignature::BoolType => false,
+        TypeSignature::SequenceType(_)
+        | TypeSignature::PrincipalType
+        | TypeSignature::CallableType(_)
+        | TypeSignature::TraitReferenceType(_) => true,
+        TypeSignature::ListUnionType(_) => unreachable!("not a value type"),
+    }
+}
+
+/// A textual form of the body of `func` which is the same for two functions
+/// with the same instructions, whatever the ids of their locals and blocks.
+fn canonical_body(func: &LocalFunction, locals: &ModuleLocals) -> String {
+    fn write_seq(
+        func: &LocalFunction,
+        seq: InstrSeqId,
+        locals: &ModuleLocals,
+        local_ids: &mut HashMap<LocalId, usize>,
+        seq_ids: &mut HashMap<InstrSeqId, usize>,
+        body: &mut String,
+    ) {
+        let next_seq = seq_ids.len();
+        seq_ids.insert(seq, next_seq);
+        let _ = writeln!(body, "seq {:?}", func.block(seq).ty);
+        for (instr, _) in &func.block(seq).instrs {
+            let mut local = |local: &LocalId| {
+                let next_local = local_ids.len();
+                let index = *local_ids.entry(*local).or_insert(next_local);
+                format!("{index}:{}", locals.get(*local).ty())
+            };
+            let _ = match instr {
+                Instr::LocalGet(LocalGet { local: id }) => {
+                    writeln!(body, "local.get {}", local(id))
+                }
+                Instr::LocalSet(LocalSet { local: id }) => {
+                    writeln!(body, "local.set {}", local(id))
+                }
+                Instr::LocalTee(LocalTee { local: id }) => {
+                    writeln!(body, "local.tee {}", local(id))
+                }
+                Instr::Br(Br { block }) => writeln!(body, "br {}", seq_ids[block]),
+                Instr::BrIf(BrIf { block }) => writeln!(body, "br_if {}", seq_ids[block]),
+                Instr::BrTable(BrTable { blocks, default }) => writeln!(
+                    body,
+                    "br_table {:?} {}",
+                    blocks
+                        .iter()
+                        .map(|block| seq_ids[block])
+                        .collect::<Vec<_>>(),
+                    seq_ids[default]
+                ),
+                Instr::Block(Block { seq }) => {
+                    write_seq(func, *seq, locals, local_ids, seq_ids, body);
+                    writeln!(body, "end")
+                }
+                Instr::Loop(Loop { seq }) => {
+                    let _ = writeln!(body, "loop");
+                    write_seq(func, *seq, locals, local_ids, seq_ids, body);
+                    writeln!(body, "end")
+                }
+                Instr::IfElse(IfElse {
+                    consequent,
+                    alternative,
+                }) => {
+                    let _ = writeln!(body, "if");
+                    write_seq(func, *consequent, locals, local_ids, seq_ids, body);
+                    let _ = writeln!(body, "else");
+                    write_seq(func, *alternative, locals, local_ids, seq_ids, body);
+                    writeln!(body, "end")
+                }
+                _ => writeln!(body, "{instr:?}"),
+            };
+        }
+    }
+
+    let mut local_ids = HashMap::new();
+    let mut seq_ids = HashMap::new();
+    // The arguments are numbered first, so that their order matters.
+    for arg in &func.args {
+        let next_local = local_ids.len();
+        local_ids.insert(*arg, next_local);
+    }
+    let mut body = String::new();
+    write_seq(
+        func,
+        func.entry_block(),
+        locals,
+        &mut local_ids,
+        &mut seq_ids,
+        &mut body,
+    );
+    body
+}
+
+/// Replaces `memory.copy` and `memory.fill` with calls to `copy` and `fill`,
+/// which take the same operands.
+struct BulkMemoryLowering {
+    copy: FunctionId,
+    fill: FunctionId,
+}
+
+impl VisitorMut for BulkMemoryLowering {
+    fn start_instr_seq_mut(&mut self, seq: &mut InstrSeq) {
+        for (instr, _) in seq.instrs.iter_mut() {
+            let func = match instr {
+                Instr::MemoryCopy(_) => self.copy,
+                Instr::MemoryFill(_) => self.fill,
+                _ => continue,
+            };
+            *instr = Instr::Call(Call { func });
+        }
+    }
+}
+
+/// Redirects the calls to the functions in its keys to the function in the
+/// corresponding value.
+struct CallRedirector<'a>(&'a HashMap<FunctionId, FunctionId>);
+
+impl VisitorMut for CallRedirector<'_> {
+    fn visit_call_mut(&mut self, instr: &mut Call) {
+        if let Some(target) = self.0.get(&instr.func) {
+            instr.func = *target;
+        }
     }
 }
 
@@ -2215,16 +4191,33 @@ mod tests {
     use clarity::vm::analysis::AnalysisDatabase;
     use clarity::vm::costs::LimitedCostTracker;
     use clarity::vm::database::MemoryBackingStore;
+    use clarity::vm::diagnostic::Level;
     use clarity::vm::errors::{CheckErrors, Error};
-    use clarity::vm::types::{QualifiedContractIdentifier, StandardPrincipalData, TupleData};
-    use clarity::vm::{ClarityVersion, Value};
-    use walrus::Module;
+    use clarity::vm::types::signatures::CallableSubtype;
+    use clarity::vm::types::{
+        PrincipalData, QualifiedContractIdentifier, StandardPrincipalData, TupleData, TypeSignature,
+    };
+    use clarity::vm::{ClarityVersion, SymbolicExpression, SymbolicExpressionType, Value};
+    use sha2::{Digest, Sha256};
+    use walrus::{FunctionBuilder, Module, ValType};
 
     // Tests that don't relate to specific words
+    use crate::error_mapping::ErrorMap;
     use crate::{
-        compile,
-        tools::{crosscheck, evaluate},
-        wasm_generator::END_OF_STANDARD_DATA,
+        compile, compile_contract,
+        tools::{
+            compile_analysis, compile_snippet, compile_snippet_with_options, crosscheck,
+            crosscheck_with_env, evaluate, function_calls, instantiate, instantiate_with_config,
+            TestEnvironment,
+        },
+        type_check,
+        wasm_generator::{
+            call_stack_alignment, clar2wasm_ty, drop_value, FunctionContext, FunctionKind,
+            GeneratorError, MemoryRegion, WasmFeatures, WasmGenerator, END_OF_STANDARD_DATA,
+            SOURCE_HASH_SECTION,
+        },
+        wasm_utils::wasm_to_clarity_value,
+        CompileError, CompileOptions,
     };
 
     #[test]
@@ -2287,6 +4280,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn large_buffer_literal_grows_memory() {
+        let buff_len = 128 * 1024;
+        let snippet = format!("0x{}", "ab".repeat(buff_len));
+
+        let module = compile_snippet(&snippet).unwrap().module;
+        // The literal alone needs 2 pages, and there is the standard data
+        // before it.
+        assert!(module.memories.iter().next().unwrap().initial >= 3);
+
+        crosscheck(
+            &snippet,
+            Ok(Some(Value::buff_from(vec![0xab; buff_len]).unwrap())),
+        );
+    }
+
     #[test]
     fn end_of_standard_data_is_correct() {
         const STANDARD_LIB_PATH: &str =
@@ -2415,6 +4424,1529 @@ mod tests {
         crosscheck(snippet, Ok(Some(expected)));
     }
 
+    #[test]
+    fn code_generation_requires_a_type_map() {
+        let compiled =
+            compile_snippet("(define-read-only (double (a int)) (* a 2)) (double 21)").unwrap();
+
+        // Analyses stored in the datastore are saved without their type map.
+        let mut stored = compiled.contract_analysis;
+        stored.type_map = None;
+        let err = compile_contract(stored).unwrap_err();
+        let GeneratorError::TypeError(msg) = err else {
+            panic!("expected a type error, got {err:?}");
+        };
+        assert!(msg.contains("type-checked"), "{msg}");
+
+        // Type-checking the contract again types every expression.
+        let Ok(analysis) = type_check(
+            &compiled.ast,
+            &QualifiedContractIdentifier::transient(),
+            LimitedCostTracker::new_free(),
+            ClarityVersion::Clarity2,
+            StacksEpochId::Epoch25,
+            &mut AnalysisDatabase::new(&mut MemoryBackingStore::new()),
+        ) else {
+            panic!("type-checking failed");
+        };
+        assert!(compile_contract(analysis).is_ok());
+    }
+
+    #[test]
+    fn type_errors_are_reported_before_code_generation() {
+        let Err(CompileError::Generic { diagnostics, .. }) = compile_snippet("(+ 1 u1)") else {
+            panic!("expected the contract to be rejected");
+        };
+        let diagnostic = diagnostics.last().unwrap();
+        assert!(matches!(diagnostic.level, Level::Error));
+        // The message is the type checker's, not one of the generator.
+        assert!(
+            diagnostic
+                .message
+                .contains("expecting expression of type 'int', found 'uint'"),
+            "{}",
+            diagnostic.message
+        );
+    }
+
+    #[test]
+    fn wide_begin_compiles_quickly() {
+        // The statements of a `begin` are traversed in a loop, with the
+        // nested `begin`s unfolded in place.
+        let snippet = format!(
+            "(define-data-var counter int 0) (begin {} (var-get counter))",
+            "(var-set counter (+ (var-get counter) 1)) ".repeat(5000)
+        );
+
+        let start = std::time::Instant::now();
+        compile_analysis(&snippet);
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(3),
+            "compilation took {:?}",
+            start.elapsed()
+        );
+
+        assert_eq!(evaluate(&snippet), Ok(Some(Value::Int(5000))));
+    }
+
+    #[test]
+    fn nested_variadic_forms_are_unfolded() {
+        crosscheck(
+            "(define-data-var v int 0)
+(begin (var-set v 1) (begin (var-set v (+ (var-get v) 1)) (begin (var-get v))))",
+            Ok(Some(Value::Int(2))),
+        );
+        crosscheck(
+            "(begin (begin 1 (some u2)) (begin (ok 3)))",
+            Ok(Some(Value::okay(Value::Int(3)).unwrap())),
+        );
+        crosscheck(
+            "(and true (and true false) (and true))",
+            Ok(Some(Value::Bool(false))),
+        );
+        crosscheck("(or false (or false true))", Ok(Some(Value::Bool(true))));
+
+        // The branches after a false one are not evaluated.
+        crosscheck(
+            "(define-data-var v int 0)
+(and (and true false) (var-set v 1))
+(var-get v)",
+            Ok(Some(Value::Int(0))),
+        );
+
+        // The operands of the nested forms are branches of the outer one.
+        let analysis = compile_analysis("(and true (and true (and false true)))");
+        let expr = analysis.expressions[0].clone();
+        let mut generator = WasmGenerator::new(analysis).unwrap();
+        let args = &expr.match_list().unwrap()[1..];
+        let operands = generator
+            .flatten_variadic("and", args, |_, _, _| Ok(()))
+            .unwrap();
+        assert_eq!(operands.len(), 4);
+    }
+
+    #[test]
+    fn generator_error_codes() {
+        let type_error = GeneratorError::TypeError("expected int".to_owned());
+        assert_eq!(type_error.code(), "E_TYPE");
+        assert_eq!(type_error.to_string(), "[E_TYPE] Type error: expected int");
+
+        let internal_error = GeneratorError::InternalError("oops".to_owned());
+        assert_eq!(internal_error.code(), "E_INTERNAL");
+        assert!(internal_error.to_string().contains("E_INTERNAL"));
+
+        let error: Box<dyn std::error::Error> = Box::new(internal_error);
+        assert_eq!(error.to_string(), "[E_INTERNAL] Internal error: oops");
+    }
+
+    /// Compiles `snippet` with `options`, and returns the module and the
+    /// result of its top-level.
+    fn run_top_level_int(snippet: &str, options: &CompileOptions) -> (Module, i128) {
+        let mut module = compile_snippet_with_options(snippet, options)
+            .unwrap()
+            .module;
+
+        // Validate the module with the same proposals as the generator.
+        let mut config = wasmtime::Config::new();
+        config
+            .wasm_bulk_memory(options.features.bulk_memory)
+            .wasm_reference_types(options.features.bulk_memory);
+        let (mut store, instance) = instantiate_with_config(&mut module, &config);
+        let (lo, hi) = instance
+            .get_typed_func::<(), (i64, i64)>(&mut store, ".top-level")
+            .unwrap()
+            .call(&mut store, ())
+            .unwrap();
+        (module, ((hi as i128) << 64) | (lo as u64 as i128))
+    }
+
+    #[test]
+    fn inline_stdlib_gives_identical_results() {
+        let elements = (0..100).map(|i| i.to_string()).collect::<Vec<_>>();
+        let snippet = format!(
+            "(define-private (count-less (x int) (acc int)) (if (< x 50) (+ acc 1) (- acc 1))) \
+             (fold count-less (list {}) 1000)",
+            elements.join(" ")
+        );
+
+        let (_, called) = run_top_level_int(&snippet, &CompileOptions::default());
+        let (inlined_module, inlined) = run_top_level_int(
+            &snippet,
+            &CompileOptions {
+                inline_stdlib: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(called, 1000);
+        assert_eq!(inlined, called);
+
+        // The comparison is not called anymore from the user function.
+        let lt_int = inlined_module.funcs.by_name("stdlib.lt-int").unwrap();
+        let count_less = inlined_module.funcs.by_name("count-less").unwrap();
+        let walrus::FunctionKind::Local(func) = &inlined_module.funcs.get(count_less).kind else {
+            panic!("count-less should be a local function");
+        };
+        assert!(!function_calls(func).contains(&lt_int));
+    }
+
+    #[test]
+    fn inline_stdlib_in_fold() {
+        // The arithmetic is inlined in the loop of the `fold`.
+        for (snippet, expected) in [
+            ("(fold + (list 1 2 3 4) 0)", 10),
+            ("(fold * (list 1 2 3 4) 1)", 24),
+            ("(fold - (list 1 2 3 4) 0)", 2),
+        ] {
+            let (_, called) = run_top_level_int(snippet, &CompileOptions::default());
+            let (_, inlined) = run_top_level_int(
+                snippet,
+                &CompileOptions {
+                    inline_stdlib: true,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(called, expected);
+            assert_eq!(inlined, called);
+        }
+    }
+
+    #[test]
+    fn inlined_locals_are_reset_in_loops() {
+        let contract_analysis = compile_analysis("(+ 1 2)");
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        generator.inline_stdlib = true;
+
+        // A function which reads its local before setting it, and so relies
+        // on it being zero on each call.
+        let x = generator.module.locals.add(ValType::I64);
+        let acc = generator.module.locals.add(ValType::I64);
+        let mut func = FunctionBuilder::new(
+            &mut generator.module.types,
+            &[ValType::I64],
+            &[ValType::I64],
+        );
+        func.name("stdlib.accumulate".to_owned());
+        func.func_body()
+            .local_get(acc)
+            .local_get(x)
+            .binop(BinaryOp::I64Add)
+            .local_tee(acc);
+        let accumulate = func.finish(vec![x], &mut generator.module.funcs);
+
+        // Calls it three times in a loop, like a `fold`.
+        let i = generator.module.locals.add(ValType::I32);
+        let total = generator.module.locals.add(ValType::I64);
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[ValType::I64]);
+        func.func_body().block(None, |done| {
+            let done_id = done.id();
+            done.loop_(None, |body| {
+                let body_id = body.id();
+                body.local_get(i)
+                    .i32_const(3)
+                    .binop(BinaryOp::I32GeU)
+                    .br_if(done_id);
+                body.local_get(total)
+                    .i64_const(1)
+                    .call(accumulate)
+                    .binop(BinaryOp::I64Add)
+                    .local_set(total);
+                body.local_get(i)
+                    .i32_const(1)
+                    .binop(BinaryOp::I32Add)
+                    .local_set(i)
+                    .br(body_id);
+            });
+        });
+        func.func_body().local_get(total);
+        let sum = func.finish(vec![], &mut generator.module.funcs);
+        generator.module.exports.add("sum", sum);
+        let (mut store, instance) = instantiate(&mut generator.generate().unwrap().module);
+        let result = instance
+            .get_typed_func::<(), i64>(&mut store, "sum")
+            .unwrap()
+            .call(&mut store, ())
+            .unwrap();
+        assert_eq!(result, 3);
+    }
+
+    #[test]
+    fn without_bulk_memory_uses_copy_and_fill_loops() {
+        // `concat` copies with the generated code, `sha256` copies and fills
+        // in the stdlib.
+        for snippet in [
+            r#"(if (is-eq (concat "abc" "de") "abcde") 1 0)"#,
+            "(if (is-eq (sha256 0x616263) \
+             0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad) 1 0)",
+        ] {
+            let (_, with_bulk) = run_top_level_int(snippet, &CompileOptions::default());
+            let (module, without_bulk) = run_top_level_int(
+                snippet,
+                &CompileOptions {
+                    features: WasmFeatures { bulk_memory: false },
+                    ..Default::default()
+                },
+            );
+            assert_eq!(with_bulk, 1);
+            assert_eq!(without_bulk, with_bulk);
+
+            #[derive(Default)]
+            struct BulkMemory(usize);
+            impl<'instr> walrus::ir::Visitor<'instr> for BulkMemory {
+                fn visit_memory_copy(&mut self, _instr: &walrus::ir::MemoryCopy) {
+                    self.0 += 1;
+                }
+                fn visit_memory_fill(&mut self, _instr: &walrus::ir::MemoryFill) {
+                    self.0 += 1;
+                }
+            }
+            let mut bulk_memory = BulkMemory::default();
+            for func in module.funcs.iter_local().map(|(_, func)| func) {
+                walrus::ir::dfs_in_order(&mut bulk_memory, func, func.entry_block());
+            }
+            assert_eq!(bulk_memory.0, 0);
+        }
+    }
+
+    #[test]
+    fn call_stack_grows_memory_in_loops() {
+        // Each call to `f` in the `map` reserves space for its result, so the
+        // call stack outgrows the initial memory, computed from the frames.
+        let snippet = format!(
+            "
+(define-constant BIG 0x{})
+(define-private (f (x uint)) BIG)
+(define-private (check (b (buff 1000)) (acc bool)) (and acc (is-eq b BIG)))
+(fold check (map f (list {})) true)
+",
+            "ab".repeat(1000),
+            "u1 ".repeat(200)
+        );
+
+        let module = compile_snippet(&snippet).unwrap().module;
+        let initial_pages = module.memories.iter().next().unwrap().initial;
+        assert!((initial_pages as usize) * 65536 < 200 * 1000);
+
+        crosscheck(&snippet, Ok(Some(Value::Bool(true))));
+    }
+
+    #[test]
+    fn stack_memory_is_ensured_once_per_function() {
+        // `hash` reserves a call stack local for each of its results.
+        let snippet = "
+(define-private (hash (a (buff 32)) (b (buff 32)))
+  (concat (sha256 a) (sha256 b)))
+(hash (sha256 0x01) (sha256 0x02))
+";
+        let module = compile_snippet(snippet).unwrap().module;
+        let ensure = module.funcs.by_name("stdlib.ensure-stack-memory").unwrap();
+
+        let functions = [
+            ("hash", module.funcs.by_name("hash").unwrap()),
+            (".top-level", module.exports.get_func(".top-level").unwrap()),
+        ];
+        for (name, func) in functions {
+            let local = module.funcs.get(func).kind.unwrap_local();
+            let calls = function_calls(local);
+            assert_eq!(calls.iter().filter(|&&f| f == ensure).count(), 1, "{name}");
+        }
+    }
+
+    #[test]
+    fn stack_memory_is_not_ensured_for_empty_frames() {
+        // Neither `one` nor the top-level reserve call stack space.
+        let module = compile_snippet("(define-private (one) 1) (+ (one) 2)")
+            .unwrap()
+            .module;
+        let Some(ensure) = module.funcs.by_name("stdlib.ensure-stack-memory") else {
+            return;
+        };
+
+        let functions = [
+            ("one", module.funcs.by_name("one").unwrap()),
+            (".top-level", module.exports.get_func(".top-level").unwrap()),
+        ];
+        for (name, func) in functions {
+            let local = module.funcs.get(func).kind.unwrap_local();
+            assert!(!function_calls(local).contains(&ensure), "{name}");
+        }
+    }
+
+    #[test]
+    fn call_stack_limit_traps_with_stack_overflow() {
+        let snippet = format!(
+            "
+(define-constant BIG 0x{})
+(define-private (f (x uint)) BIG)
+(len (map f (list {})))
+",
+            "ab".repeat(1000),
+            "u1 ".repeat(200)
+        );
+
+        let run = |max_call_stack| {
+            let mut module = compile_snippet_with_options(
+                &snippet,
+                &CompileOptions {
+                    max_call_stack,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .module;
+
+            let (mut store, instance) = instantiate(&mut module);
+
+            let top_level = instance.get_func(&mut store, ".top-level").unwrap();
+            let mut results = [wasmtime::Val::I64(0), wasmtime::Val::I64(0)];
+            let call = top_level.call(&mut store, &[], &mut results);
+            let error_code = instance
+                .get_global(&mut store, "runtime-error-code")
+                .unwrap()
+                .get(&mut store);
+            (call.is_ok(), error_code.i32())
+        };
+
+        assert!(run(None).0);
+        assert!(run(Some(1 << 20)).0);
+        assert_eq!(
+            run(Some(4096)),
+            (false, Some(ErrorMap::StackOverflow as i32))
+        );
+    }
+
+    #[test]
+    fn written_buffers_are_not_zero_initialized() {
+        // Words writing a result buffer fill it entirely, so the generator
+        // emits no `memory.fill` that a later copy or store would overwrite.
+        #[derive(Default)]
+        struct Fills(usize);
+        impl<'instr> walrus::ir::Visitor<'instr> for Fills {
+            fn visit_memory_fill(&mut self, _instr: &walrus::ir::MemoryFill) {
+                self.0 += 1;
+            }
+        }
+
+        for snippet in [
+            r#"(if (is-eq (concat "abc" "de") "abcde") 1 0)"#,
+            r#"(if (is-eq (int-to-ascii (+ 40 2)) "42") 1 0)"#,
+            "(if (is-eq (list 1 2 3) (list 1 2 3)) 1 0)",
+            "(if (is-eq (sha256 0x01) (sha256 0x01)) 1 0)",
+        ] {
+            let (module, result) = run_top_level_int(snippet, &CompileOptions::default());
+            assert_eq!(result, 1, "{snippet}");
+
+            let top_level = module.exports.get_func(".top-level").unwrap();
+            let walrus::FunctionKind::Local(func) = &module.funcs.get(top_level).kind else {
+                panic!(".top-level should be a local function");
+            };
+            let mut fills = Fills::default();
+            walrus::ir::dfs_in_order(&mut fills, func, func.entry_block());
+            assert_eq!(fills.0, 0, "{snippet}");
+        }
+    }
+
+    #[test]
+    fn current_function_context() {
+        let snippet = "
+(define-private (foo (a int) (b uint))
+  (let ((c (+ a 1)))
+    (begin
+      (try! (if (> c 2) (ok c) (err b)))
+      (ok c))))
+(+ 1 2)
+";
+        let contract_analysis = compile_analysis(snippet);
+        let SymbolicExpressionType::List(define) = &contract_analysis.expressions[0].expr else {
+            panic!("expected a list");
+        };
+        let body = define[2].clone();
+        let top_level = contract_analysis.expressions[1].clone();
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let Some(clarity::vm::types::FunctionType::Fixed(foo)) = generator
+            .contract_analysis
+            .get_private_function("foo")
+            .cloned()
+        else {
+            panic!("expected the type of foo");
+        };
+
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        generator
+            .traverse_define_function(
+                &mut func.func_body(),
+                &"foo".into(),
+                &body,
+                FunctionKind::Private,
+            )
+            .unwrap();
+        assert!(generator.current_function().is_none());
+
+        // Every word of the nested body is traversed in `foo`.
+        let contexts = std::mem::take(&mut generator.traversed_contexts);
+        let words: Vec<_> = contexts.iter().map(|(word, _)| word.as_str()).collect();
+        assert_eq!(
+            words,
+            vec!["let", "+", "begin", "try!", "if", ">", "ok", "err", "ok"]
+        );
+        for (word, context) in &contexts {
+            let context = context
+                .as_ref()
+                .unwrap_or_else(|| panic!("{word} is traversed outside of foo"));
+            assert_eq!(context.name.as_str(), "foo");
+            assert_eq!(context.returns, foo.returns);
+            let arg_types: Vec<_> = context.args.iter().map(|arg| &arg.signature).collect();
+            assert_eq!(
+                arg_types,
+                vec![&TypeSignature::IntType, &TypeSignature::UIntType]
+            );
+        }
+
+        // The top level is not in any function.
+        generator
+            .traverse_expr(&mut func.func_body(), &top_level)
+            .unwrap();
+        let contexts = std::mem::take(&mut generator.traversed_contexts);
+        assert!(matches!(
+            contexts.as_slice(),
+            [(word, None)] if word.as_str() == "+"
+        ));
+
+        // The early return of `try!` inside the nested `let` and `begin`
+        // uses the return type of `foo`.
+        crosscheck(
+            &format!("{snippet} (foo 1 u7)"),
+            Ok(Some(Value::error(Value::UInt(7)).unwrap())),
+        );
+        crosscheck(
+            &format!("{snippet} (foo 5 u7)"),
+            Ok(Some(Value::okay(Value::Int(6)).unwrap())),
+        );
+    }
+
+    #[test]
+    fn entry_wrappers_for_public_functions() {
+        let snippet = "
+(define-public (add (a int) (b int)) (ok (+ a b)))
+(define-read-only (get-one) 1)
+";
+        let mut module = compile_snippet_with_options(
+            snippet,
+            &CompileOptions {
+                entry_wrappers: true,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .module;
+
+        let wrapper = module.exports.get_func("add.entry").unwrap();
+        let ty = module.types.get(module.funcs.get(wrapper).ty());
+        assert_eq!(ty.params(), &[ValType::I32; 4]);
+        assert_eq!(ty.results(), &[ValType::I32]);
+        assert!(module.exports.get_func("get-one.entry").is_err());
+
+        let (mut store, instance) = instantiate(&mut module);
+        let memory = instance.get_memory(&mut store, "memory").unwrap();
+        let entry = instance
+            .get_typed_func::<(i32, i32, i32, i32), i32>(&mut store, "add.entry")
+            .unwrap();
+
+        // The buffers are placed at the end of the memory, away from the
+        // call stack.
+        let args_offset = memory.data_size(&store) as i32 - 256;
+        let result_offset = args_offset + 128;
+        let mut args = Value::Int(40).serialize_to_vec().unwrap();
+        args.extend(Value::Int(2).serialize_to_vec().unwrap());
+        memory
+            .write(&mut store, args_offset as usize, &args)
+            .unwrap();
+
+        let length = entry
+            .call(
+                &mut store,
+                (args_offset, args.len() as i32, result_offset, 128),
+            )
+            .unwrap();
+        let expected = Value::okay(Value::Int(42))
+            .unwrap()
+            .serialize_to_vec()
+            .unwrap();
+        assert_eq!(length, expected.len() as i32);
+        let mut result = vec![0; expected.len()];
+        memory
+            .read(&store, result_offset as usize, &mut result)
+            .unwrap();
+        assert_eq!(result, expected);
+
+        // A missing argument is rejected.
+        assert!(entry
+            .call(
+                &mut store,
+                (args_offset, args.len() as i32 - 17, result_offset, 128),
+            )
+            .is_err());
+        let error_code = instance
+            .get_global(&mut store, "runtime-error-code")
+            .unwrap()
+            .get(&mut store);
+        assert_eq!(
+            error_code.i32(),
+            Some(ErrorMap::ArgumentTypeMismatch as i32)
+        );
+    }
+
+    #[test]
+    fn entry_wrapper_rejects_mistyped_arguments() {
+        let snippet = "(define-public (add (a int) (b int)) (ok (+ a b)))";
+        let mut module = compile_snippet_with_options(
+            snippet,
+            &CompileOptions {
+                entry_wrappers: true,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .module;
+
+        let (mut store, instance) = instantiate(&mut module);
+        let memory = instance.get_memory(&mut store, "memory").unwrap();
+        let entry = instance
+            .get_typed_func::<(i32, i32, i32, i32), i32>(&mut store, "add.entry")
+            .unwrap();
+        let stack_pointer = instance.get_global(&mut store, "stack-pointer").unwrap();
+        let initial_stack_pointer = stack_pointer.get(&mut store).i32();
+
+        // The second argument is a `uint` where an `int` is declared.
+        let args_offset = memory.data_size(&store) as i32 - 256;
+        let result_offset = args_offset + 128;
+        let mut args = Value::Int(40).serialize_to_vec().unwrap();
+        args.extend(Value::UInt(2).serialize_to_vec().unwrap());
+        memory
+            .write(&mut store, args_offset as usize, &args)
+            .unwrap();
+
+        assert!(entry
+            .call(
+                &mut store,
+                (args_offset, args.len() as i32, result_offset, 128)
+            )
+            .is_err());
+        let error_code = instance
+            .get_global(&mut store, "runtime-error-code")
+            .unwrap()
+            .get(&mut store);
+        assert_eq!(
+            error_code.i32(),
+            Some(ErrorMap::ArgumentTypeMismatch as i32)
+        );
+        assert_eq!(stack_pointer.get(&mut store).i32(), initial_stack_pointer);
+    }
+
+    #[test]
+    fn entry_wrapper_rejects_small_result_buffer() {
+        let snippet = "(define-public (add (a int) (b int)) (ok (+ a b)))";
+        let mut module = compile_snippet_with_options(
+            snippet,
+            &CompileOptions {
+                entry_wrappers: true,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .module;
+
+        let (mut store, instance) = instantiate(&mut module);
+        let memory = instance.get_memory(&mut store, "memory").unwrap();
+        let entry = instance
+            .get_typed_func::<(i32, i32, i32, i32), i32>(&mut store, "add.entry")
+            .unwrap();
+
+        let args_offset = memory.data_size(&store) as i32 - 256;
+        let result_offset = args_offset + 128;
+        let mut args = Value::Int(40).serialize_to_vec().unwrap();
+        args.extend(Value::Int(2).serialize_to_vec().unwrap());
+        memory
+            .write(&mut store, args_offset as usize, &args)
+            .unwrap();
+        memory
+            .write(&mut store, result_offset as usize, &[0xff; 32])
+            .unwrap();
+
+        // `(ok 42)` takes 18 bytes.
+        assert!(entry
+            .call(
+                &mut store,
+                (args_offset, args.len() as i32, result_offset, 17)
+            )
+            .is_err());
+        let error_code = instance
+            .get_global(&mut store, "runtime-error-code")
+            .unwrap()
+            .get(&mut store);
+        assert_eq!(error_code.i32(), Some(ErrorMap::ResultTooLarge as i32));
+        let mut result = [0; 32];
+        memory
+            .read(&store, result_offset as usize, &mut result)
+            .unwrap();
+        assert_eq!(result, [0xff; 32]);
+
+        assert_eq!(
+            entry
+                .call(
+                    &mut store,
+                    (args_offset, args.len() as i32, result_offset, 18)
+                )
+                .unwrap(),
+            18
+        );
+    }
+
+    #[test]
+    fn defined_functions_are_exported_by_name() {
+        let snippet = "
+(define-read-only (double (a int)) (* a 2))
+(define-public (increment (a uint)) (if (< a u100) (ok (+ a u1)) (err a)))
+(define-private (hidden) true)
+";
+        let mut module = compile_snippet(snippet).unwrap().module;
+        assert!(module.exports.get_func("hidden").is_err());
+
+        let (mut store, instance) = instantiate(&mut module);
+
+        // Each function is called on its own, without running the top-level.
+        let double = instance
+            .get_typed_func::<(i64, i64), (i64, i64)>(&mut store, "double")
+            .unwrap();
+        assert_eq!(double.call(&mut store, (21, 0)).unwrap(), (42, 0));
+
+        // The response is returned as its indicator, followed by the ok and
+        // err values.
+        let increment = instance
+            .get_typed_func::<(i64, i64), (i32, i64, i64, i64, i64)>(&mut store, "increment")
+            .unwrap();
+        assert_eq!(
+            increment.call(&mut store, (41, 0)).unwrap(),
+            (1, 42, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn runtime_errors_set_their_code_before_trapping() {
+        for (snippet, code) in [
+            ("(/ 1 0)", ErrorMap::DivisionByZero),
+            (
+                "(+ 170141183460469231731687303715884105727 1)",
+                ErrorMap::ArithmeticOverflow,
+            ),
+        ] {
+            let mut module = compile_snippet(snippet).unwrap().module;
+
+            let (mut store, instance) = instantiate(&mut module);
+
+            let top_level = instance.get_func(&mut store, ".top-level").unwrap();
+            let mut results = [wasmtime::Val::I64(0), wasmtime::Val::I64(0)];
+            assert!(top_level.call(&mut store, &[], &mut results).is_err());
+
+            let error_code = instance
+                .get_global(&mut store, "runtime-error-code")
+                .unwrap()
+                .get(&mut store);
+            assert_eq!(error_code.i32(), Some(code as i32), "{snippet}");
+        }
+    }
+
+    #[test]
+    fn compilation_is_reproducible() {
+        let snippet = r#"
+(define-constant greeting "hello")
+(define-map names principal (string-ascii 16))
+(define-public (greet (who principal))
+  (begin
+    (map-set names who "world")
+    (print { greeting: greeting, bytes: 0x0102, utf8: u"caf\u{e9}" })
+    (ok (concat greeting (default-to "" (map-get? names who))))))
+(greet tx-sender)
+"#;
+        let compile_module = || compile_snippet(snippet).unwrap().module;
+
+        let mut first = compile_module();
+        let mut second = compile_module();
+        assert_eq!(first.emit_wasm(), second.emit_wasm());
+
+        // The literals are laid out in the order of the traversal, without
+        // overlapping.
+        let offsets: Vec<_> = first
+            .data
+            .iter()
+            .filter_map(|data| match data.kind {
+                walrus::DataKind::Active(walrus::ActiveData {
+                    location: walrus::ActiveDataLocation::Absolute(offset),
+                    ..
+                }) if offset >= END_OF_STANDARD_DATA => Some((offset, data.value.len() as u32)),
+                _ => None,
+            })
+            .collect();
+        assert!(!offsets.is_empty());
+        assert!(offsets
+            .windows(2)
+            .all(|pair| pair[0].0 + pair[0].1 <= pair[1].0));
+    }
+
+    #[test]
+    fn source_hash_section() {
+        let snippet = "(define-read-only (get-one) 1)";
+        let compile_module = |source_hash| {
+            compile_snippet_with_options(
+                snippet,
+                &CompileOptions {
+                    source_hash,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .module
+        };
+
+        let mut module = compile_module(true);
+        let section = module.customs.remove_raw(SOURCE_HASH_SECTION).unwrap();
+        let mut expected = Sha256::digest(snippet).to_vec();
+        expected.extend_from_slice(env!("CARGO_PKG_VERSION").as_bytes());
+        assert_eq!(section.data, expected);
+
+        let mut module = compile_module(false);
+        assert!(module.customs.remove_raw(SOURCE_HASH_SECTION).is_none());
+    }
+
+    #[test]
+    fn identical_functions_are_merged() {
+        let snippet = "
+(define-private (inc-a (x int)) (+ x 1))
+(define-private (inc-b (x int)) (+ x 1))
+(define-private (twice-a (x int)) (inc-a (inc-a x)))
+(define-private (twice-b (x int)) (inc-b (inc-b x)))
+(define-read-only (double-a (x int)) (* x 2))
+(define-read-only (double-b (x int)) (* x 2))
+(+
+  (fold + (map inc-a (list 1 2)) 0)
+  (fold + (map inc-b (list 1 2)) 0)
+  (twice-a 1)
+  (twice-b 1)
+  (double-b 1)
+)
+";
+        let mut generator = WasmGenerator::new(compile_analysis(snippet)).unwrap();
+        generator.deduplicate_functions = true;
+        let generated = generator.generate().unwrap();
+
+        // Only the first of the identical functions is emitted. `twice-b` is
+        // identical to `twice-a` once its calls are redirected to `inc-a`.
+        let funcs = &generated.module.funcs;
+        assert!(funcs.by_name("inc-a").is_some());
+        assert!(funcs.by_name("inc-b").is_none());
+        assert!(funcs.by_name("twice-a").is_some());
+        assert!(funcs.by_name("twice-b").is_none());
+
+        // Exported functions are kept, even if identical.
+        let double_b = generated.module.exports.get_func("double-b").unwrap();
+        assert_eq!(funcs.get(double_b).name.as_deref(), Some("double-b"));
+
+        let (_, merged) = run_top_level_int(
+            snippet,
+            &CompileOptions {
+                deduplicate_functions: true,
+                ..Default::default()
+            },
+        );
+        let (_, unmerged) = run_top_level_int(snippet, &CompileOptions::default());
+        assert_eq!(merged, 18);
+        assert_eq!(unmerged, merged);
+    }
+
+    #[test]
+    fn functions_are_not_merged_by_default() {
+        let snippet = "
+(define-private (inc-a (x int)) (+ x 1))
+(define-private (inc-b (x int)) (+ x 1))
+(+ (inc-a 1) (inc-b 1))
+";
+        let generated = WasmGenerator::new(compile_analysis(snippet))
+            .unwrap()
+            .generate()
+            .unwrap();
+        assert!(generated.module.funcs.by_name("inc-a").is_some());
+        assert!(generated.module.funcs.by_name("inc-b").is_some());
+    }
+
+    #[test]
+    fn instruction_budget_exceeded() {
+        let snippet = format!(
+            "(define-read-only (big) (fold + (list {}) 0))",
+            (0..500)
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+
+        let generated = WasmGenerator::new(compile_analysis(&snippet))
+            .unwrap()
+            .generate()
+            .unwrap();
+        assert!(generated.stats.instructions > 1000);
+
+        let mut generator = WasmGenerator::new(compile_analysis(&snippet)).unwrap();
+        generator.instruction_budget = Some(1000);
+        let Err(err) = generator.generate() else {
+            panic!("expected the instruction budget to be exceeded");
+        };
+        assert!(matches!(
+            err,
+            GeneratorError::InstructionBudgetExceeded {
+                instructions,
+                budget: 1000,
+            } if instructions > 1000
+        ));
+
+        let Err(CompileError::Generic { diagnostics, .. }) = compile_snippet_with_options(
+            &snippet,
+            &CompileOptions {
+                instruction_budget: Some(1000),
+                ..Default::default()
+            },
+        ) else {
+            panic!("expected the contract to be rejected");
+        };
+        assert!(diagnostics
+            .last()
+            .unwrap()
+            .message
+            .contains("exceeding the budget of 1000"));
+    }
+
+    #[test]
+    fn instruction_budget_is_checked_during_traversal() {
+        let snippet = format!(
+            "(+ {})",
+            (0..500)
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        let contract_analysis = compile_analysis(&snippet);
+        let expr = contract_analysis.expressions[0].clone();
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        generator.instruction_budget = Some(100);
+
+        // The budget is exceeded before any function is finished.
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        let err = generator
+            .traverse_expr(&mut func.func_body(), &expr)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            GeneratorError::InstructionBudgetExceeded {
+                instructions,
+                budget: 100,
+            } if instructions > 100
+        ));
+        assert_eq!(generator.finished_instructions, 0);
+    }
+
+    #[test]
+    fn stats_are_populated() {
+        let snippet = r#"
+(define-data-var greeting (string-ascii 16) "hello")
+(define-public (sum (a int) (b int))
+  (ok (fold + (list a b 3) 0)))
+(define-read-only (greet)
+  (concat (var-get greeting) " world"))
+"#;
+        let generated = WasmGenerator::new(compile_analysis(snippet))
+            .unwrap()
+            .generate()
+            .unwrap();
+        let stats = generated.stats;
+
+        assert!(stats.instructions > 0);
+        assert!(stats.stdlib_calls > 0);
+        assert!(stats.stdlib_calls < stats.instructions);
+        assert!(stats.stack_bytes > 0);
+        assert!(stats.data_bytes > 0);
+        assert_eq!(
+            stats.data_bytes,
+            generated
+                .module
+                .data
+                .iter()
+                .map(|data| data.value.len())
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn drop_value_drops_each_slot() {
+        let count_drops = |ty: &TypeSignature| {
+            let mut module = Module::default();
+            let mut func = FunctionBuilder::new(&mut module.types, &[], &[]);
+            let mut body = func.func_body();
+            drop_value(&mut body, ty);
+            body.instrs()
+                .iter()
+                .filter(|(instr, _)| matches!(instr, walrus::ir::Instr::Drop(_)))
+                .count()
+        };
+
+        assert_eq!(count_drops(&TypeSignature::IntType), 2);
+        let tuple = TypeSignature::TupleType(
+            vec![
+                ("a".into(), TypeSignature::IntType),
+                ("b".into(), TypeSignature::IntType),
+            ]
+            .try_into()
+            .unwrap(),
+        );
+        assert_eq!(count_drops(&tuple), 4);
+        let optional = TypeSignature::new_option(TypeSignature::IntType).unwrap();
+        assert_eq!(count_drops(&optional), 3);
+    }
+
+    #[test]
+    fn no_code_after_diverging_statement() {
+        // `(unwrap-panic none)` is rejected by the type-checker, so the `none`
+        // and its type are substituted after analysis. Only the type tells
+        // that the unwrap always panics.
+        let analysis = compile_analysis("(begin (unwrap-panic (some u2)) u1)");
+        let mut begin = analysis.expressions[0].clone();
+        let SymbolicExpressionType::List(statements) = &mut begin.expr else {
+            panic!("expected a list");
+        };
+        let SymbolicExpressionType::List(unwrap) = &mut statements[1].expr else {
+            panic!("expected a list");
+        };
+        unwrap[1].expr = SymbolicExpressionType::Atom("none".into());
+        let none = unwrap[1].clone();
+
+        let mut generator = WasmGenerator::new(analysis).unwrap();
+        generator
+            .set_expr_type(
+                &none,
+                TypeSignature::OptionalType(Box::new(TypeSignature::NoType)),
+            )
+            .unwrap();
+        let mut func = FunctionBuilder::new(
+            &mut generator.module.types,
+            &[],
+            &[ValType::I64, ValType::I64],
+        );
+        generator
+            .traverse_expr(&mut func.func_body(), &begin)
+            .unwrap();
+        let func = func.finish(vec![], &mut generator.module.funcs);
+        generator.module.exports.add("dead-code", func);
+        let mut module = generator.generate().unwrap().module;
+
+        // The `u1` is never pushed.
+        #[derive(Default)]
+        struct Consts(Vec<walrus::ir::Value>);
+        impl<'instr> walrus::ir::Visitor<'instr> for Consts {
+            fn visit_const(&mut self, instr: &walrus::ir::Const) {
+                self.0.push(instr.value);
+            }
+        }
+        let walrus::FunctionKind::Local(local) = &module.funcs.get(func).kind else {
+            panic!("dead-code should be a local function");
+        };
+        let mut consts = Consts::default();
+        walrus::ir::dfs_in_order(&mut consts, local, local.entry_block());
+        assert!(!consts
+            .0
+            .iter()
+            .any(|value| matches!(value, walrus::ir::Value::I64(1))));
+
+        // The evaluation still traps.
+        let (mut store, instance) = instantiate(&mut module);
+        assert!(instance
+            .get_typed_func::<(), (i64, i64)>(&mut store, "dead-code")
+            .unwrap()
+            .call(&mut store, ())
+            .is_err());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn stack_shape_matches_expression_type() {
+        let analysis = compile_analysis("(+ 1 (len (list 1 2 3)))");
+        let expr = analysis.expressions[0].clone();
+        let mut generator = WasmGenerator::new(analysis).unwrap();
+        let mut func = FunctionBuilder::new(
+            &mut generator.module.types,
+            &[],
+            &[ValType::I64, ValType::I64],
+        );
+
+        // `traverse_expr` checks the stack shape of every sub-expression.
+        generator
+            .traverse_expr(&mut func.func_body(), &expr)
+            .unwrap();
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "leaves 1 values on the stack instead of 2")]
+    fn stack_shape_mismatch_is_detected() {
+        use clarity::vm::ClarityName;
+        use walrus::InstrSeqBuilder;
+
+        use crate::wasm_utils::Arity;
+        use crate::words::{ComplexWord, Word};
+
+        // An `int` needs two i64s, but this word only pushes one.
+        #[derive(Debug)]
+        struct BrokenAdd;
+        impl Word for BrokenAdd {
+            fn name(&self) -> ClarityName {
+                "+".into()
+            }
+        }
+        impl ComplexWord for BrokenAdd {
+            fn arity(&self) -> Arity {
+                Arity::Exact(2)
+            }
+
+            fn traverse(
+                &self,
+                _generator: &mut WasmGenerator,
+                builder: &mut InstrSeqBuilder,
+                _expr: &SymbolicExpression,
+                _args: &[SymbolicExpression],
+            ) -> Result<(), GeneratorError> {
+                builder.i64_const(3);
+                Ok(())
+            }
+        }
+
+        let analysis = compile_analysis("(+ 1 2)");
+        let expr = analysis.expressions[0].clone();
+        let mut generator = WasmGenerator::new(analysis).unwrap();
+        let mut func = FunctionBuilder::new(
+            &mut generator.module.types,
+            &[],
+            &[ValType::I64, ValType::I64],
+        );
+        let mut body = func.func_body();
+        BrokenAdd
+            .traverse(&mut generator, &mut body, &expr, &[])
+            .unwrap();
+        generator.debug_assert_stack_shape(&mut body, 0, &expr);
+    }
+
+    #[test]
+    fn memory_layout_lists_tuple_fields() {
+        let snippet = "
+(define-map entries uint { a: int, b: bool, c: uint })
+(define-read-only (get-entry) (map-get? entries u1))
+";
+        let mut generator = WasmGenerator::new(compile_analysis(snippet)).unwrap();
+        generator.record_memory_layouts = true;
+        let generated = generator.generate().unwrap();
+        let layout = &generated.memory_layouts["get-entry"];
+
+        // The key is reserved first, then the optional tuple result.
+        let locals: Vec<_> = layout
+            .iter()
+            .filter(|entry| entry.description.starts_with("local"))
+            .map(|entry| (entry.region, entry.offset))
+            .collect();
+        assert_eq!(
+            locals,
+            vec![(MemoryRegion::CallStack, 0), (MemoryRegion::CallStack, 16)]
+        );
+
+        // The fields come after the `some` indicator.
+        let fields: Vec<_> = layout
+            .iter()
+            .filter(|entry| entry.description.starts_with("field"))
+            .map(|entry| (entry.description.as_str(), entry.offset, entry.size))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("field a of type int", 20, 16),
+                ("field b of type bool", 36, 4),
+                ("field c of type uint", 40, 16),
+            ]
+        );
+
+        // Nothing is recorded unless requested.
+        let generated = WasmGenerator::new(compile_analysis(snippet))
+            .unwrap()
+            .generate()
+            .unwrap();
+        assert!(generated.memory_layouts.is_empty());
+    }
+
+    #[test]
+    fn func_by_name_is_cached() {
+        let generator = WasmGenerator::new(compile_analysis("(+ 1 2)")).unwrap();
+        let expected = generator.module.funcs.by_name("stdlib.lt-int").unwrap();
+
+        assert_eq!(generator.func_by_name("stdlib.lt-int"), expected);
+        assert_eq!(generator.func_by_name("stdlib.lt-int"), expected);
+        assert_eq!(generator.try_func_by_name("stdlib.lt-int"), Some(expected));
+        assert_eq!(generator.func_ids.borrow().len(), 1);
+
+        assert_eq!(generator.try_func_by_name("stdlib.does-not-exist"), None);
+        assert_eq!(generator.func_ids.borrow().len(), 1);
+    }
+
+    #[test]
+    fn many_comparisons_output_is_identical() {
+        let comparisons = "(< a b) (<= a b) (> a b) (>= a b) ".repeat(100);
+        let snippet = format!(
+            "(define-read-only (cmp (a int) (b int)) (begin {comparisons} (< a b))) (cmp 1 2)"
+        );
+        let emit = || {
+            WasmGenerator::new(compile_analysis(&snippet))
+                .unwrap()
+                .generate()
+                .unwrap()
+                .module
+                .emit_wasm()
+        };
+        assert_eq!(emit(), emit());
+
+        crosscheck(&snippet, Ok(Some(Value::Bool(true))));
+    }
+
+    #[test]
+    fn wide_variadic_forms() {
+        let ones = "1 ".repeat(5000);
+        assert_eq!(evaluate(&format!("(+ {ones})")), Ok(Some(Value::Int(5000))));
+        let trues = "true ".repeat(5000);
+        assert_eq!(
+            evaluate(&format!("(and {trues})")),
+            Ok(Some(Value::Bool(true)))
+        );
+    }
+
+    #[test]
+    fn write_value_to_memory_round_trip() {
+        let contract_principal = PrincipalData::Contract(QualifiedContractIdentifier::new(
+            StandardPrincipalData::transient(),
+            "contract".into(),
+        ));
+        let values = [
+            Value::Int(-42),
+            Value::UInt(u128::MAX),
+            Value::Bool(true),
+            Value::buff_from(vec![1, 2, 3]).unwrap(),
+            Value::string_ascii_from_bytes(b"hello".to_vec()).unwrap(),
+            Value::string_utf8_from_bytes("h\u{e9}llo".as_bytes().to_vec()).unwrap(),
+            Value::Principal(StandardPrincipalData::transient().into()),
+            Value::Principal(contract_principal),
+            Value::cons_list_unsanitized(vec![Value::Int(1), Value::Int(2)]).unwrap(),
+            Value::cons_list_unsanitized(vec![
+                Value::string_ascii_from_bytes(b"a".to_vec()).unwrap(),
+                Value::string_ascii_from_bytes(b"bc".to_vec()).unwrap(),
+            ])
+            .unwrap(),
+            Value::from(
+                TupleData::from_data(vec![
+                    ("a".into(), Value::UInt(1)),
+                    ("b".into(), Value::buff_from(vec![4, 5]).unwrap()),
+                ])
+                .unwrap(),
+            ),
+            Value::some(Value::Int(3)).unwrap(),
+            Value::okay(Value::Bool(false)).unwrap(),
+            Value::error(Value::UInt(7)).unwrap(),
+        ];
+
+        for value in values {
+            let ty = TypeSignature::type_of(&value).unwrap();
+            let mut generator = WasmGenerator::new(compile_analysis("(+ 1 2)")).unwrap();
+
+            let result_types = clar2wasm_ty(&ty);
+            let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &result_types);
+            let mut body = func.func_body();
+            let (offset, _) = generator
+                .create_call_stack_local(&mut body, &ty, false, true)
+                .unwrap();
+            generator
+                .write_value_to_memory(&mut body, offset, 0, &value, &ty)
+                .unwrap();
+            generator
+                .read_from_memory(&mut body, offset, 0, &ty)
+                .unwrap();
+            let func = func.finish(vec![], &mut generator.module.funcs);
+            generator.module.exports.add("round-trip", func);
+            let (mut store, instance) = instantiate(&mut generator.generate().unwrap().module);
+            let memory = instance.get_memory(&mut store, "memory").unwrap();
+
+            let mut results: Vec<_> = result_types
+                .iter()
+                .map(|ty| match ty {
+                    ValType::I64 => wasmtime::Val::I64(0),
+                    _ => wasmtime::Val::I32(0),
+                })
+                .collect();
+            instance
+                .get_func(&mut store, "round-trip")
+                .unwrap()
+                .call(&mut store, &[], &mut results)
+                .unwrap();
+
+            let (read, _) = wasm_to_clarity_value(
+                &ty,
+                0,
+                &results,
+                memory,
+                &mut store,
+                StacksEpochId::latest(),
+            )
+            .unwrap();
+            assert_eq!(read, Some(value));
+        }
+    }
+
+    #[test]
+    fn memory_bounds_check_traps_outside_of_sequence() {
+        let contract_analysis = compile_analysis("(+ 1 2)");
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+
+        let params = [(); 4].map(|_| generator.module.locals.add(ValType::I32));
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[ValType::I32; 4], &[]);
+        let [seq_offset, seq_length, offset, size] = params;
+        generator.emit_memory_bounds_check(
+            &mut func.func_body(),
+            seq_offset,
+            seq_length,
+            offset,
+            size,
+        );
+        let func = func.finish(params.to_vec(), &mut generator.module.funcs);
+        generator.module.exports.add("bounds-check", func);
+        let (mut store, instance) = instantiate(&mut generator.generate().unwrap().module);
+        let bounds_check = instance
+            .get_typed_func::<(i32, i32, i32, i32), ()>(&mut store, "bounds-check")
+            .unwrap();
+
+        let seq = END_OF_STANDARD_DATA as i32;
+        // The last element of a 16 bytes sequence is in bounds.
+        assert!(bounds_check
+            .call(&mut store, (seq, 16, seq + 12, 4))
+            .is_ok());
+        // The next one is still in the memory, but past the sequence.
+        assert!(bounds_check
+            .call(&mut store, (seq, 16, seq + 16, 4))
+            .is_err());
+        assert!(bounds_check
+            .call(&mut store, (seq, 16, seq - 4, 4))
+            .is_err());
+        // A corrupted size cannot wrap around.
+        assert!(bounds_check.call(&mut store, (seq, 16, seq, -1)).is_err());
+    }
+
+    #[test]
+    fn dead_call_stack_locals_are_eliminated() {
+        let contract_analysis = compile_analysis("(+ 1 2)");
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        let (dead, _) = generator
+            .create_call_stack_local(&mut func.func_body(), &TypeSignature::UIntType, false, true)
+            .unwrap();
+        let (live, live_size) = generator
+            .create_call_stack_local(&mut func.func_body(), &TypeSignature::UIntType, false, true)
+            .unwrap();
+        func.func_body().local_get(live).drop();
+        let func_id = func.finish(vec![], &mut generator.module.funcs);
+
+        generator.eliminate_dead_call_stack_locals();
+
+        // The live reservation keeps its worst case alignment padding.
+        assert_eq!(generator.frame_size, live_size + 7);
+        assert!(!generator.call_stack_locals.contains_key(&dead));
+        assert!(generator.call_stack_locals.contains_key(&live));
+        // Only the live reservation (9 instructions) and its read remain.
+        let func = generator.module.funcs.get(func_id).kind.unwrap_local();
+        assert_eq!(func.block(func.entry_block()).instrs.len(), 11);
+    }
+
+    #[test]
+    fn call_stack_local_without_fixed_size() {
+        let contract_analysis = compile_analysis("(+ 1 2)");
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let ty = TypeSignature::OptionalType(Box::new(TypeSignature::ListUnionType(
+            [CallableSubtype::Principal(
+                QualifiedContractIdentifier::transient(),
+            )]
+            .into_iter()
+            .collect(),
+        )));
+
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        let err = generator
+            .create_call_stack_local(&mut func.func_body(), &ty, false, true)
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            GeneratorError::TypeError(msg) if msg.contains("has no fixed size in memory")
+        ));
+        assert_eq!(generator.frame_size, 0);
+    }
+
+    #[test]
+    fn call_stack_locals_are_aligned() {
+        assert_eq!(call_stack_alignment(&TypeSignature::IntType), 8);
+        assert_eq!(call_stack_alignment(&TypeSignature::BoolType), 4);
+        assert_eq!(
+            call_stack_alignment(&TypeSignature::new_option(TypeSignature::UIntType).unwrap()),
+            8
+        );
+        assert_eq!(
+            call_stack_alignment(&clarity::vm::types::signatures::BUFF_1),
+            4
+        );
+
+        let contract_analysis = compile_analysis("(+ 1 2)");
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        generator
+            .create_call_stack_local(&mut func.func_body(), &TypeSignature::IntType, false, true)
+            .unwrap();
+        let func_id = func.finish(vec![], &mut generator.module.funcs);
+
+        // The stack pointer is rounded up to a multiple of 8 before use.
+        let func = generator.module.funcs.get(func_id).kind.unwrap_local();
+        let instrs = &func.block(func.entry_block()).instrs;
+        assert!(matches!(
+            instrs[1].0,
+            walrus::ir::Instr::Const(walrus::ir::Const {
+                value: walrus::ir::Value::I32(7)
+            })
+        ));
+        assert!(matches!(
+            instrs[3].0,
+            walrus::ir::Instr::Const(walrus::ir::Const {
+                value: walrus::ir::Value::I32(-8)
+            })
+        ));
+    }
+
+    #[test]
+    fn call_stack_local_after_odd_sized_one_is_aligned_at_runtime() {
+        let contract_analysis = compile_analysis("(+ 1 2)");
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let mut func = FunctionBuilder::new(
+            &mut generator.module.types,
+            &[],
+            &[ValType::I32, ValType::I32],
+        );
+        let mut body = func.func_body();
+        // A `(buff 1)` value takes a single byte.
+        let (byte, byte_size) = generator
+            .create_call_stack_local(
+                &mut body,
+                &clarity::vm::types::signatures::BUFF_1,
+                false,
+                true,
+            )
+            .unwrap();
+        assert_eq!(byte_size, 1);
+        let (int, _) = generator
+            .create_call_stack_local(&mut body, &TypeSignature::IntType, false, true)
+            .unwrap();
+        body.local_get(byte).local_get(int);
+        let func = func.finish(vec![], &mut generator.module.funcs);
+        generator.module.exports.add("offsets", func);
+        let (mut store, instance) = instantiate(&mut generator.generate().unwrap().module);
+        let offsets = instance
+            .get_typed_func::<(), (i32, i32)>(&mut store, "offsets")
+            .unwrap();
+
+        // Whatever the alignment of the stack pointer on entry.
+        let stack_pointer = instance.get_global(&mut store, "stack-pointer").unwrap();
+        let initial = stack_pointer.get(&mut store).i32().unwrap();
+        for misalignment in 0..8 {
+            stack_pointer
+                .set(&mut store, wasmtime::Val::I32(initial + misalignment))
+                .unwrap();
+            let (byte, int) = offsets.call(&mut store, ()).unwrap();
+            assert!(int > byte);
+            assert_eq!(int % 8, 0);
+        }
+    }
+
+    #[test]
+    fn dead_call_stack_locals_keep_results() {
+        // The body of `foo` is discarded because of the reused argument name,
+        // leaving the reservation for the `sha256` result unused.
+        crosscheck(
+            "
+(define-private (foo (a uint) (a uint))
+  (sha256 a))
+(foo u1 u2)
+",
+            Err(Error::Unchecked(CheckErrors::NameAlreadyUsed(
+                "a".to_string(),
+            ))),
+        );
+    }
+
+    #[test]
+    fn conditionally_read_call_stack_local_is_kept() {
+        // The result of `get-block-info?` is only read when `read` is true.
+        let snippet = "
+(define-private (time-of (read bool) (height uint))
+  (if read (get-block-info? time height) none))
+(list (time-of true u0) (time-of false u1))
+";
+        let contract_analysis = compile_analysis(snippet);
+        let SymbolicExpressionType::List(define) = &contract_analysis.expressions[0].expr else {
+            panic!("expected a list");
+        };
+        let body = define[2].clone();
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        generator
+            .traverse_define_function(
+                &mut func.func_body(),
+                &"time-of".into(),
+                &body,
+                FunctionKind::Private,
+            )
+            .unwrap();
+        let reservations = generator.call_stack_locals.clone();
+        let frame_size = generator.frame_size;
+        assert!(!reservations.is_empty());
+
+        generator.eliminate_dead_call_stack_locals();
+
+        assert_eq!(generator.call_stack_locals, reservations);
+        assert_eq!(generator.frame_size, frame_size);
+
+        let epoch = if cfg!(feature = "test-clarity-v1") {
+            StacksEpochId::Epoch2_05
+        } else {
+            StacksEpochId::Epoch25
+        };
+        let mut env = TestEnvironment::new(epoch, ClarityVersion::default_for_epoch(epoch));
+        env.advance_chain_tip(2);
+        env.freeze_time(1_700_000_000);
+        crosscheck_with_env(
+            snippet,
+            Ok(Some(
+                Value::cons_list_unsanitized(vec![
+                    Value::some(Value::UInt(1_700_000_000)).unwrap(),
+                    Value::none(),
+                ])
+                .unwrap(),
+            )),
+            env,
+        );
+    }
+
     //
     // Module with tests that should only be executed
     // when running Clarity::V2 or Clarity::v3.
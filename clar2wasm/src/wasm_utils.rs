@@ -1667,6 +1667,68 @@ pub enum ArgumentCountCheck {
     AtMost,
 }
 
+/// The number of arguments accepted by a word, checked before the word is
+/// visited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exact(usize),
+    AtLeast(usize),
+    /// Between the two counts, inclusive.
+    Between(usize, usize),
+}
+
+impl Arity {
+    /// Returns an [`GeneratorError::ArityMismatch`] if `name` cannot be called
+    /// with `got` arguments.
+    pub fn check(self, name: &str, got: usize) -> Result<(), GeneratorError> {
+        let accepted = match self {
+            Arity::Exact(count) => got == count,
+            Arity::AtLeast(count) => got >= count,
+            Arity::Between(min, max) => (min..=max).contains(&got),
+        };
+        self.accept_or_mismatch(accepted, name, got)
+    }
+
+    /// Like [`Self::check`], but only rejects missing arguments.
+    ///
+    /// Extra arguments to a special form are reported at runtime by
+    /// `check_args!`, like the interpreter does when the typechecker lets
+    /// them through (see issue #488).
+    pub fn check_minimum(self, name: &str, got: usize) -> Result<(), GeneratorError> {
+        let minimum = match self {
+            Arity::Exact(count) | Arity::AtLeast(count) | Arity::Between(count, _) => count,
+        };
+        self.accept_or_mismatch(got >= minimum, name, got)
+    }
+
+    fn accept_or_mismatch(
+        self,
+        accepted: bool,
+        name: &str,
+        got: usize,
+    ) -> Result<(), GeneratorError> {
+        if accepted {
+            Ok(())
+        } else {
+            Err(GeneratorError::ArityMismatch {
+                name: name.to_owned(),
+                expected: self,
+                got,
+            })
+        }
+    }
+}
+
+impl std::fmt::Display for Arity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Arity::Exact(count) => write!(f, "{count}"),
+            Arity::AtLeast(count) => write!(f, ">= {count}"),
+            Arity::Between(min, max) => write!(f, "{min} to {max}"),
+        }
+    }
+}
+
 pub fn check_argument_count(
     generator: &mut WasmGenerator,
     builder: &mut InstrSeqBuilder,
@@ -1,10 +1,12 @@
 use clarity::vm::types::TypeSignature;
 use clarity::vm::ClarityName;
+use walrus::ir::{Const, Instr, Value};
 use walrus::ValType;
 
 use super::{SimpleWord, Word};
 use crate::error_mapping::ErrorMap;
 use crate::wasm_generator::{GeneratorError, WasmGenerator};
+use crate::wasm_utils::Arity;
 
 fn simple_typed_one_call(
     generator: &mut WasmGenerator,
@@ -29,6 +31,64 @@ fn simple_typed_one_call(
     Ok(())
 }
 
+/// Returns the two operands of a binary operation on integers, if they were
+/// pushed by the last constant instructions of `builder`.
+pub(crate) fn constant_operands(builder: &walrus::InstrSeqBuilder) -> Option<(u128, u128)> {
+    let instrs = builder.instrs();
+    let mut halves = [0u64; 4];
+    for (half, (instr, _)) in halves
+        .iter_mut()
+        .zip(&instrs[instrs.len().checked_sub(4)?..])
+    {
+        let Instr::Const(Const {
+            value: Value::I64(value),
+        }) = instr
+        else {
+            return None;
+        };
+        *half = *value as u64;
+    }
+    let [lhs_lo, lhs_hi, rhs_lo, rhs_hi] = halves.map(u128::from);
+    Some(((lhs_hi << 64) | lhs_lo, (rhs_hi << 64) | rhs_lo))
+}
+
+/// Computes a division or a modulo at compile time if both operands are
+/// constants, replacing them with the result. Returns `false` when the
+/// operation is left to the stdlib, e.g. for a runtime error, which is
+/// reported as a warning for a constant zero divisor.
+fn fold_constant_division(
+    generator: &mut WasmGenerator,
+    builder: &mut walrus::InstrSeqBuilder,
+    return_type: &TypeSignature,
+    name: &str,
+    int_op: fn(i128, i128) -> Option<i128>,
+    uint_op: fn(u128, u128) -> Option<u128>,
+) -> bool {
+    let Some((lhs, rhs)) = constant_operands(builder) else {
+        return false;
+    };
+    if rhs == 0 {
+        generator.warn_unattributed(format!("{name}: division by zero"));
+        return false;
+    }
+
+    let result = match return_type {
+        TypeSignature::IntType => int_op(lhs as i128, rhs as i128).map(|result| result as u128),
+        TypeSignature::UIntType => uint_op(lhs, rhs),
+        _ => None,
+    };
+    let Some(result) = result else {
+        return false;
+    };
+
+    let instrs = builder.instrs_mut();
+    instrs.truncate(instrs.len() - 4);
+    builder
+        .i64_const(result as i64)
+        .i64_const((result >> 64) as i64);
+    true
+}
+
 #[derive(Debug)]
 pub struct Add;
 
@@ -39,6 +99,10 @@ impl Word for Add {
 }
 
 impl SimpleWord for Add {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -73,6 +137,10 @@ impl Word for Sub {
 }
 
 impl SimpleWord for Sub {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -131,6 +199,10 @@ impl Word for Mul {
 }
 
 impl SimpleWord for Mul {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -165,6 +237,10 @@ impl Word for Div {
 }
 
 impl SimpleWord for Div {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -173,6 +249,17 @@ impl SimpleWord for Div {
         return_type: &TypeSignature,
     ) -> Result<(), GeneratorError> {
         if arg_types.len() > 1 {
+            if fold_constant_division(
+                generator,
+                builder,
+                return_type,
+                "/",
+                i128::checked_div,
+                u128::checked_div,
+            ) {
+                return Ok(());
+            }
+
             let type_suffix = match return_type {
                 TypeSignature::IntType => "int",
                 TypeSignature::UIntType => "uint",
@@ -199,6 +286,10 @@ impl Word for Modulo {
 }
 
 impl SimpleWord for Modulo {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -206,6 +297,16 @@ impl SimpleWord for Modulo {
         arg_types: &[TypeSignature],
         return_type: &TypeSignature,
     ) -> Result<(), GeneratorError> {
+        if fold_constant_division(
+            generator,
+            builder,
+            return_type,
+            "mod",
+            i128::checked_rem,
+            u128::checked_rem,
+        ) {
+            return Ok(());
+        }
         simple_typed_one_call(generator, builder, arg_types, return_type, "mod")
     }
 }
@@ -220,6 +321,10 @@ impl Word for Log2 {
 }
 
 impl SimpleWord for Log2 {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit<'b>(
         &self,
         generator: &mut WasmGenerator,
@@ -241,6 +346,10 @@ impl Word for Power {
 }
 
 impl SimpleWord for Power {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -262,6 +371,10 @@ impl Word for Sqrti {
 }
 
 impl SimpleWord for Sqrti {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -275,10 +388,17 @@ impl SimpleWord for Sqrti {
 
 #[cfg(test)]
 mod tests {
+    use clarity::types::StacksEpochId;
+    use clarity::vm::costs::LimitedCostTracker;
+    use clarity::vm::database::MemoryBackingStore;
+    use clarity::vm::diagnostic::Level;
     use clarity::vm::errors::{Error, RuntimeErrorType};
-    use clarity::vm::Value;
+    use clarity::vm::types::QualifiedContractIdentifier;
+    use clarity::vm::{ClarityVersion, Value};
 
-    use crate::tools::{crosscheck, crosscheck_expect_failure, evaluate};
+    use crate::tools::{
+        crosscheck, crosscheck_compare_only, crosscheck_expect_failure, disassemble, evaluate,
+    };
 
     #[test]
     fn test_overflow() {
@@ -359,6 +479,54 @@ mod tests {
         crosscheck("(mod 8 3)", Ok(Some(Value::Int(2))));
     }
 
+    #[test]
+    fn div_constants_fold() {
+        crosscheck("(/ 10 3)", Ok(Some(Value::Int(3))));
+        crosscheck("(/ -10 3)", Ok(Some(Value::Int(-3))));
+        crosscheck("(mod -10 3)", Ok(Some(Value::Int(-1))));
+        crosscheck("(/ u10 u3)", Ok(Some(Value::UInt(3))));
+
+        for snippet in ["(/ 10 3)", "(mod u10 u3)"] {
+            let listing = disassemble(snippet).unwrap();
+            assert!(!listing.contains("call "), "{listing}");
+        }
+        let listing = disassemble("(let ((a 10)) (/ a 3))").unwrap();
+        assert!(listing.contains("call stdlib.div-int"), "{listing}");
+    }
+
+    #[test]
+    fn div_constant_overflow_not_folded() {
+        crosscheck_compare_only("(/ -170141183460469231731687303715884105728 -1)");
+    }
+
+    #[test]
+    fn div_by_constant_zero_warns() {
+        let result = crate::compile(
+            "(/ 1 0)",
+            &QualifiedContractIdentifier::transient(),
+            LimitedCostTracker::new_free(),
+            ClarityVersion::latest(),
+            StacksEpochId::latest(),
+            &mut MemoryBackingStore::new().as_analysis_db(),
+        )
+        .unwrap();
+        assert!(result.diagnostics.iter().any(|diagnostic| matches!(
+            diagnostic.level,
+            Level::Warning
+        ) && diagnostic
+            .message
+            .contains("division by zero")));
+
+        // Contracts dividing by zero are valid, the error is raised at runtime.
+        crosscheck(
+            "(/ 1 0)",
+            Err(Error::Runtime(
+                RuntimeErrorType::DivisionByZero,
+                Some(Vec::new()),
+            )),
+        );
+    }
+
     #[test]
     fn test_log2() {
         crosscheck("(log2 8)", Ok(Some(Value::Int(3))));
@@ -380,6 +548,32 @@ mod tests {
         crosscheck("(pow 2 3)", Ok(Some(Value::Int(8))));
     }
 
+    #[test]
+    fn test_pow_negative_base() {
+        // `pow` on `int` takes an `int` exponent, so `(pow -2 u3)` does not
+        // typecheck.
+        crosscheck("(pow -2 3)", Ok(Some(Value::Int(-8))));
+        crosscheck("(pow -2 2)", Ok(Some(Value::Int(4))));
+        crosscheck("(pow -1 0)", Ok(Some(Value::Int(1))));
+        crosscheck("(pow -1 1)", Ok(Some(Value::Int(-1))));
+    }
+
+    #[test]
+    fn test_pow_negative_exponent_any_base() {
+        // The exponent is checked before the shortcuts for small bases.
+        for snippet in ["(pow -2 -1)", "(pow 0 -1)", "(pow 1 -2)"] {
+            crosscheck(
+                snippet,
+                Err(Error::Runtime(
+                    RuntimeErrorType::Arithmetic(
+                        "Power argument to (pow ...) must be a u32 integer".to_string(),
+                    ),
+                    Some(Vec::new()),
+                )),
+            );
+        }
+    }
+
     #[test]
     fn test_pow_negative_exponent() {
         crosscheck(
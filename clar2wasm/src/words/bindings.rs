@@ -1,8 +1,9 @@
-use clarity::vm::{ClarityName, SymbolicExpression};
+use clarity::vm::types::TypeSignature;
+use clarity::vm::{ClarityName, SymbolicExpression, SymbolicExpressionType, Value};
 
 use crate::check_args;
 use crate::wasm_generator::{ArgumentsExt, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 use crate::words::{ComplexWord, Word};
 
 #[derive(Debug)]
@@ -15,6 +16,10 @@ impl Word for Let {
 }
 
 impl ComplexWord for Let {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -48,16 +53,35 @@ impl ComplexWord for Let {
                 )));
             }
 
-            // Traverse the value
-            generator.traverse_expr(builder, value)?;
-
-            // Store store the value in locals, and save to the var map
             let ty = generator
                 .get_expr_type(value)
                 .ok_or_else(|| {
                     GeneratorError::TypeError("let value expression must be typed".to_owned())
                 })?
                 .clone();
+
+            // Integer literals are propagated to the uses of the name, so that
+            // operations on them can be folded.
+            if let SymbolicExpressionType::LiteralValue(
+                literal @ (Value::Int(_) | Value::UInt(_)),
+            ) = &value.expr
+            {
+                if matches!(
+                    (&ty, literal),
+                    (TypeSignature::IntType, Value::Int(_))
+                        | (TypeSignature::UIntType, Value::UInt(_))
+                ) {
+                    generator
+                        .bindings
+                        .insert_constant(name.clone(), ty, literal.clone());
+                    continue;
+                }
+            }
+
+            // Traverse the value
+            generator.traverse_expr(builder, value)?;
+
+            // Store store the value in locals, and save to the var map
             let locals = generator.save_to_locals(builder, &ty, true);
 
             // Add these named locals to the map
@@ -4,6 +4,7 @@ use walrus::InstrSeqBuilder;
 
 use super::{SimpleWord, Word};
 use crate::wasm_generator::{GeneratorError, WasmGenerator};
+use crate::wasm_utils::Arity;
 
 #[derive(Debug)]
 pub struct BitwiseNot;
@@ -15,6 +16,10 @@ impl Word for BitwiseNot {
 }
 
 impl SimpleWord for BitwiseNot {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -54,6 +59,10 @@ impl Word for BitwiseOr {
 }
 
 impl SimpleWord for BitwiseOr {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -75,6 +84,10 @@ impl Word for BitwiseAnd {
 }
 
 impl SimpleWord for BitwiseAnd {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -96,6 +109,10 @@ impl Word for BitwiseXor {
 }
 
 impl SimpleWord for BitwiseXor {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -117,6 +134,10 @@ impl Word for BitwiseLShift {
 }
 
 impl SimpleWord for BitwiseLShift {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -140,6 +161,10 @@ impl Word for BitwiseRShift {
 }
 
 impl SimpleWord for BitwiseRShift {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -175,6 +200,10 @@ impl Word for Xor {
 }
 
 impl SimpleWord for Xor {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
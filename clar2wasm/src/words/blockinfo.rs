@@ -1,9 +1,10 @@
+use clarity::types::StacksEpochId;
 use clarity::vm::{ClarityName, SymbolicExpression};
 
 use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{ArgumentsExt, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct GetBlockInfo;
@@ -15,6 +16,10 @@ impl Word for GetBlockInfo {
 }
 
 impl ComplexWord for GetBlockInfo {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -24,6 +29,14 @@ impl ComplexWord for GetBlockInfo {
     ) -> Result<(), GeneratorError> {
         check_args!(generator, builder, 2, args.len(), ArgumentCountCheck::Exact);
 
+        if generator.contract_analysis.epoch >= StacksEpochId::Epoch30 {
+            generator.warn(
+                expr,
+                "get-block-info? is deprecated, use get-stacks-block-info? or get-tenure-info?"
+                    .to_owned(),
+            );
+        }
+
         let prop_name = args.get_name(0)?;
         let block = args.get_expr(1)?;
 
@@ -39,7 +52,7 @@ impl ComplexWord for GetBlockInfo {
             .clone();
 
         let (return_offset, return_size) =
-            generator.create_call_stack_local(builder, &return_ty, true, true);
+            generator.create_call_stack_local(builder, &return_ty, true, true)?;
 
         // Push the offset and size to the data stack
         builder.local_get(return_offset).i32_const(return_size);
@@ -102,6 +115,10 @@ impl Word for GetBurnBlockInfo {
 }
 
 impl ComplexWord for GetBurnBlockInfo {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -128,7 +145,7 @@ impl ComplexWord for GetBurnBlockInfo {
             .clone();
 
         let (return_offset, return_size) =
-            generator.create_call_stack_local(builder, &return_ty, true, true);
+            generator.create_call_stack_local(builder, &return_ty, true, true)?;
 
         // Push the offset and size to the data stack
         builder.local_get(return_offset).i32_const(return_size);
@@ -169,6 +186,10 @@ impl Word for AtBlock {
 }
 
 impl ComplexWord for AtBlock {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -207,6 +228,10 @@ impl Word for GetStacksBlockInfo {
 }
 
 impl ComplexWord for GetStacksBlockInfo {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -233,7 +258,7 @@ impl ComplexWord for GetStacksBlockInfo {
             .clone();
 
         let (return_offset, return_size) =
-            generator.create_call_stack_local(builder, &return_ty, true, true);
+            generator.create_call_stack_local(builder, &return_ty, true, true)?;
 
         // Push the offset and size to the data stack
         builder.local_get(return_offset).i32_const(return_size);
@@ -277,6 +302,10 @@ impl Word for GetTenureInfo {
 }
 
 impl ComplexWord for GetTenureInfo {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -301,7 +330,7 @@ impl ComplexWord for GetTenureInfo {
             .clone();
 
         let (return_offset, return_size) =
-            generator.create_call_stack_local(builder, &return_ty, true, true);
+            generator.create_call_stack_local(builder, &return_ty, true, true)?;
 
         // Push the offset and size to the data stack
         builder.local_get(return_offset).i32_const(return_size);
@@ -353,11 +382,19 @@ impl ComplexWord for GetTenureInfo {
 #[cfg(test)]
 mod tests {
     use clarity::types::StacksEpochId;
+    use clarity::vm::diagnostic::Level;
     use clarity::vm::errors::{CheckErrors, Error};
-    use clarity::vm::types::{OptionalData, PrincipalData, TupleData};
-    use clarity::vm::{ClarityVersion, Value};
-
-    use crate::tools::{evaluate, TestEnvironment};
+    use clarity::vm::types::{PrincipalData, TupleData, TypeSignature};
+    use clarity::vm::{ClarityVersion, SymbolicExpressionType, Value};
+    use walrus::FunctionBuilder;
+
+    use crate::tools::{
+        compile_analysis, compile_snippet, compile_snippet_at, crosscheck_approx_with_env,
+        crosscheck_with_env, evaluate, TestEnvironment,
+    };
+    use crate::wasm_generator::{GeneratorError, WasmGenerator};
+    use crate::wasm_utils::Arity;
+    use crate::CompileError;
 
     //
     // Module with tests that should only be executed
@@ -370,39 +407,52 @@ mod tests {
         use clarity::vm::ClarityVersion;
 
         use super::*;
-        use crate::tools::crosscheck_with_epoch;
+        use crate::tools::{crosscheck_all_epochs, crosscheck_with_epoch};
 
         #[test]
         fn get_block_info_non_existent() {
-            crosscheck_with_epoch(
+            crosscheck_all_epochs(
                 "(get-block-info? time u9999999)",
-                Ok(Some(Value::none())),
-                StacksEpochId::Epoch25,
+                &[
+                    (StacksEpochId::Epoch21, Ok(Some(Value::none()))),
+                    (StacksEpochId::Epoch24, Ok(Some(Value::none()))),
+                    (StacksEpochId::Epoch25, Ok(Some(Value::none()))),
+                ],
             );
         }
 
         #[test]
-        fn test_block_height() {
-            let snpt = "
-                (define-public (block)
-                (ok block-height))
-
-                (define-public (burn-block)
-                (ok burn-block-height))
-            ";
-
-            crosscheck_with_epoch(
-                &format!("{snpt} (block)"),
-                evaluate("(ok u0)"),
-                StacksEpochId::Epoch24,
+        fn get_block_info_header_hash_non_existent() {
+            crosscheck_all_epochs(
+                "(get-block-info? header-hash u9999999)",
+                &[
+                    (StacksEpochId::Epoch21, Ok(Some(Value::none()))),
+                    (StacksEpochId::Epoch24, Ok(Some(Value::none()))),
+                    (StacksEpochId::Epoch25, Ok(Some(Value::none()))),
+                ],
             );
+        }
+
+        #[test]
+        fn get_block_info_miner_address_non_existent() {
             crosscheck_with_epoch(
-                &format!("{snpt} (burn-block)"),
-                evaluate("(ok u0)"),
-                StacksEpochId::Epoch24,
+                "(get-block-info? miner-address u9999999)",
+                Ok(Some(Value::none())),
+                StacksEpochId::Epoch25,
             );
         }
 
+        #[test]
+        fn get_block_info_other_properties_non_existent() {
+            for property in ["vrf-seed", "burnchain-header-hash", "id-header-hash"] {
+                crosscheck_with_epoch(
+                    &format!("(get-block-info? {property} u9999999)"),
+                    Ok(Some(Value::none())),
+                    StacksEpochId::Epoch25,
+                );
+            }
+        }
+
         #[test]
         fn at_block() {
             crosscheck_with_epoch(
@@ -412,6 +462,54 @@ mod tests {
             )
         }
 
+        #[test]
+        fn nested_at_block() {
+            // The outer block is restored when the inner `at-block` exits.
+            let snippet = "
+(let ((h1 (unwrap-panic (get-block-info? id-header-hash u1)))
+      (h2 (unwrap-panic (get-block-info? id-header-hash u2))))
+  (at-block h1 (list block-height (at-block h2 block-height) block-height)))
+";
+            crate::tools::crosscheck_compare_only_advancing_tip(snippet, 3);
+
+            let mut env = TestEnvironment::new(
+                crate::tools::TestConfig::latest_epoch(),
+                crate::tools::TestConfig::clarity_version(),
+            );
+            env.advance_chain_tip(3);
+            let heights = env
+                .evaluate(snippet)
+                .unwrap()
+                .unwrap()
+                .expect_list()
+                .unwrap();
+            assert_eq!(heights[0], heights[2]);
+            assert_ne!(heights[0], heights[1]);
+        }
+
+        #[test]
+        fn at_block_complex_result() {
+            // The values of the inner expression stay on the stack across the
+            // `exit_at_block` call.
+            let tuple = Value::Tuple(
+                TupleData::from_data(vec![
+                    ("a".into(), Value::UInt(1)),
+                    ("b".into(), Value::UInt(2)),
+                ])
+                .unwrap(),
+            );
+            crosscheck_with_epoch(
+                "(at-block 0x0000000000000000000000000000000000000000000000000000000000000000 {a: u1, b: u2})",
+                Ok(Some(tuple.clone())),
+                StacksEpochId::Epoch24,
+            );
+            crosscheck_with_epoch(
+                "(at-block 0x0000000000000000000000000000000000000000000000000000000000000000 (some {a: u1, b: u2}))",
+                Ok(Some(Value::some(tuple).unwrap())),
+                StacksEpochId::Epoch24,
+            );
+        }
+
         #[test]
         fn get_block_info_less_than_two_args() {
             let epoch = if cfg!(feature = "test-clarity-v1") {
@@ -533,8 +631,9 @@ mod tests {
         #[test]
         fn get_stacks_block_info_time() {
             let mut env = TestEnvironment::new(StacksEpochId::Epoch30, ClarityVersion::Clarity3);
+            env.freeze_time(1_700_000_000);
             env.advance_chain_tip(1);
-            let expected = chrono::Utc::now().timestamp() as u128;
+            let expected = 1_700_000_000;
             crosscheck_with_env(
                 "(get-stacks-block-info? time u0)",
                 Ok(Some(Value::some(Value::UInt(expected)).unwrap())),
@@ -555,8 +654,9 @@ mod tests {
         #[test]
         fn get_tenure_info_time() {
             let mut env = TestEnvironment::new(StacksEpochId::Epoch30, ClarityVersion::Clarity3);
+            env.freeze_time(1_700_000_000);
             env.advance_chain_tip(1);
-            let expected = chrono::Utc::now().timestamp() as u128;
+            let expected = 1_700_000_000;
             crosscheck_with_env(
                 "(get-tenure-info? time u0)",
                 Ok(Some(Value::some(Value::UInt(expected)).unwrap())),
@@ -630,6 +730,37 @@ mod tests {
 
     //- Block Info
 
+    #[test]
+    fn get_block_info_without_block_is_an_arity_mismatch() {
+        // The typechecker rejects `(get-block-info? time)`, so the block
+        // height of `(get-block-info? time u0)` is removed after analysis.
+        // The missing argument is reported before the word is traversed.
+        let contract_analysis = compile_analysis("(get-block-info? time u0)");
+        let mut expr = contract_analysis.expressions[0].clone();
+        let SymbolicExpressionType::List(list) = &mut expr.expr else {
+            panic!("expected a list");
+        };
+        list.pop();
+
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        let err = generator
+            .traverse_expr(&mut func.func_body(), &expr)
+            .unwrap_err();
+        let GeneratorError::ArityMismatch {
+            name,
+            expected,
+            got,
+        } = err
+        else {
+            panic!("expected an arity mismatch, got {err:?}");
+        };
+        assert_eq!(
+            (name.as_str(), expected, got),
+            ("get-block-info?", Arity::Exact(2), 1)
+        );
+    }
+
     #[test]
     fn get_block_info_burnchain_header_hash() {
         let epoch = if cfg!(feature = "test-clarity-v1") {
@@ -720,8 +851,24 @@ mod tests {
         )
     }
 
-    #[test]
-    fn get_block_info_time() {
+    /// Checks that `(list (get-block-info? time u0) (get-block-info? time
+    /// u1))` returns a genesis time within `window`, and the following block
+    /// 1800 seconds later.
+    fn block_times_within(value: &Value, window: std::ops::RangeInclusive<u128>) -> bool {
+        let Ok(times) = value.clone().expect_list() else {
+            return false;
+        };
+        let times: Vec<_> = times
+            .into_iter()
+            .map(|time| time.expect_optional().ok().flatten()?.expect_u128().ok())
+            .collect();
+        match times.as_slice() {
+            [Some(genesis), Some(next)] => window.contains(genesis) && *next == genesis + 1800,
+            _ => false,
+        }
+    }
+
+    fn block_time_env() -> TestEnvironment {
         let epoch = if cfg!(feature = "test-clarity-v1") {
             StacksEpochId::Epoch2_05
         } else {
@@ -729,24 +876,86 @@ mod tests {
         };
 
         let mut env = TestEnvironment::new(epoch, ClarityVersion::default_for_epoch(epoch));
+        env.advance_chain_tip(2);
+        env
+    }
 
-        env.advance_chain_tip(1);
-        let result = env
-            .evaluate("(get-block-info? time u0)")
-            .expect("Failed to init contract.");
-        let block_time_val = match result {
-            Some(Value::Optional(OptionalData { data: Some(data) })) => *data,
-            _ => panic!("expected value"),
-        };
-        let block_time = match block_time_val {
-            Value::UInt(val) => val,
-            _ => panic!("expected uint"),
-        };
-        let now = chrono::Utc::now().timestamp() as u128;
+    #[test]
+    fn get_block_info_time() {
+        let mut env = block_time_env();
+        env.freeze_time(1_700_000_000);
+
+        crosscheck_with_env(
+            "(list (get-block-info? time u0) (get-block-info? time u1))",
+            Ok(Some(
+                Value::cons_list_unsanitized(vec![
+                    Value::some(Value::UInt(1_700_000_000)).unwrap(),
+                    Value::some(Value::UInt(1_700_001_800)).unwrap(),
+                ])
+                .unwrap(),
+            )),
+            env,
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn get_block_info_time_from_wall_clock() {
+        // Without a frozen time, the genesis time is taken from the wall
+        // clock.
+        let before = chrono::Utc::now().timestamp() as u128;
+        let env = block_time_env();
+        let after = chrono::Utc::now().timestamp() as u128;
+
+        crosscheck_approx_with_env(
+            "(list (get-block-info? time u0) (get-block-info? time u1))",
+            env,
+            |value| block_times_within(value, before..=after),
+        );
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[test]
+    fn get_block_info_time_from_epoch() {
+        crosscheck_approx_with_env(
+            "(list (get-block-info? time u0) (get-block-info? time u1))",
+            block_time_env(),
+            |value| block_times_within(value, 0..=0),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not satisfy the predicate")]
+    fn get_block_info_time_outside_window() {
+        crosscheck_approx_with_env(
+            "(list (get-block-info? time u0) (get-block-info? time u1))",
+            block_time_env(),
+            |value| block_times_within(value, 0..=1_000_000),
+        );
+    }
 
-        // The block time should be close to the current time, so let's give it
-        // a 10 second window, to be safe.
-        assert!(block_time >= now - 10);
+    #[test]
+    fn get_block_info_time_high_bits_are_zero() {
+        let mut env = block_time_env();
+        env.freeze_time(1_700_000_000);
+
+        // The 64-bit time is written as a full 128-bit uint, with a zeroed
+        // high half.
+        crosscheck_with_env(
+            "
+(define-data-var t uint u0)
+(var-set t (unwrap-panic (get-block-info? time u1)))
+{ high: (/ (var-get t) u18446744073709551616), time: (var-get t) }
+",
+            Ok(Some(Value::Tuple(
+                TupleData::from_data(vec![
+                    ("high".into(), Value::UInt(0)),
+                    ("time".into(), Value::UInt(1_700_001_800)),
+                ])
+                .unwrap(),
+            ))),
+            env,
+        );
     }
 
     #[test]
@@ -843,6 +1052,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_burn_block_info_pox_addrs_multiple_addresses() {
+        let addr = |byte: u8, version: u8| -> TupleData {
+            TupleData::from_data(vec![
+                (
+                    "hashbytes".into(),
+                    Value::buff_from([byte; 32].to_vec()).unwrap(),
+                ),
+                ("version".into(), Value::buff_from_byte(version)),
+            ])
+            .unwrap()
+        };
+
+        let mut env = TestEnvironment::default();
+        env.advance_chain_tip(1);
+        env.set_pox_payout_addrs(0, vec![addr(0x11, 1), addr(0x22, 4)], 1000);
+
+        crosscheck_with_env(
+            "(get-burn-block-info? pox-addrs u0)",
+            Ok(Some(
+                Value::some(
+                    TupleData::from_data(vec![
+                        (
+                            "addrs".into(),
+                            Value::cons_list_unsanitized(vec![
+                                addr(0x11, 1).into(),
+                                addr(0x22, 4).into(),
+                            ])
+                            .unwrap(),
+                        ),
+                        ("payout".into(), Value::UInt(1000)),
+                    ])
+                    .unwrap()
+                    .into(),
+                )
+                .unwrap(),
+            )),
+            env,
+        );
+    }
+
     #[test]
     fn at_block_less_than_two_args() {
         let result = evaluate(
@@ -882,6 +1132,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn at_block_rejects_state_mutation() {
+        // The read-only checker runs before code generation, and rejects
+        // writes in the closure of `at-block`.
+        for body in [
+            "(var-set data u1)",
+            "(map-set m u1 u1)",
+            "(ft-mint? tok u1 tx-sender)",
+        ] {
+            let Err(CompileError::Generic { diagnostics, .. }) = compile_snippet(&format!(
+                "
+(define-data-var data uint u0)
+(define-map m uint uint)
+(define-fungible-token tok)
+(define-public (f)
+  (ok (at-block 0xb5e076ab7609c7f8c763b5c571d07aea80b06b41452231b1437370f4964ed66e {body})))
+"
+            )) else {
+                panic!("expected {body} in at-block to be rejected");
+            };
+            let diagnostic = diagnostics.last().unwrap();
+            assert!(matches!(diagnostic.level, Level::Error));
+            assert!(
+                diagnostic.message.contains("read-only"),
+                "{}",
+                diagnostic.message
+            );
+        }
+    }
+
+    #[test]
+    fn get_block_info_deprecation_warning() {
+        let result = compile_snippet_at(
+            "(get-block-info? time u0)",
+            StacksEpochId::Epoch30,
+            ClarityVersion::Clarity2,
+        )
+        .expect("Failed to compile contract.");
+
+        assert!(result.diagnostics.iter().any(|d| {
+            matches!(d.level, Level::Warning) && d.message.contains("get-block-info? is deprecated")
+        }));
+        assert_eq!(
+            result.top_level_type,
+            Some(TypeSignature::OptionalType(Box::new(
+                TypeSignature::UIntType
+            )))
+        );
+        // The module is still generated.
+        assert!(result.module.exports.iter().any(|e| e.name == ".top-level"));
+    }
+
     //
     // Module with tests that should only be executed
     // when running Clarity::V2 or Clarity::v3.
@@ -913,16 +1215,16 @@ mod tests {
         }
 
         #[test]
-        fn test_chain_id() {
-            crosscheck(
-                "
-(define-public (get-chain-id)
-  (ok chain-id))
-
-(get-chain-id)
-",
-                evaluate("(ok u2147483648)"),
-            );
+        fn get_burn_block_info_header_hash_of_current_burn_block() {
+            let mut env = TestEnvironment::default();
+            env.advance_chain_tip(3);
+            crosscheck_with_env(
+                "(get-burn-block-info? header-hash burn-block-height)",
+                Ok(Some(
+                    Value::some(Value::buff_from([0; 32].to_vec()).unwrap()).unwrap(),
+                )),
+                env,
+            )
         }
     }
 }
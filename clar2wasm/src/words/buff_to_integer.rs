@@ -1,19 +1,115 @@
-use clarity::vm::types::TypeSignature;
+use clarity::vm::types::{SequenceSubtype, TypeSignature};
+use walrus::ir::{BinaryOp, Const, InstrSeqType, LoadKind, MemArg, UnaryOp};
+use walrus::ValType;
 
 use crate::wasm_generator::{GeneratorError, WasmGenerator};
+use crate::wasm_utils::Arity;
 use crate::words::{SimpleWord, Word};
 
+#[derive(Debug, Clone, Copy)]
+enum Endianness {
+    Big,
+    Little,
+}
+
 fn traverse_buffer_to_integer(
     name: &str,
+    endianness: Endianness,
     generator: &mut WasmGenerator,
     builder: &mut walrus::InstrSeqBuilder,
+    arg_types: &[TypeSignature],
 ) -> Result<(), GeneratorError> {
     let func = generator
-        .module
-        .funcs
-        .by_name(name)
+        .try_func_by_name(name)
         .ok_or_else(|| GeneratorError::InternalError(format!("function not found: {name}")))?;
-    builder.call(func);
+
+    let len = match arg_types.first() {
+        Some(TypeSignature::SequenceType(SequenceSubtype::BufferType(len))) => u32::from(len),
+        Some(ty) => {
+            return Err(GeneratorError::TypeError(format!(
+                "invalid type for buffer to integer conversion: {ty}; expected a buff"
+            )))
+        }
+        None => {
+            return Err(GeneratorError::InternalError(
+                "buffer to integer conversion expects an argument".to_owned(),
+            ))
+        }
+    };
+    let is_buff_16 = len == 16;
+    if !is_buff_16 {
+        builder.call(func);
+        return Ok(());
+    }
+
+    // A `(buff 16)` can still be shorter at runtime, so the direct 128-bit
+    // load is only used when the buffer is exactly 16 bytes long. Other
+    // lengths fall back to the generic stdlib function.
+    let memory = generator.get_memory()?;
+    let length = generator.module.locals.add(ValType::I32);
+    let offset = generator.module.locals.add(ValType::I32);
+    let tmp_v128 = generator.module.locals.add(ValType::V128);
+    builder.local_set(length).local_set(offset);
+
+    let block_ty = InstrSeqType::new(
+        &mut generator.module.types,
+        &[],
+        &[ValType::I64, ValType::I64],
+    );
+    builder
+        .local_get(length)
+        .i32_const(16)
+        .binop(BinaryOp::I32Eq)
+        .if_else(
+            block_ty,
+            |then| match endianness {
+                Endianness::Little => {
+                    // Memory: Offset -> | Low | High |
+                    then.local_get(offset).load(
+                        memory,
+                        LoadKind::I64 { atomic: false },
+                        MemArg {
+                            align: 1,
+                            offset: 0,
+                        },
+                    );
+                    then.local_get(offset).load(
+                        memory,
+                        LoadKind::I64 { atomic: false },
+                        MemArg {
+                            align: 1,
+                            offset: 8,
+                        },
+                    );
+                }
+                Endianness::Big => {
+                    // Load the integer into a vector register
+                    then.local_get(offset).load(
+                        memory,
+                        LoadKind::V128 {},
+                        MemArg {
+                            align: 1,
+                            offset: 0,
+                        },
+                    );
+                    // Convert from big-endian to little
+                    then.instr(Const {
+                        value: walrus::ir::Value::V128(0x000102030405060708090a0b0c0d0e0f),
+                    })
+                    .i8x16_swizzle()
+                    .local_tee(tmp_v128);
+
+                    // Push the two i64s onto the stack
+                    then.unop(UnaryOp::I64x2ExtractLane { idx: 0 });
+                    then.local_get(tmp_v128)
+                        .unop(UnaryOp::I64x2ExtractLane { idx: 1 });
+                }
+            },
+            |else_| {
+                else_.local_get(offset).local_get(length).call(func);
+            },
+        );
+
     Ok(())
 }
 
@@ -27,14 +123,24 @@ impl Word for BuffToUintBe {
 }
 
 impl SimpleWord for BuffToUintBe {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
         builder: &mut walrus::InstrSeqBuilder,
-        _arg_types: &[TypeSignature],
+        arg_types: &[TypeSignature],
         _return_type: &TypeSignature,
     ) -> Result<(), crate::wasm_generator::GeneratorError> {
-        traverse_buffer_to_integer("stdlib.buff-to-uint-be", generator, builder)
+        traverse_buffer_to_integer(
+            "stdlib.buff-to-uint-be",
+            Endianness::Big,
+            generator,
+            builder,
+            arg_types,
+        )
     }
 }
 
@@ -48,16 +154,26 @@ impl Word for BuffToIntBe {
 }
 
 impl SimpleWord for BuffToIntBe {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
         builder: &mut walrus::InstrSeqBuilder,
-        _arg_types: &[TypeSignature],
+        arg_types: &[TypeSignature],
         _return_type: &TypeSignature,
     ) -> Result<(), crate::wasm_generator::GeneratorError> {
         // This is the same function as "buff-to-uint-be", with the result interpreted
         // as i128 instead of u128.
-        traverse_buffer_to_integer("stdlib.buff-to-uint-be", generator, builder)
+        traverse_buffer_to_integer(
+            "stdlib.buff-to-uint-be",
+            Endianness::Big,
+            generator,
+            builder,
+            arg_types,
+        )
     }
 }
 
@@ -71,14 +187,24 @@ impl Word for BuffToUintLe {
 }
 
 impl SimpleWord for BuffToUintLe {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
         builder: &mut walrus::InstrSeqBuilder,
-        _arg_types: &[TypeSignature],
+        arg_types: &[TypeSignature],
         _return_type: &TypeSignature,
     ) -> Result<(), crate::wasm_generator::GeneratorError> {
-        traverse_buffer_to_integer("stdlib.buff-to-uint-le", generator, builder)
+        traverse_buffer_to_integer(
+            "stdlib.buff-to-uint-le",
+            Endianness::Little,
+            generator,
+            builder,
+            arg_types,
+        )
     }
 }
 
@@ -92,15 +218,212 @@ impl Word for BuffToIntLe {
 }
 
 impl SimpleWord for BuffToIntLe {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
         builder: &mut walrus::InstrSeqBuilder,
-        _arg_types: &[TypeSignature],
+        arg_types: &[TypeSignature],
         _return_type: &TypeSignature,
     ) -> Result<(), crate::wasm_generator::GeneratorError> {
         // This is the same function as "buff-to-uint-le", with the result interpreted
         // as i128 instead of u128.
-        traverse_buffer_to_integer("stdlib.buff-to-uint-le", generator, builder)
+        traverse_buffer_to_integer(
+            "stdlib.buff-to-uint-le",
+            Endianness::Little,
+            generator,
+            builder,
+            arg_types,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clarity::vm::types::TypeSignature;
+    use clarity::vm::Value;
+    use walrus::FunctionBuilder;
+
+    use super::BuffToUintBe;
+    use crate::tools::{compile_analysis, crosscheck, crosscheck_compare_only};
+    use crate::wasm_generator::{GeneratorError, WasmGenerator};
+    use crate::words::SimpleWord;
+
+    const CONVERT: &str = "
+(define-read-only (be (b (buff 16))) (buff-to-uint-be b))
+(define-read-only (le (b (buff 16))) (buff-to-uint-le b))
+(define-read-only (be-signed (b (buff 16))) (buff-to-int-be b))
+(define-read-only (le-signed (b (buff 16))) (buff-to-int-le b))
+";
+
+    #[test]
+    fn buff_to_uint_of_uint_names_the_type() {
+        // The typechecker rejects `(buff-to-uint-be u5)`, so the word is
+        // visited directly with a uint operand.
+        let contract_analysis = compile_analysis("(+ 1 2)");
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+
+        let err = BuffToUintBe
+            .visit(
+                &mut generator,
+                &mut func.func_body(),
+                &[TypeSignature::UIntType],
+                &TypeSignature::UIntType,
+            )
+            .unwrap_err();
+        let GeneratorError::TypeError(msg) = err else {
+            panic!("expected a type error, got {err:?}");
+        };
+        assert_eq!(
+            msg,
+            "invalid type for buffer to integer conversion: uint; expected a buff"
+        );
+    }
+
+    #[test]
+    fn buff_16_specialization_big_endian() {
+        crosscheck(
+            &format!("{CONVERT} (be 0x0102030405060708090a0b0c0d0e0f10)"),
+            Ok(Some(Value::UInt(0x0102030405060708090a0b0c0d0e0f10))),
+        );
+        crosscheck(
+            &format!("{CONVERT} (be-signed 0xffffffffffffffffffffffffffffff85)"),
+            Ok(Some(Value::Int(-123))),
+        );
+    }
+
+    #[test]
+    fn buff_16_specialization_little_endian() {
+        crosscheck(
+            &format!("{CONVERT} (le 0x0102030405060708090a0b0c0d0e0f10)"),
+            Ok(Some(Value::UInt(0x100f0e0d0c0b0a090807060504030201))),
+        );
+        crosscheck(
+            &format!("{CONVERT} (le-signed 0x85ffffffffffffffffffffffffffffff)"),
+            Ok(Some(Value::Int(-123))),
+        );
+    }
+
+    #[test]
+    fn buff_to_uint_le_zero_pads_short_buffers() {
+        crosscheck("(buff-to-uint-le 0x0100)", Ok(Some(Value::UInt(1))));
+        crosscheck("(buff-to-uint-le 0x)", Ok(Some(Value::UInt(0))));
+        crosscheck(
+            "(buff-to-uint-le 0x010000000000000001)",
+            Ok(Some(Value::UInt((1 << 64) + 1))),
+        );
+        // Clarity pads signed conversions with zeros too, without sign extension.
+        crosscheck("(buff-to-int-le 0xff)", Ok(Some(Value::Int(255))));
+    }
+
+    #[test]
+    fn buff_to_int_16_bytes_high_bit_set() {
+        // With the top bit set, a full 16-byte buffer is a negative `int`.
+        crosscheck(
+            "(buff-to-int-be 0xffffffffffffffffffffffffffffffff)",
+            Ok(Some(Value::Int(-1))),
+        );
+        crosscheck(
+            "(buff-to-int-be 0x80000000000000000000000000000000)",
+            Ok(Some(Value::Int(i128::MIN))),
+        );
+        crosscheck(
+            "(buff-to-int-le 0xffffffffffffffffffffffffffffffff)",
+            Ok(Some(Value::Int(-1))),
+        );
+        crosscheck(
+            "(buff-to-int-le 0x00000000000000000000000000000080)",
+            Ok(Some(Value::Int(i128::MIN))),
+        );
+        crosscheck(
+            &format!("{CONVERT} (be-signed 0x80000000000000000000000000000000)"),
+            Ok(Some(Value::Int(i128::MIN))),
+        );
+        crosscheck(
+            &format!("{CONVERT} (le-signed 0x00000000000000000000000000000080)"),
+            Ok(Some(Value::Int(i128::MIN))),
+        );
+    }
+
+    #[test]
+    fn buff_16_specialization_shorter_buffer() {
+        // A `(buff 16)` shorter than 16 bytes takes the generic path.
+        crosscheck(
+            &format!("{CONVERT} (be 0x0102)"),
+            Ok(Some(Value::UInt(0x0102))),
+        );
+        crosscheck(
+            &format!("{CONVERT} (le 0x0102)"),
+            Ok(Some(Value::UInt(0x0201))),
+        );
+    }
+
+    #[test]
+    fn buff_16_specialization_matches_generic() {
+        // The halves are 8 bytes long and go through the generic stdlib function.
+        crosscheck(
+            "
+(let ((hi 0x8899aabbccddeeff) (lo 0x0011223344556677))
+  (is-eq
+    (buff-to-uint-be (concat hi lo))
+    (+ (* (buff-to-uint-be hi) u18446744073709551616) (buff-to-uint-be lo))))
+",
+            Ok(Some(Value::Bool(true))),
+        );
+        crosscheck(
+            "
+(let ((lo 0x8899aabbccddeeff) (hi 0x0011223344556677))
+  (is-eq
+    (buff-to-uint-le (concat lo hi))
+    (+ (* (buff-to-uint-le hi) u18446744073709551616) (buff-to-uint-le lo))))
+",
+            Ok(Some(Value::Bool(true))),
+        );
+    }
+
+    #[test]
+    fn buff_to_integer_matrix() {
+        let words = [
+            "buff-to-uint-be",
+            "buff-to-uint-le",
+            "buff-to-int-be",
+            "buff-to-int-le",
+        ];
+        for word in words {
+            for len in [0, 1, 8, 15, 16] {
+                // A positive value, and one with the high bit of the first
+                // and last bytes set.
+                let positive: String = (1..=len).map(|i| format!("{i:02x}")).collect();
+                let high_bit: String = (0..len)
+                    .map(|i| if i == 0 || i == len - 1 { "80" } else { "5a" })
+                    .collect();
+                for hex in [positive, high_bit] {
+                    // A literal has the exact length as type, while the
+                    // argument is typed as `(buff 16)`.
+                    crosscheck_compare_only(&format!("({word} 0x{hex})"));
+                    crosscheck_compare_only(&format!(
+                        "(define-read-only (convert (b (buff 16))) ({word} b)) (convert 0x{hex})"
+                    ));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn map_buff_to_uint_be() {
+        crosscheck(
+            "(map buff-to-uint-be (list 0x01 0x02))",
+            Ok(Some(
+                Value::cons_list_unsanitized(vec![Value::UInt(1), Value::UInt(2)]).unwrap(),
+            )),
+        );
+        crosscheck(
+            "(fold + (map buff-to-uint-be (list 0x01 0x0102 0x)) u0)",
+            Ok(Some(Value::UInt(259))),
+        );
     }
 }
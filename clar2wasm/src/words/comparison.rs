@@ -1,8 +1,10 @@
 use clarity::vm::types::{SequenceSubtype, StringSubtype, TypeSignature};
 use clarity::vm::ClarityName;
 
+use super::arithmetic::constant_operands;
 use super::{SimpleWord, Word};
 use crate::wasm_generator::{GeneratorError, WasmGenerator};
+use crate::wasm_utils::Arity;
 
 fn traverse_comparison(
     name: &str,
@@ -25,17 +27,51 @@ fn traverse_comparison(
             // Since we already have 32-bit unicode scalars, we can just compare them with buff.
             "buff"
         }
+        TypeSignature::TupleType(_)
+        | TypeSignature::SequenceType(SequenceSubtype::ListType(_))
+        | TypeSignature::OptionalType(_)
+        | TypeSignature::ResponseType(_) => {
+            return Err(GeneratorError::TypeError(format!(
+                "invalid type for comparison: {ty}; ordered comparison is not defined for tuple, \
+                 list, optional and response types, use `is-eq` to test them for equality"
+            )))
+        }
         _ => {
-            return Err(GeneratorError::TypeError(
-                "invalid type for comparison".to_string(),
-            ))
+            return Err(GeneratorError::TypeError(format!(
+                "invalid type for comparison: {ty}; comparison only supports int, uint, buff, \
+                 string-ascii and string-utf8"
+            )))
         }
     };
 
+    // Compare integer constants at compile time.
+    if let Some((lhs, rhs)) = constant_operands(builder) {
+        let ordering = match ty {
+            TypeSignature::IntType => Some((lhs as i128).cmp(&(rhs as i128))),
+            TypeSignature::UIntType => Some(lhs.cmp(&rhs)),
+            _ => None,
+        };
+        if let Some(ordering) = ordering {
+            let result = match name {
+                "lt" => ordering.is_lt(),
+                "le" => ordering.is_le(),
+                "gt" => ordering.is_gt(),
+                "ge" => ordering.is_ge(),
+                _ => {
+                    return Err(GeneratorError::InternalError(format!(
+                        "unknown comparison: {name}"
+                    )))
+                }
+            };
+            let instrs = builder.instrs_mut();
+            instrs.truncate(instrs.len() - 4);
+            builder.i32_const(result as i32);
+            return Ok(());
+        }
+    }
+
     let func = generator
-        .module
-        .funcs
-        .by_name(&format!("stdlib.{name}-{type_suffix}"))
+        .try_func_by_name(&format!("stdlib.{name}-{type_suffix}"))
         .ok_or_else(|| {
             GeneratorError::InternalError(format!("function not found: {name}-{type_suffix}"))
         })?;
@@ -55,6 +91,10 @@ impl Word for CmpLess {
 }
 
 impl SimpleWord for CmpLess {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -76,6 +116,10 @@ impl Word for CmpLeq {
 }
 
 impl SimpleWord for CmpLeq {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -97,6 +141,10 @@ impl Word for CmpGreater {
 }
 
 impl SimpleWord for CmpGreater {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -118,6 +166,10 @@ impl Word for CmpGeq {
 }
 
 impl SimpleWord for CmpGeq {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -128,3 +180,238 @@ impl SimpleWord for CmpGeq {
         traverse_comparison("ge", generator, builder, arg_types, return_type)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use clarity::vm::types::{
+        QualifiedContractIdentifier, StandardPrincipalData, TupleTypeSignature, TypeSignature,
+    };
+    use clarity::vm::{SymbolicExpressionType, Value};
+    use walrus::FunctionBuilder;
+
+    use super::CmpLess;
+    use crate::tools::{compile_analysis, crosscheck, crosscheck_expect_failure, top_level_calls};
+    use crate::wasm_generator::{GeneratorError, WasmGenerator};
+    use crate::wasm_utils::Arity;
+    use crate::words::SimpleWord;
+
+    #[test]
+    fn comparison_of_optional_names_the_type() {
+        // The typechecker rejects `(< (some 1) (some 2))`, so the word is
+        // visited directly with an optional operand.
+        let contract_analysis = compile_analysis("(+ 1 2)");
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+
+        let optional_int = TypeSignature::OptionalType(Box::new(TypeSignature::IntType));
+        let err = CmpLess
+            .visit(
+                &mut generator,
+                &mut func.func_body(),
+                &[optional_int.clone(), optional_int],
+                &TypeSignature::BoolType,
+            )
+            .unwrap_err();
+        let GeneratorError::TypeError(msg) = err else {
+            panic!("expected a type error, got {err:?}");
+        };
+        assert_eq!(
+            msg,
+            "invalid type for comparison: (optional int); ordered comparison is not defined for \
+             tuple, list, optional and response types, use `is-eq` to test them for equality"
+        );
+    }
+
+    #[test]
+    fn comparison_of_tuples_points_at_is_eq() {
+        // The typechecker rejects `(< {a: 1} {a: 2})`, so the word is also
+        // visited directly with tuple operands.
+        crosscheck_expect_failure("(< {a: 1} {a: 2})");
+
+        let contract_analysis = compile_analysis("(+ 1 2)");
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+
+        let tuple = TypeSignature::TupleType(
+            TupleTypeSignature::try_from(vec![("a".into(), TypeSignature::IntType)]).unwrap(),
+        );
+        let err = CmpLess
+            .visit(
+                &mut generator,
+                &mut func.func_body(),
+                &[tuple.clone(), tuple],
+                &TypeSignature::BoolType,
+            )
+            .unwrap_err();
+        let GeneratorError::TypeError(msg) = err else {
+            panic!("expected a type error, got {err:?}");
+        };
+        assert_eq!(
+            msg,
+            "invalid type for comparison: (tuple (a int)); ordered comparison is not defined for \
+             tuple, list, optional and response types, use `is-eq` to test them for equality"
+        );
+
+        // Other types keep the list of the supported ones.
+        let err = CmpLess
+            .visit(
+                &mut generator,
+                &mut func.func_body(),
+                &[TypeSignature::BoolType, TypeSignature::BoolType],
+                &TypeSignature::BoolType,
+            )
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("comparison only supports int, uint"));
+    }
+
+    #[test]
+    fn comparison_with_one_operand_is_an_arity_error() {
+        // The typechecker rejects `(< 1)`, so the last operand of `(< 1 2)` is
+        // removed after analysis.
+        let contract_analysis = compile_analysis("(< 1 2)");
+        let mut expr = contract_analysis.expressions[0].clone();
+        let SymbolicExpressionType::List(list) = &mut expr.expr else {
+            panic!("expected a list");
+        };
+        list.pop();
+
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        let err = generator
+            .traverse_expr(&mut func.func_body(), &expr)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[E_ARGUMENT_COUNT] <: expecting 2 arguments, got 1"
+        );
+        let GeneratorError::ArityMismatch {
+            name,
+            expected,
+            got,
+        } = err
+        else {
+            panic!("expected an arity mismatch, got {err:?}");
+        };
+        assert_eq!((name.as_str(), expected, got), ("<", Arity::Exact(2), 1));
+    }
+
+    #[test]
+    fn uint_literals_straddling_64_bits() {
+        crosscheck(
+            "(> u18446744073709551617 u18446744073709551616)",
+            Ok(Some(Value::Bool(true))),
+        );
+        crosscheck(
+            "(< u18446744073709551615 u18446744073709551616)",
+            Ok(Some(Value::Bool(true))),
+        );
+    }
+
+    #[test]
+    fn int_literals_straddling_64_bits() {
+        crosscheck(
+            "(< -18446744073709551617 -18446744073709551616)",
+            Ok(Some(Value::Bool(true))),
+        );
+        crosscheck(
+            "(>= 18446744073709551616 9223372036854775808)",
+            Ok(Some(Value::Bool(true))),
+        );
+    }
+
+    /// Counts the calls to `stdlib.lt-int` in the top-level function compiled
+    /// from `snippet`.
+    fn top_level_lt_int_calls(snippet: &str) -> usize {
+        let (module, calls) = top_level_calls(snippet);
+        let lt_int = module.funcs.by_name("stdlib.lt-int").unwrap();
+        calls.iter().filter(|&&func| func == lt_int).count()
+    }
+
+    #[test]
+    fn comparison_of_let_bound_literal_folds() {
+        let snippet = "(let ((x 1)) (< x 2))";
+        crosscheck(snippet, Ok(Some(Value::Bool(true))));
+        assert_eq!(top_level_lt_int_calls(snippet), 0);
+    }
+
+    #[test]
+    fn comparison_of_let_bound_runtime_value_is_not_folded() {
+        let snippet = "(define-data-var v int 1) (let ((x (var-get v))) (< x 2))";
+        crosscheck(snippet, Ok(Some(Value::Bool(true))));
+        assert_eq!(top_level_lt_int_calls(snippet), 1);
+    }
+
+    //
+    // Module with tests that should only be executed
+    // when running Clarity::V2 or Clarity::V3.
+    //
+    #[cfg(any(feature = "test-clarity-v2", feature = "test-clarity-v3"))]
+    #[cfg(test)]
+    mod clarity_v2_v3 {
+        use clarity::vm::Value;
+
+        use crate::tools::crosscheck;
+
+        #[test]
+        fn string_ascii_prefix_is_less() {
+            crosscheck(r#"(< "ab" "abc")"#, Ok(Some(Value::Bool(true))));
+            crosscheck(r#"(< "abc" "ab")"#, Ok(Some(Value::Bool(false))));
+            crosscheck(r#"(< "" "a")"#, Ok(Some(Value::Bool(true))));
+            crosscheck(r#"(> "abc" "ab")"#, Ok(Some(Value::Bool(true))));
+            crosscheck(r#"(<= "ab" "ab")"#, Ok(Some(Value::Bool(true))));
+        }
+
+        #[test]
+        fn buff_comparison_matrix() {
+            // Buffers compare like byte slices: lexicographically, with a
+            // strict prefix being less.
+            let pairs: [(&[u8], &[u8]); 8] = [
+                (&[1, 2], &[1, 2]),
+                (&[1], &[1, 2]),
+                (&[1, 2], &[1]),
+                (&[1, 0xff], &[2]),
+                (&[2], &[1, 0xff]),
+                (&[], &[1]),
+                (&[1], &[]),
+                (&[], &[]),
+            ];
+            let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+            for (lhs, rhs) in pairs {
+                for (op, expected) in [
+                    ("<", lhs < rhs),
+                    ("<=", lhs <= rhs),
+                    (">", lhs > rhs),
+                    (">=", lhs >= rhs),
+                ] {
+                    crosscheck(
+                        &format!("({op} 0x{} 0x{})", hex(lhs), hex(rhs)),
+                        Ok(Some(Value::Bool(expected))),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn min_max_are_user_defined() {
+        // No Clarity version has `min` or `max`, so they are unknown
+        // functions unless the contract defines them.
+        crosscheck_expect_failure("(max 1 2 3)");
+        crosscheck_expect_failure("(min u5 u2)");
+
+        crosscheck(
+            "
+(define-private (max (a int) (b int)) (if (> a b) a b))
+(max (max 1 2) 3)",
+            Ok(Some(Value::Int(3))),
+        );
+        crosscheck(
+            "
+(define-private (min (a uint) (b uint)) (if (< a b) a b))
+(min u5 u2)",
+            Ok(Some(Value::UInt(2))),
+        );
+    }
+}
@@ -9,7 +9,7 @@ use crate::wasm_generator::{
     add_placeholder_for_clarity_type, clar2wasm_ty, drop_value, ArgumentsExt, GeneratorError,
     SequenceElementType, WasmGenerator,
 };
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 use crate::{check_args, words};
 
 #[derive(Debug)]
@@ -22,6 +22,10 @@ impl Word for If {
 }
 
 impl ComplexWord for If {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -67,6 +71,10 @@ impl Word for Match {
 }
 
 impl ComplexWord for Match {
+    fn arity(&self) -> Arity {
+        Arity::Between(4, 5)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -192,6 +200,10 @@ impl Word for Filter {
 }
 
 impl ComplexWord for Filter {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -243,7 +255,7 @@ impl ComplexWord for Filter {
         // now we have an empty stack, and three initialized locals
 
         // reserve space for the length of the output list
-        let (output_offset, _) = generator.create_call_stack_local(builder, &ty, false, true);
+        let (output_offset, _) = generator.create_call_stack_local(builder, &ty, false, true)?;
 
         let memory = generator.get_memory()?;
 
@@ -309,10 +321,11 @@ impl ComplexWord for Filter {
             // [ output_write_pos ]
             .local_get(input_offset)
             // [ output_write_pos, input_offset ]
-            .i32_const(elem_size)
-            // [ output_write_pos, input_offset, element_size ]
-            .memory_copy(memory, memory)
-            // [  ]
+            .i32_const(elem_size);
+        // [ output_write_pos, input_offset, element_size ]
+        generator.emit_memory_copy(&mut success_branch, memory);
+        // [  ]
+        success_branch
             .local_get(output_len)
             // [ output_len ]
             .i32_const(elem_size)
@@ -362,7 +375,10 @@ fn traverse_short_circuiting_list(
     args: &[SymbolicExpression],
     invert: bool,
 ) -> Result<(), GeneratorError> {
-    let n_branches = args.len();
+    // The operands of the nested forms of the same word are branches of this
+    // one, `(and a (and b c))` short-circuits as `(and a b c)`.
+    let name = if invert { "or" } else { "and" };
+    let operands = generator.flatten_variadic(name, args, |_, _, _| Ok(()))?;
 
     let mut branches = vec![];
 
@@ -376,9 +392,7 @@ fn traverse_short_circuiting_list(
         .i32_const(if invert { 1 } else { 0 })
         .id();
 
-    for i in 0..n_branches {
-        let branch_expr = args.get_expr(i)?;
-
+    for branch_expr in operands {
         let mut branch = builder.dangling_instr_seq(InstrSeqType::new(
             &mut generator.module.types,
             &[],
@@ -419,6 +433,10 @@ impl Word for And {
 }
 
 impl ComplexWord for And {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -448,6 +466,10 @@ impl Word for SimpleAnd {
 }
 
 impl SimpleWord for SimpleAnd {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn visit(
         &self,
         _generator: &mut WasmGenerator,
@@ -472,6 +494,10 @@ impl Word for Or {
 }
 
 impl ComplexWord for Or {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -501,6 +527,10 @@ impl Word for SimpleOr {
 }
 
 impl SimpleWord for SimpleOr {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn visit(
         &self,
         _generator: &mut WasmGenerator,
@@ -525,6 +555,10 @@ impl Word for Unwrap {
 }
 
 impl ComplexWord for Unwrap {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -610,6 +644,10 @@ impl Word for UnwrapErr {
 }
 
 impl ComplexWord for UnwrapErr {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -703,6 +741,10 @@ impl Word for Asserts {
 }
 
 impl ComplexWord for Asserts {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -782,6 +824,10 @@ impl Word for Try {
 }
 
 impl ComplexWord for Try {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -862,19 +908,41 @@ impl ComplexWord for Try {
                 // then add a placeholder for the ok-type of the return type
                 // and restore the err value from the locals.
                 throw_branch.i32_const(0);
-                let placeholder_ty = match generator.get_current_function_return_type() {
-                    Some(TypeSignature::ResponseType(inner_types)) => &inner_types.0,
-                    Some(other) => {
+                let (placeholder_ty, return_err_ty) =
+                    match generator.get_current_function_return_type() {
+                        Some(TypeSignature::ResponseType(inner_types)) => {
+                            (&inner_types.0, Some(&inner_types.1))
+                        }
+                        Some(other) => {
+                            return Err(GeneratorError::TypeError(format!(
+                                "expected response type, got {:?}",
+                                other
+                            )));
+                        }
+                        None => (&TypeSignature::NoType, None),
+                    };
+                add_placeholder_for_clarity_type(&mut throw_branch, placeholder_ty);
+                match return_err_ty {
+                    // The input can't be an `err`, so its error value is only a
+                    // placeholder, which may not have the representation of
+                    // the error type of the function.
+                    Some(return_err_ty) if *err_type == TypeSignature::NoType => {
+                        add_placeholder_for_clarity_type(&mut throw_branch, return_err_ty);
+                    }
+                    Some(return_err_ty)
+                        if !return_err_ty
+                            .admits_type(&generator.contract_analysis.epoch, err_type)
+                            .unwrap_or(false) =>
+                    {
                         return Err(GeneratorError::TypeError(format!(
-                            "expected response type, got {:?}",
-                            other
+                            "try! error type {err_type} is not admitted by the function error type {return_err_ty}"
                         )));
                     }
-                    None => &TypeSignature::NoType,
-                };
-                add_placeholder_for_clarity_type(&mut throw_branch, placeholder_ty);
-                for local in &err_locals {
-                    throw_branch.local_get(*local);
+                    _ => {
+                        for local in &err_locals {
+                            throw_branch.local_get(*local);
+                        }
+                    }
                 }
                 generator.return_early(
                     &mut throw_branch,
@@ -918,10 +986,17 @@ impl ComplexWord for Try {
 #[cfg(test)]
 mod tests {
     use clarity::vm::errors::{Error, ShortReturnType};
-    use clarity::vm::types::ResponseData;
+    use clarity::vm::types::{
+        FixedFunction, QualifiedContractIdentifier, ResponseData, StandardPrincipalData,
+        TypeSignature,
+    };
     use clarity::vm::Value;
+    use walrus::FunctionBuilder;
 
-    use crate::tools::{crosscheck, crosscheck_expect_failure, evaluate};
+    use super::Try;
+    use crate::tools::{compile_analysis, crosscheck, crosscheck_expect_failure, evaluate};
+    use crate::wasm_generator::{GeneratorError, WasmGenerator};
+    use crate::words::ComplexWord;
 
     #[test]
     fn trivial() {
@@ -1415,6 +1490,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn try_propagates_error_of_function_type() {
+        let snippet = "
+(define-private (checked (x uint))
+  (if (> x u10) (err u1) (ok x)))
+(define-private (double-checked (x uint))
+  (begin
+    (try! (ok u0))
+    (ok (* (try! (checked x)) u2))))";
+
+        crosscheck(
+            &format!("{snippet} (double-checked u3)"),
+            Ok(Some(Value::okay(Value::UInt(6)).unwrap())),
+        );
+        crosscheck(
+            &format!("{snippet} (double-checked u11)"),
+            Ok(Some(Value::error(Value::UInt(1)).unwrap())),
+        );
+    }
+
+    #[test]
+    fn try_error_type_mismatch() {
+        let result = evaluate(
+            "
+(define-private (mismatched (x (response uint int)))
+  (begin
+    (try! x)
+    (err u1)))",
+        );
+        assert!(result.is_err());
+
+        // The typechecker rejects the snippet above, so the word is also
+        // traversed directly, in a function whose error type is `uint`.
+        let contract_analysis = compile_analysis(
+            "(define-private (f) (begin (try! (if true (ok u1) (err 2))) (ok u2)))",
+        );
+        let define = contract_analysis.expressions[0].match_list().unwrap();
+        let try_expr = define[2].match_list().unwrap()[1].clone();
+        let args = try_expr.match_list().unwrap()[1..].to_vec();
+
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        generator.enter_function(
+            &"f".into(),
+            &FixedFunction {
+                args: vec![],
+                returns: TypeSignature::new_response(
+                    TypeSignature::UIntType,
+                    TypeSignature::UIntType,
+                )
+                .unwrap(),
+            },
+        );
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        let err = Try
+            .traverse(&mut generator, &mut func.func_body(), &try_expr, &args)
+            .unwrap_err();
+        let GeneratorError::TypeError(msg) = err else {
+            panic!("expected a type error, got {err:?}");
+        };
+        assert_eq!(
+            msg,
+            "try! error type int is not admitted by the function error type uint"
+        );
+    }
+
     #[test]
     fn try_less_than_one_arg() {
         let result =
@@ -7,7 +7,7 @@ use crate::wasm_generator::{
     add_placeholder_for_clarity_type, clar2wasm_ty, drop_value, ArgumentsExt, GeneratorError,
     WasmGenerator,
 };
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct ToConsensusBuff;
@@ -19,6 +19,10 @@ impl Word for ToConsensusBuff {
 }
 
 impl ComplexWord for ToConsensusBuff {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
@@ -47,7 +51,7 @@ impl ComplexWord for ToConsensusBuff {
                 )
             })?
             .clone();
-        let (offset, _) = generator.create_call_stack_local(builder, &expr_ty, false, true);
+        let (offset, _) = generator.create_call_stack_local(builder, &expr_ty, false, true)?;
 
         let length = generator.module.locals.add(walrus::ValType::I32);
 
@@ -98,6 +102,10 @@ impl Word for FromConsensusBuff {
 }
 
 impl ComplexWord for FromConsensusBuff {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -130,7 +138,7 @@ impl ComplexWord for FromConsensusBuff {
         generator.traverse_expr(builder, args.get_expr(1)?)?;
 
         // TODO: true, true is too big; see issue: #593
-        let (offset_result, _len) = generator.create_call_stack_local(builder, &ty, true, true);
+        let (offset_result, _len) = generator.create_call_stack_local(builder, &ty, true, true)?;
         let offset = generator.module.locals.add(walrus::ValType::I32);
         let end = generator.module.locals.add(walrus::ValType::I32);
         builder
@@ -5,7 +5,7 @@ use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{ArgumentsExt, GeneratorError, WasmGenerator};
 use crate::wasm_utils::{
-    check_argument_count, get_type_size, is_in_memory_type, ArgumentCountCheck,
+    check_argument_count, get_type_size, is_in_memory_type, ArgumentCountCheck, Arity,
 };
 
 #[derive(Debug)]
@@ -18,6 +18,10 @@ impl Word for DefineConstant {
 }
 
 impl ComplexWord for DefineConstant {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
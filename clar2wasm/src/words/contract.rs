@@ -7,8 +7,8 @@ use walrus::ValType;
 
 use super::{ComplexWord, Word};
 use crate::check_args;
-use crate::wasm_generator::{ArgumentsExt, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_generator::{clar2wasm_ty, ArgumentsExt, GeneratorError, WasmGenerator};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct AsContract;
@@ -20,6 +20,10 @@ impl Word for AsContract {
 }
 
 impl ComplexWord for AsContract {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -54,6 +58,10 @@ impl Word for ContractCall {
 }
 
 impl ComplexWord for ContractCall {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -71,11 +79,17 @@ impl ComplexWord for ContractCall {
 
         let function_name = args.get_name(1)?;
         let contract_expr = args.get_expr(0)?;
+        let mut signature = None;
         if let SymbolicExpressionType::LiteralValue(Value::Principal(PrincipalData::Contract(
             ref contract_identifier,
         ))) = contract_expr.expr
         {
             // This is a static contract call.
+            signature = generator
+                .contract_call_signatures
+                .get(&(contract_identifier.clone(), function_name.clone()))
+                .cloned();
+
             // Push an empty trait name first
             builder.i32_const(0).i32_const(0);
             // Push the contract identifier onto the stack
@@ -138,6 +152,15 @@ impl ComplexWord for ContractCall {
             })
             .collect::<Result<_, _>>()?;
 
+        // The callee reads the arguments with the types it declares, which
+        // may be wider than the ones of the arguments.
+        let args_ty = match signature {
+            Some(signature) => {
+                coerce_contract_call_args(generator, function_name, args, args_ty, signature.args)?
+            }
+            None => args_ty,
+        };
+
         // Push the function name onto the stack
         let (fn_offset, fn_length) = generator.add_string_literal(function_name)?;
         builder
@@ -173,7 +196,7 @@ impl ComplexWord for ContractCall {
             })?
             .clone();
         let (return_offset, return_size) =
-            generator.create_call_stack_local(builder, &return_ty, true, true);
+            generator.create_call_stack_local(builder, &return_ty, true, true)?;
 
         // Push the return offset and size to the data stack
         builder.local_get(return_offset).i32_const(return_size);
@@ -189,11 +212,55 @@ impl ComplexWord for ContractCall {
     }
 }
 
+/// Returns the types with which the arguments of a `contract-call?` should be
+/// written for the callee, given the parameter types it declares. Arguments
+/// keep their own type when it has the same representation, e.g. a shorter
+/// `string-ascii`. Otherwise, like a `none` passed as an `(optional int)`,
+/// they are generated with the declared type, which must admit them.
+fn coerce_contract_call_args(
+    generator: &mut WasmGenerator,
+    function_name: &ClarityName,
+    args: &[SymbolicExpression],
+    args_ty: Vec<TypeSignature>,
+    declared_ty: Vec<TypeSignature>,
+) -> Result<Vec<TypeSignature>, GeneratorError> {
+    if args_ty.len() != declared_ty.len() {
+        return Err(GeneratorError::TypeError(format!(
+            "contract-call? to {function_name}: expecting {} arguments, got {}",
+            declared_ty.len(),
+            args_ty.len()
+        )));
+    }
+
+    let epoch = generator.contract_analysis.epoch;
+    let mut coerced = Vec::with_capacity(args_ty.len());
+    for ((arg, arg_ty), declared) in args.iter().zip(args_ty).zip(declared_ty) {
+        if clar2wasm_ty(&arg_ty) == clar2wasm_ty(&declared) {
+            coerced.push(arg_ty);
+            continue;
+        }
+        if !declared.admits_type(&epoch, &arg_ty).unwrap_or(false) {
+            return Err(GeneratorError::TypeError(format!(
+                "contract-call? to {function_name}: expecting argument of type {declared}, \
+                 found {arg_ty}"
+            )));
+        }
+        // WORKAROUND: as in `tuple`, the argument is generated with the
+        // declared type so that its placeholders have the right shape.
+        generator.set_expr_type(arg, declared.clone())?;
+        coerced.push(declared);
+    }
+    Ok(coerced)
+}
+
 #[cfg(test)]
 mod tests {
-    use clarity::vm::Value;
+    use clarity::vm::types::TypeSignature;
+    use clarity::vm::{SymbolicExpression, Value};
 
-    use crate::tools::{evaluate, TestEnvironment};
+    use super::coerce_contract_call_args;
+    use crate::tools::{compile_analysis, evaluate, TestEnvironment};
+    use crate::wasm_generator::{GeneratorError, WasmGenerator};
 
     #[test]
     fn as_contract_less_than_one_arg() {
@@ -357,6 +424,98 @@ mod tests {
         );
     }
 
+    #[test]
+    fn static_args_with_declared_types() {
+        let mut env = TestEnvironment::default();
+        env.init_contract_with_snippet(
+            "contract-callee",
+            r#"
+(define-public (wide-args (x (optional int)) (s (string-ascii 16)) (y int))
+    (ok (+ (default-to 10 x) (to-int (len s)) y))
+)
+            "#,
+        )
+        .expect("Failed to init contract.");
+        // `none` has a narrower type than the declared `(optional int)`, and
+        // the arguments after it must still be read at the right offsets.
+        let val = env
+            .init_contract_with_snippet(
+                "contract-caller",
+                r#"(contract-call? .contract-callee wide-args none "abc" 5)"#,
+            )
+            .expect("Failed to init contract.");
+
+        assert_eq!(val.unwrap(), Value::okay(Value::Int(18)).unwrap());
+    }
+
+    #[test]
+    fn static_mismatched_arg_type() {
+        let mut env = TestEnvironment::default();
+        env.init_contract_with_snippet(
+            "contract-callee",
+            r#"
+(define-public (one-simple-arg (x int))
+    (ok x)
+)
+            "#,
+        )
+        .expect("Failed to init contract.");
+        let result = env.init_contract_with_snippet(
+            "contract-caller",
+            "(contract-call? .contract-callee one-simple-arg u42)",
+        );
+
+        // The type checker rejects the argument before the generator.
+        let err = result.unwrap_err().to_string();
+        assert!(
+            err.contains("expecting expression of type 'int', found 'uint'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn coerce_mismatched_arg_type() {
+        // The type checker rejects a `bool` passed as an `(optional int)`,
+        // so the arguments are coerced directly.
+        let contract_analysis = compile_analysis("(+ 1 2)");
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let err = coerce_contract_call_args(
+            &mut generator,
+            &"one-optional-arg".into(),
+            &[SymbolicExpression::atom_value(Value::Bool(true))],
+            vec![TypeSignature::BoolType],
+            vec![TypeSignature::new_option(TypeSignature::IntType).unwrap()],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            &err,
+            GeneratorError::TypeError(msg) if msg == "contract-call? to one-optional-arg: \
+                expecting argument of type (optional int), found bool"
+        ));
+    }
+
+    #[test]
+    fn static_read_only_args_with_declared_types() {
+        let mut env = TestEnvironment::default();
+        env.init_contract_with_snippet(
+            "contract-callee",
+            r#"
+(define-read-only (wide-args (x (optional int)) (s (string-ascii 16)) (y int))
+    (ok (+ (default-to 10 x) (to-int (len s)) y))
+)
+            "#,
+        )
+        .expect("Failed to init contract.");
+        let val = env
+            .init_contract_with_snippet(
+                "contract-caller",
+                r#"(contract-call? .contract-callee wide-args none "abc" 5)"#,
+            )
+            .expect("Failed to init contract.");
+
+        assert_eq!(val.unwrap(), Value::okay(Value::Int(18)).unwrap());
+    }
+
     #[test]
     fn dynamic_no_args() {
         let mut env = TestEnvironment::default();
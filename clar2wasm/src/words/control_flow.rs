@@ -6,7 +6,7 @@ use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::error_mapping::ErrorMap;
 use crate::wasm_generator::{drop_value, ArgumentsExt, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct Begin;
@@ -18,6 +18,10 @@ impl Word for Begin {
 }
 
 impl ComplexWord for Begin {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -42,7 +46,20 @@ impl ComplexWord for Begin {
                 .ok_or_else(|| GeneratorError::TypeError("begin must be typed".to_owned()))?
                 .clone(),
         )?;
-        generator.traverse_statement_list(builder, args)
+
+        // The statements of the nested `begin`s are traversed in place, with
+        // the last one of each taking its type.
+        let statements = generator.flatten_variadic("begin", args, |generator, begin, args| {
+            let ty = generator
+                .get_expr_type(begin)
+                .ok_or_else(|| GeneratorError::TypeError("begin must be typed".to_owned()))?
+                .clone();
+            match args.last() {
+                Some(last) => generator.set_expr_type(last, ty),
+                None => Ok(()),
+            }
+        })?;
+        generator.traverse_statements(builder, &statements)
     }
 }
 
@@ -56,6 +73,10 @@ impl Word for UnwrapPanic {
 }
 
 impl ComplexWord for UnwrapPanic {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -79,6 +100,28 @@ impl ComplexWord for UnwrapPanic {
             })?
             .clone();
 
+        // An optional without a `some` type can only be `none`, and a response
+        // without an `ok` type can only be an `err`: unwrapping it always
+        // panics, and nothing after it is reachable.
+        let always_panics = match &input_ty {
+            TypeSignature::OptionalType(val_ty) => **val_ty == TypeSignature::NoType,
+            TypeSignature::ResponseType(ok_err_ty) => ok_err_ty.0 == TypeSignature::NoType,
+            _ => false,
+        };
+        if always_panics {
+            drop_value(builder, &input_ty);
+            let runtime_error = generator
+                .try_func_by_name("stdlib.runtime-error")
+                .ok_or_else(|| {
+                    GeneratorError::InternalError("stdlib.runtime-error not found".to_owned())
+                })?;
+            builder
+                .i32_const(ErrorMap::Panic as i32)
+                .call(runtime_error)
+                .unreachable();
+            return Ok(());
+        }
+
         match &input_ty {
             TypeSignature::OptionalType(val_ty) => {
                 // For the optional case, e.g. `(unwrap-panic (some 1))`, the stack
@@ -98,9 +141,7 @@ impl ComplexWord for UnwrapPanic {
                     let mut if_case = builder.dangling_instr_seq(None);
                     if_case.i32_const(ErrorMap::Panic as i32).call(
                         generator
-                            .module
-                            .funcs
-                            .by_name("stdlib.runtime-error")
+                            .try_func_by_name("stdlib.runtime-error")
                             .ok_or_else(|| {
                                 GeneratorError::InternalError(
                                     "stdlib.runtime-error not found".to_owned(),
@@ -152,9 +193,7 @@ impl ComplexWord for UnwrapPanic {
                     let mut if_case = builder.dangling_instr_seq(None);
                     if_case.i32_const(ErrorMap::Panic as i32).call(
                         generator
-                            .module
-                            .funcs
-                            .by_name("stdlib.runtime-error")
+                            .try_func_by_name("stdlib.runtime-error")
                             .ok_or_else(|| {
                                 GeneratorError::InternalError(
                                     "stdlib.runtime-error not found".to_owned(),
@@ -196,6 +235,10 @@ impl Word for UnwrapErrPanic {
 }
 
 impl ComplexWord for UnwrapErrPanic {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -252,9 +295,7 @@ impl ComplexWord for UnwrapErrPanic {
                     let mut else_case = builder.dangling_instr_seq(None);
                     else_case.i32_const(ErrorMap::Panic as i32).call(
                         generator
-                            .module
-                            .funcs
-                            .by_name("stdlib.runtime-error")
+                            .try_func_by_name("stdlib.runtime-error")
                             .ok_or_else(|| {
                                 GeneratorError::InternalError(
                                     "stdlib.runtime-error not found".to_owned(),
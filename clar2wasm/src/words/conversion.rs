@@ -1,7 +1,10 @@
-use clarity::vm::types::{SequenceSubtype, StringSubtype, TypeSignature};
+use clarity::vm::types::{SequenceData, SequenceSubtype, StringSubtype, TypeSignature};
+use clarity::vm::{SymbolicExpression, SymbolicExpressionType, Value};
 
-use super::{SimpleWord, Word};
-use crate::wasm_generator::GeneratorError;
+use super::{ComplexWord, SimpleWord, Word};
+use crate::check_args;
+use crate::wasm_generator::{ArgumentsExt, GeneratorError, WasmGenerator};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct StringToInt;
@@ -13,6 +16,10 @@ impl Word for StringToInt {
 }
 
 impl SimpleWord for StringToInt {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
@@ -51,6 +58,10 @@ impl Word for StringToUint {
 }
 
 impl SimpleWord for StringToUint {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
@@ -80,6 +91,75 @@ impl SimpleWord for StringToUint {
     }
 }
 
+/// Pushes the decimal representation of a constant integer as a string literal.
+/// Returns `false` if `arg` is not an integer literal.
+fn traverse_constant_int_to_string(
+    generator: &mut WasmGenerator,
+    builder: &mut walrus::InstrSeqBuilder,
+    arg: &SymbolicExpression,
+    utf8: bool,
+) -> Result<bool, GeneratorError> {
+    let decimal = match &arg.expr {
+        SymbolicExpressionType::LiteralValue(Value::Int(i)) => i.to_string(),
+        SymbolicExpressionType::LiteralValue(Value::UInt(u)) => u.to_string(),
+        _ => return Ok(false),
+    };
+
+    let string = if utf8 {
+        Value::string_utf8_from_bytes(decimal.into_bytes())
+    } else {
+        Value::string_ascii_from_bytes(decimal.into_bytes())
+    }
+    .map_err(|e| GeneratorError::InternalError(e.to_string()))?;
+    let Value::Sequence(SequenceData::String(string)) = string else {
+        return Err(GeneratorError::InternalError(
+            "expected a string value".to_owned(),
+        ));
+    };
+
+    let (offset, len) = generator.add_clarity_string_literal(&string)?;
+    builder.i32_const(offset as i32).i32_const(len as i32);
+
+    Ok(true)
+}
+
+/// Converts the integer on top of the stack to a string at runtime, with the
+/// stdlib function `stdlib.{int|uint}-{func_suffix}`.
+fn traverse_int_to_string(
+    name: &str,
+    func_suffix: &str,
+    generator: &mut WasmGenerator,
+    builder: &mut walrus::InstrSeqBuilder,
+    expr: &SymbolicExpression,
+    arg: &SymbolicExpression,
+) -> Result<(), GeneratorError> {
+    let type_prefix = match generator.get_expr_type(arg) {
+        Some(TypeSignature::IntType) => "int",
+        Some(TypeSignature::UIntType) => "uint",
+        _ => {
+            return Err(GeneratorError::TypeError(format!(
+                "invalid type for {name}"
+            )));
+        }
+    };
+    let return_type = generator
+        .get_expr_type(expr)
+        .ok_or_else(|| GeneratorError::TypeError(format!("{name} expression must be typed")))?
+        .clone();
+
+    generator.traverse_expr(builder, arg)?;
+
+    let (result_offset, _) =
+        generator.create_call_stack_local(builder, &return_type, false, true)?;
+    builder.local_get(result_offset);
+
+    let func = generator.func_by_name(&format!("stdlib.{type_prefix}-{func_suffix}"));
+
+    builder.call(func);
+
+    Ok(())
+}
+
 #[derive(Debug)]
 pub struct IntToAscii;
 
@@ -89,33 +169,27 @@ impl Word for IntToAscii {
     }
 }
 
-impl SimpleWord for IntToAscii {
-    fn visit(
+impl ComplexWord for IntToAscii {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
+    fn traverse(
         &self,
-        generator: &mut crate::wasm_generator::WasmGenerator,
+        generator: &mut WasmGenerator,
         builder: &mut walrus::InstrSeqBuilder,
-        arg_types: &[TypeSignature],
-        return_type: &TypeSignature,
-    ) -> Result<(), crate::wasm_generator::GeneratorError> {
-        let type_prefix = match arg_types[0] {
-            TypeSignature::IntType => "int",
-            TypeSignature::UIntType => "uint",
-            _ => {
-                return Err(GeneratorError::TypeError(
-                    "invalid type for int-to-ascii".to_owned(),
-                ));
-            }
-        };
-
-        let (result_offset, _) =
-            generator.create_call_stack_local(builder, return_type, false, true);
-        builder.local_get(result_offset);
-
-        let func = generator.func_by_name(&format!("stdlib.{type_prefix}-to-string"));
+        expr: &SymbolicExpression,
+        args: &[SymbolicExpression],
+    ) -> Result<(), GeneratorError> {
+        check_args!(generator, builder, 1, args.len(), ArgumentCountCheck::Exact);
 
-        builder.call(func);
+        let arg = args.get_expr(0)?;
+        // A constant can be converted at compile time.
+        if traverse_constant_int_to_string(generator, builder, arg, false)? {
+            return Ok(());
+        }
 
-        Ok(())
+        traverse_int_to_string("int-to-ascii", "to-string", generator, builder, expr, arg)
     }
 }
 
@@ -128,33 +202,27 @@ impl Word for IntToUtf8 {
     }
 }
 
-impl SimpleWord for IntToUtf8 {
-    fn visit(
+impl ComplexWord for IntToUtf8 {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
+    fn traverse(
         &self,
-        generator: &mut crate::wasm_generator::WasmGenerator,
+        generator: &mut WasmGenerator,
         builder: &mut walrus::InstrSeqBuilder,
-        arg_types: &[TypeSignature],
-        return_type: &TypeSignature,
+        expr: &SymbolicExpression,
+        args: &[SymbolicExpression],
     ) -> Result<(), GeneratorError> {
-        let type_prefix = match arg_types[0] {
-            TypeSignature::IntType => "int",
-            TypeSignature::UIntType => "uint",
-            _ => {
-                return Err(GeneratorError::TypeError(
-                    "invalid type for int-to-utf8".to_owned(),
-                ));
-            }
-        };
-
-        let (result_offset, _) =
-            generator.create_call_stack_local(builder, return_type, false, true);
-        builder.local_get(result_offset);
-
-        let func = generator.func_by_name(&format!("stdlib.{type_prefix}-to-utf8"));
+        check_args!(generator, builder, 1, args.len(), ArgumentCountCheck::Exact);
 
-        builder.call(func);
+        let arg = args.get_expr(0)?;
+        // A constant can be converted at compile time.
+        if traverse_constant_int_to_string(generator, builder, arg, true)? {
+            return Ok(());
+        }
 
-        Ok(())
+        traverse_int_to_string("int-to-utf8", "to-utf8", generator, builder, expr, arg)
     }
 }
 
@@ -166,7 +234,8 @@ mod tests {
         use clarity::vm::types::{ASCIIData, CharType, SequenceData, UTF8Data};
         use clarity::vm::Value;
 
-        use crate::tools::crosscheck;
+        use crate::tools::{compile_snippet, crosscheck, top_level_calls};
+        use crate::CompileError;
 
         #[test]
         fn valid_string_to_int() {
@@ -307,5 +376,64 @@ mod tests {
                 ))))),
             )
         }
+
+        #[test]
+        fn int_to_string_variable() {
+            crosscheck(
+                r#"(let ((n -2048)) (int-to-ascii n))"#,
+                Ok(Some(Value::Sequence(SequenceData::String(
+                    CharType::ASCII(ASCIIData {
+                        data: "-2048".bytes().collect(),
+                    }),
+                )))),
+            );
+            crosscheck(
+                r#"(let ((n u42)) (int-to-utf8 n))"#,
+                Ok(Some(Value::Sequence(SequenceData::String(CharType::UTF8(
+                    UTF8Data {
+                        data: "42".bytes().map(|b| vec![b]).collect(),
+                    },
+                ))))),
+            );
+        }
+
+        #[test]
+        fn int_to_ascii_does_not_fit_a_shorter_string() {
+            // `int-to-ascii` returns a `(string-ascii 40)`, which the type
+            // checker refuses to narrow, whatever the runtime length.
+            let Err(CompileError::Generic { diagnostics, .. }) =
+                compile_snippet("(define-data-var s (string-ascii 3) (int-to-ascii u12345))")
+            else {
+                panic!("expected the contract to be rejected");
+            };
+            let diagnostic = diagnostics.last().unwrap();
+            assert!(
+                diagnostic.message.contains("(string-ascii 3)")
+                    && diagnostic.message.contains("(string-ascii 40)"),
+                "{}",
+                diagnostic.message
+            );
+
+            crosscheck(
+                "(define-data-var s (string-ascii 40) (int-to-ascii u12345)) (var-get s)",
+                Ok(Some(Value::Sequence(SequenceData::String(
+                    CharType::ASCII(ASCIIData {
+                        data: "12345".bytes().collect(),
+                    }),
+                )))),
+            );
+        }
+
+        #[test]
+        fn int_to_ascii_constant_is_folded() {
+            let (module, calls) = top_level_calls("(int-to-ascii 12345)");
+            let int_to_string = module.funcs.by_name("stdlib.int-to-string").unwrap();
+            assert!(!calls.contains(&int_to_string));
+            assert!(module.data.iter().any(|data| data.value == b"12345"));
+
+            let (module, calls) = top_level_calls("(let ((n 12345)) (int-to-ascii n))");
+            let int_to_string = module.funcs.by_name("stdlib.int-to-string").unwrap();
+            assert!(calls.contains(&int_to_string));
+        }
     }
 }
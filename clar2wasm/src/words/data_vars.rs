@@ -5,7 +5,7 @@ use walrus::ValType;
 use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{ArgumentsExt, GeneratorError, LiteralMemoryEntry, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct DefineDataVar;
@@ -17,6 +17,10 @@ impl Word for DefineDataVar {
 }
 
 impl ComplexWord for DefineDataVar {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -80,9 +84,7 @@ impl ComplexWord for DefineDataVar {
         // Call the host interface function, `define_variable`
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.define_variable")
+                .try_func_by_name("stdlib.define_variable")
                 .ok_or_else(|| {
                     GeneratorError::InternalError("stdlib.define_variable not found".to_owned())
                 })?,
@@ -109,6 +111,10 @@ impl Word for SetDataVar {
 }
 
 impl ComplexWord for SetDataVar {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -143,7 +149,7 @@ impl ComplexWord for SetDataVar {
         let id_length = name.len();
 
         // Create space on the call stack to write the value
-        let (offset, size) = generator.create_call_stack_local(builder, &ty, true, false);
+        let (offset, size) = generator.create_call_stack_local(builder, &ty, true, false)?;
 
         // Write the value to the memory, to be read by the host
         generator.write_to_memory(builder, offset, 0, &ty)?;
@@ -159,9 +165,7 @@ impl ComplexWord for SetDataVar {
         // Call the host interface function, `set_variable`
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.set_variable")
+                .try_func_by_name("stdlib.set_variable")
                 .ok_or_else(|| {
                     GeneratorError::InternalError("stdlib.set_variable not found".to_owned())
                 })?,
@@ -184,6 +188,10 @@ impl Word for GetDataVar {
 }
 
 impl ComplexWord for GetDataVar {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -209,7 +217,7 @@ impl ComplexWord for GetDataVar {
                 GeneratorError::TypeError("var-get expression must be typed".to_owned())
             })?
             .clone();
-        let (offset, size) = generator.create_call_stack_local(builder, &ty, true, true);
+        let (offset, size) = generator.create_call_stack_local(builder, &ty, true, true)?;
 
         // Push the identifier offset and length onto the data stack
         builder
@@ -222,9 +230,7 @@ impl ComplexWord for GetDataVar {
         // Call the host interface function, `get_variable`
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.get_variable")
+                .try_func_by_name("stdlib.get_variable")
                 .ok_or_else(|| {
                     GeneratorError::InternalError("stdlib.get_variable not found".to_owned())
                 })?,
@@ -241,10 +247,12 @@ impl ComplexWord for GetDataVar {
 #[cfg(test)]
 mod tests {
     use clarity::vm::errors::{CheckErrors, Error};
+    use clarity::vm::types::PrincipalData;
     use clarity::vm::Value;
 
     use crate::tools::{
-        crosscheck, crosscheck_expect_failure, crosscheck_with_clarity_version, evaluate,
+        crosscheck, crosscheck_expect_failure, crosscheck_with_clarity_version,
+        crosscheck_with_state, evaluate, TestEnvironment,
     };
 
     //
@@ -362,6 +370,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn var_get_tuple_default_value() {
+        crosscheck(
+            "
+(define-data-var v {a: uint} {a: u7})
+(var-get v)",
+            evaluate("{a: u7}"),
+        );
+    }
+
     #[test]
     fn validate_define_data_var() {
         // Reserved keyword
@@ -389,4 +407,45 @@ mod tests {
             clarity::vm::ClarityVersion::Clarity1,
         );
     }
+
+    #[test]
+    fn var_set_leaves_matching_state() {
+        let snippet = "(define-data-var x uint u0) (var-set x u5)";
+        crosscheck_with_state(snippet, Ok(Some(Value::Bool(true))));
+
+        // The snapshot does hold the variable.
+        let mut env = TestEnvironment::default();
+        let before = env.data_snapshot();
+        env.evaluate(snippet).unwrap();
+        let after = env.data_snapshot();
+        assert!(after
+            .keys()
+            .any(|key| key.ends_with("::x") && !before.contains_key(key)));
+    }
+
+    #[test]
+    fn var_get_standard_principal() {
+        crosscheck(
+            "
+(define-data-var owner principal 'ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM)
+(var-get owner)",
+            Ok(Some(Value::Principal(
+                PrincipalData::parse("ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM").unwrap(),
+            ))),
+        );
+    }
+
+    #[test]
+    fn var_get_contract_principal() {
+        crosscheck(
+            "
+(define-data-var owner principal 'ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM)
+(var-set owner 'ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM.my-contract)
+(var-get owner)",
+            Ok(Some(Value::Principal(
+                PrincipalData::parse("ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM.my-contract")
+                    .unwrap(),
+            ))),
+        );
+    }
 }
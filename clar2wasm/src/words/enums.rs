@@ -6,7 +6,7 @@ use crate::check_args;
 use crate::wasm_generator::{
     add_placeholder_for_type, clar2wasm_ty, ArgumentsExt, GeneratorError, WasmGenerator,
 };
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct ClaritySome;
@@ -18,6 +18,10 @@ impl Word for ClaritySome {
 }
 
 impl ComplexWord for ClaritySome {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -57,6 +61,10 @@ impl Word for ClarityOk {
 }
 
 impl ComplexWord for ClarityOk {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -106,6 +114,10 @@ impl Word for ClarityErr {
 }
 
 impl ComplexWord for ClarityErr {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -140,7 +152,27 @@ impl ComplexWord for ClarityErr {
 
 #[cfg(test)]
 mod tests {
-    use crate::tools::evaluate;
+    use clarity::vm::Value;
+
+    use crate::tools::{crosscheck, evaluate};
+
+    #[test]
+    fn some_round_trip() {
+        crosscheck("(unwrap-panic (some u1))", Ok(Some(Value::UInt(1))));
+        crosscheck("(match (some u1) v (+ v u1) u0)", Ok(Some(Value::UInt(2))));
+    }
+
+    #[test]
+    fn ok_round_trip() {
+        crosscheck("(unwrap-panic (ok 3))", Ok(Some(Value::Int(3))));
+        crosscheck("(match (ok 3) v (+ v 1) e 0)", Ok(Some(Value::Int(4))));
+    }
+
+    #[test]
+    fn err_round_trip() {
+        crosscheck("(unwrap-err-panic (err u7))", Ok(Some(Value::UInt(7))));
+        crosscheck("(match (err u7) v u0 e (+ e u1))", Ok(Some(Value::UInt(8))));
+    }
 
     #[test]
     fn some_less_than_one_arg() {
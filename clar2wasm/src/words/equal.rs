@@ -1,6 +1,8 @@
 use clarity::vm::types::signatures::CallableSubtype;
-use clarity::vm::types::{SequenceSubtype, StringSubtype, TupleTypeSignature, TypeSignature};
-use clarity::vm::{ClarityName, SymbolicExpression};
+use clarity::vm::types::{
+    SequenceData, SequenceSubtype, StringSubtype, TupleTypeSignature, TypeSignature,
+};
+use clarity::vm::{ClarityName, SymbolicExpression, SymbolicExpressionType, Value};
 use walrus::ir::{BinaryOp, IfElse, InstrSeqType, Loop, UnaryOp};
 use walrus::{InstrSeqBuilder, LocalId, ValType};
 
@@ -9,7 +11,7 @@ use crate::check_args;
 use crate::wasm_generator::{
     clar2wasm_ty, drop_value, ArgumentsExt, GeneratorError, SequenceElementType, WasmGenerator,
 };
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct IsEq;
@@ -21,6 +23,10 @@ impl Word for IsEq {
 }
 
 impl ComplexWord for IsEq {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -36,6 +42,12 @@ impl ComplexWord for IsEq {
             ArgumentCountCheck::AtLeast
         );
 
+        // Literal operands are compared at compile time.
+        if let Some(equal) = constant_is_eq(args) {
+            builder.i32_const(equal as i32);
+            return Ok(());
+        }
+
         // Traverse the first operand pushing it onto the stack
         let first_op = args.get_expr(0)?;
         generator.traverse_expr(builder, first_op)?;
@@ -104,6 +116,22 @@ impl ComplexWord for IsEq {
     }
 }
 
+/// Returns the result of `is-eq` if all its operands are integer or buffer
+/// literals.
+fn constant_is_eq(args: &[SymbolicExpression]) -> Option<bool> {
+    let values = args
+        .iter()
+        .map(|arg| match &arg.expr {
+            SymbolicExpressionType::LiteralValue(
+                value @ (Value::Int(_) | Value::UInt(_) | Value::Sequence(SequenceData::Buffer(_))),
+            ) => Some(value),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let (first, rest) = values.split_first()?;
+    (!rest.is_empty()).then(|| rest.iter().all(|value| value == first))
+}
+
 #[derive(Debug)]
 pub enum IndexOf {
     Original,
@@ -120,6 +148,10 @@ impl Word for IndexOf {
 }
 
 impl ComplexWord for IndexOf {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -392,6 +424,9 @@ fn assign_first_operand_to_locals(
     assign_to_locals(builder, ty, ty, locals)
 }
 
+/// Compares the values of types `ty` and `nth_ty` held in `first_op` and
+/// `nth_op`, recursing into the optionals, responses, tuples and lists, and
+/// pushes the boolean result. Shared by `is-eq` and `index-of?`.
 fn wasm_equal(
     ty: &TypeSignature,
     nth_ty: &TypeSignature,
@@ -1024,7 +1059,25 @@ mod tests {
     use clarity::vm::types::{ListData, ListTypeData, SequenceData};
     use clarity::vm::Value;
 
-    use crate::tools::{crosscheck, evaluate, TestEnvironment};
+    use crate::tools::{crosscheck, evaluate, top_level_calls, TestEnvironment};
+
+    #[test]
+    fn is_eq_literals_fold() {
+        crosscheck("(is-eq u1 u1)", Ok(Some(Value::Bool(true))));
+        crosscheck("(is-eq 1 2)", Ok(Some(Value::Bool(false))));
+        crosscheck("(is-eq 0x01 0x02)", Ok(Some(Value::Bool(false))));
+        crosscheck("(is-eq 0x0102 0x0102 0x0102)", Ok(Some(Value::Bool(true))));
+
+        assert!(top_level_calls("(is-eq u1 u1)").1.is_empty());
+        assert!(top_level_calls("(is-eq 0x01 0x02)").1.is_empty());
+    }
+
+    #[test]
+    fn is_eq_variables_compared_at_runtime() {
+        let snippet = "(let ((a 0x01)) (is-eq a 0x02))";
+        crosscheck(snippet, Ok(Some(Value::Bool(false))));
+        assert!(!top_level_calls(snippet).1.is_empty());
+    }
 
     #[test]
     fn is_eq_less_than_one_arg() {
@@ -1269,6 +1322,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn is_eq_nested_tuple_of_list_of_tuples() {
+        const NESTED: &str = "(define-constant x { a: (list { b: 1 } { b: 2 }) })";
+        crosscheck(
+            &format!("{NESTED} (is-eq x {{ a: (list {{ b: 1 }} {{ b: 2 }}) }})"),
+            Ok(Some(Value::Bool(true))),
+        );
+        crosscheck(
+            &format!("{NESTED} (is-eq x {{ a: (list {{ b: 1 }} {{ b: 3 }}) }})"),
+            Ok(Some(Value::Bool(false))),
+        );
+        crosscheck(
+            &format!("{NESTED} (is-eq x {{ a: (list {{ b: 1 }}) }})"),
+            Ok(Some(Value::Bool(false))),
+        );
+    }
+
+    #[test]
+    fn index_of_nested_tuple_of_list_of_tuples() {
+        const NESTED: &str =
+            "(define-constant l (list { a: (list { b: 1 }) } { a: (list { b: 1 } { b: 2 }) }))";
+        crosscheck(
+            &format!("{NESTED} (index-of l {{ a: (list {{ b: 1 }} {{ b: 2 }}) }})"),
+            evaluate("(some u1)"),
+        );
+        crosscheck(
+            &format!("{NESTED} (index-of l {{ a: (list {{ b: 2 }}) }})"),
+            evaluate("none"),
+        );
+    }
+
     #[test]
     fn index_of_tuple_complex_type() {
         crosscheck("(index-of (list (tuple (id 42) (name \"Clarity\")) (tuple (id 133) (name \"Wasm\"))) (tuple (id 42) (name \"Wasm\")))",
@@ -1330,6 +1414,14 @@ mod tests {
         use super::*;
         use crate::tools::crosscheck;
 
+        #[test]
+        fn index_of_alias_empty_data_var() {
+            crosscheck(
+                "(define-data-var l (list 4 int) (list)) (index-of? (var-get l) 1)",
+                Ok(Some(Value::none())),
+            );
+        }
+
         #[test]
         fn index_of_alias_list_zero_len() {
             let mut env = TestEnvironment::default();
@@ -7,7 +7,7 @@ use crate::error_mapping::ErrorMap;
 use crate::wasm_generator::{
     get_global, ArgumentsExt, FunctionKind, GeneratorError, LiteralMemoryEntry, WasmGenerator,
 };
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct DefinePrivateFunction;
@@ -19,6 +19,10 @@ impl Word for DefinePrivateFunction {
 }
 
 impl ComplexWord for DefinePrivateFunction {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -57,6 +61,10 @@ impl Word for DefineReadonlyFunction {
 }
 
 impl ComplexWord for DefineReadonlyFunction {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -118,6 +126,10 @@ impl Word for DefinePublicFunction {
 }
 
 impl ComplexWord for DefinePublicFunction {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -150,12 +162,15 @@ impl ComplexWord for DefinePublicFunction {
 
 #[cfg(test)]
 mod tests {
+    use clarity::types::StacksEpochId;
+    use clarity::vm::diagnostic::Level;
     use clarity::vm::errors::{CheckErrors, Error};
     use clarity::vm::Value;
 
     use crate::tools::{
-        crosscheck, crosscheck_expect_failure, crosscheck_multi_contract, evaluate,
+        compile_snippet, crosscheck, crosscheck_expect_failure, crosscheck_multi_contract, evaluate,
     };
+    use crate::CompileError;
 
     //
     // Module with tests that should only be executed
@@ -490,4 +505,37 @@ mod tests {
             ))),
         )
     }
+
+    #[test]
+    fn read_only_without_side_effects_compiles() {
+        crosscheck(
+            "
+(define-data-var v int 1)
+(define-read-only (get-v) (ok (var-get v)))
+(get-v)
+",
+            Ok(Some(Value::okay(Value::Int(1)).unwrap())),
+        );
+    }
+
+    #[test]
+    fn read_only_with_var_set_is_rejected() {
+        // The read-only checker runs before code generation, as for the
+        // writes in the closure of `at-block`.
+        let Err(CompileError::Generic { diagnostics, .. }) = compile_snippet(
+            "
+(define-data-var v int 1)
+(define-read-only (set-v) (begin (var-set v 2) (ok true)))
+",
+        ) else {
+            panic!("expected var-set in a read-only function to be rejected");
+        };
+        let diagnostic = diagnostics.last().unwrap();
+        assert!(matches!(diagnostic.level, Level::Error));
+        assert!(
+            diagnostic.message.contains("read-only"),
+            "{}",
+            diagnostic.message
+        );
+    }
 }
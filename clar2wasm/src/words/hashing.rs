@@ -3,6 +3,7 @@ use clarity::vm::ClarityName;
 
 use super::{SimpleWord, Word};
 use crate::wasm_generator::{GeneratorError, WasmGenerator};
+use crate::wasm_utils::Arity;
 
 pub fn traverse_hash(
     name: &'static str,
@@ -23,7 +24,7 @@ pub fn traverse_hash(
     ));
 
     // Allocate space on the stack for the result
-    let (result_local, _) = generator.create_call_stack_local(builder, &return_ty, false, true);
+    let (result_local, _) = generator.create_call_stack_local(builder, &return_ty, false, true)?;
 
     let hash_type = match arg_types[0] {
         TypeSignature::IntType | TypeSignature::UIntType => {
@@ -41,9 +42,7 @@ pub fn traverse_hash(
     };
 
     let hash_func = generator
-        .module
-        .funcs
-        .by_name(&format!("stdlib.{name}-{hash_type}"))
+        .try_func_by_name(&format!("stdlib.{name}-{hash_type}"))
         .ok_or_else(|| {
             GeneratorError::InternalError(format!("function not found: {name}-{hash_type}"))
         })?;
@@ -63,6 +62,10 @@ impl Word for Hash160 {
 }
 
 impl SimpleWord for Hash160 {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -85,6 +88,10 @@ impl Word for Sha256 {
 }
 
 impl SimpleWord for Sha256 {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -107,6 +114,10 @@ impl Word for Keccak256 {
 }
 
 impl SimpleWord for Keccak256 {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -119,7 +130,7 @@ impl SimpleWord for Keccak256 {
             TypeSignature::IntType | TypeSignature::UIntType => {
                 // Convert integers to buffers by storing them to memory
                 let (buffer_local, size) =
-                    generator.create_call_stack_local(builder, ty, false, true);
+                    generator.create_call_stack_local(builder, ty, false, true)?;
                 generator.write_to_memory(builder, buffer_local, 0, ty)?;
 
                 // Then load the offset and length onto the stack
@@ -136,15 +147,13 @@ impl SimpleWord for Keccak256 {
         // Reserve stack space for the host-function to write the result
         let ret_ty = BUFF_32.clone();
         let (result_local, result_size) =
-            generator.create_call_stack_local(builder, &ret_ty, false, true);
+            generator.create_call_stack_local(builder, &ret_ty, false, true)?;
         builder.local_get(result_local).i32_const(result_size);
 
         // Call the host interface function, `keccak256`
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.keccak256")
+                .try_func_by_name("stdlib.keccak256")
                 .ok_or_else(|| {
                     GeneratorError::InternalError("stdlib.keccak256 not found".to_owned())
                 })?,
@@ -164,6 +173,10 @@ impl Word for Sha512 {
 }
 
 impl SimpleWord for Sha512 {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -186,6 +199,10 @@ impl Word for Sha512_256 {
 }
 
 impl SimpleWord for Sha512_256 {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -198,7 +215,7 @@ impl SimpleWord for Sha512_256 {
             TypeSignature::IntType | TypeSignature::UIntType => {
                 // Convert integers to buffers by storing them to memory
                 let (buffer_local, size) =
-                    generator.create_call_stack_local(builder, ty, false, true);
+                    generator.create_call_stack_local(builder, ty, false, true)?;
                 generator.write_to_memory(builder, buffer_local, 0, ty)?;
 
                 // Then load the offset and length onto the stack
@@ -215,15 +232,13 @@ impl SimpleWord for Sha512_256 {
         // Reserve stack space for the host-function to write the result
         let ret_ty = BUFF_32.clone();
         let (result_local, result_size) =
-            generator.create_call_stack_local(builder, &ret_ty, false, true);
+            generator.create_call_stack_local(builder, &ret_ty, false, true)?;
         builder.local_get(result_local).i32_const(result_size);
 
         // Call the host interface function, `sha512_256`
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.sha512_256")
+                .try_func_by_name("stdlib.sha512_256")
                 .ok_or_else(|| {
                     GeneratorError::InternalError("stdlib.sha512_256 not found".to_owned())
                 })?,
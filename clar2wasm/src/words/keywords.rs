@@ -0,0 +1,418 @@
+use clarity::types::StacksEpochId;
+use clarity::vm::{ClarityName, ClarityVersion, SymbolicExpression};
+
+use super::{Keyword, Word};
+use crate::wasm_generator::{GeneratorError, WasmGenerator};
+
+#[derive(Debug)]
+pub struct BlockHeight;
+
+impl Word for BlockHeight {
+    fn name(&self) -> ClarityName {
+        "block-height".into()
+    }
+}
+
+impl Keyword for BlockHeight {
+    fn visit(
+        &self,
+        generator: &mut WasmGenerator,
+        builder: &mut walrus::InstrSeqBuilder,
+        expr: &SymbolicExpression,
+    ) -> Result<(), GeneratorError> {
+        if generator.contract_analysis.epoch >= StacksEpochId::Epoch30 {
+            generator.warn(
+                expr,
+                "block-height is deprecated, use stacks-block-height or tenure-height".to_owned(),
+            );
+        }
+        // Call the host interface function, `block_height`
+        builder.call(generator.func_by_name("stdlib.block_height"));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct BurnBlockHeight;
+
+impl Word for BurnBlockHeight {
+    fn name(&self) -> ClarityName {
+        "burn-block-height".into()
+    }
+}
+
+impl Keyword for BurnBlockHeight {
+    fn visit(
+        &self,
+        generator: &mut WasmGenerator,
+        builder: &mut walrus::InstrSeqBuilder,
+        _expr: &SymbolicExpression,
+    ) -> Result<(), GeneratorError> {
+        // Call the host interface function, `burn_block_height`
+        builder.call(generator.func_by_name("stdlib.burn_block_height"));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct StacksBlockHeight;
+
+impl Word for StacksBlockHeight {
+    fn name(&self) -> ClarityName {
+        "stacks-block-height".into()
+    }
+}
+
+impl Keyword for StacksBlockHeight {
+    fn visit(
+        &self,
+        generator: &mut WasmGenerator,
+        builder: &mut walrus::InstrSeqBuilder,
+        _expr: &SymbolicExpression,
+    ) -> Result<(), GeneratorError> {
+        // `stacks-block-height` only exists since Clarity 3, older contracts use
+        // `block-height`.
+        if generator.contract_analysis.clarity_version < ClarityVersion::Clarity3 {
+            return Err(GeneratorError::TypeError(
+                "stacks-block-height is only available since Clarity 3".to_owned(),
+            ));
+        }
+        // Call the host interface function, `stacks_block_height`
+        builder.call(generator.func_by_name("stdlib.stacks_block_height"));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct TenureHeight;
+
+impl Word for TenureHeight {
+    fn name(&self) -> ClarityName {
+        "tenure-height".into()
+    }
+}
+
+impl Keyword for TenureHeight {
+    fn visit(
+        &self,
+        generator: &mut WasmGenerator,
+        builder: &mut walrus::InstrSeqBuilder,
+        _expr: &SymbolicExpression,
+    ) -> Result<(), GeneratorError> {
+        // Call the host interface function, `tenure_height`
+        builder.call(generator.func_by_name("stdlib.tenure_height"));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ChainId;
+
+impl Word for ChainId {
+    fn name(&self) -> ClarityName {
+        "chain-id".into()
+    }
+}
+
+impl Keyword for ChainId {
+    fn visit(
+        &self,
+        generator: &mut WasmGenerator,
+        builder: &mut walrus::InstrSeqBuilder,
+        _expr: &SymbolicExpression,
+    ) -> Result<(), GeneratorError> {
+        // Call the host interface function, `chain_id`
+        builder.call(generator.func_by_name("stdlib.chain_id"));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct StxLiquidSupply;
+
+impl Word for StxLiquidSupply {
+    fn name(&self) -> ClarityName {
+        "stx-liquid-supply".into()
+    }
+}
+
+impl Keyword for StxLiquidSupply {
+    fn visit(
+        &self,
+        generator: &mut WasmGenerator,
+        builder: &mut walrus::InstrSeqBuilder,
+        _expr: &SymbolicExpression,
+    ) -> Result<(), GeneratorError> {
+        // Call the host interface function, `stx_liquid_supply`
+        builder.call(generator.func_by_name("stdlib.stx_liquid_supply"));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct IsInRegtest;
+
+impl Word for IsInRegtest {
+    fn name(&self) -> ClarityName {
+        "is-in-regtest".into()
+    }
+}
+
+impl Keyword for IsInRegtest {
+    fn visit(
+        &self,
+        generator: &mut WasmGenerator,
+        builder: &mut walrus::InstrSeqBuilder,
+        _expr: &SymbolicExpression,
+    ) -> Result<(), GeneratorError> {
+        // Call the host interface function, `is_in_regtest`
+        builder.call(generator.func_by_name("stdlib.is_in_regtest"));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct IsInMainnet;
+
+impl Word for IsInMainnet {
+    fn name(&self) -> ClarityName {
+        "is-in-mainnet".into()
+    }
+}
+
+impl Keyword for IsInMainnet {
+    fn visit(
+        &self,
+        generator: &mut WasmGenerator,
+        builder: &mut walrus::InstrSeqBuilder,
+        _expr: &SymbolicExpression,
+    ) -> Result<(), GeneratorError> {
+        // Call the host interface function, `is_in_mainnet`
+        builder.call(generator.func_by_name("stdlib.is_in_mainnet"));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clarity::types::StacksEpochId;
+    use clarity::vm::diagnostic::Level;
+    use clarity::vm::{ClarityVersion, SymbolicExpression};
+    use walrus::FunctionBuilder;
+
+    use super::StacksBlockHeight;
+    use crate::tools::{
+        compile_snippet_at, crosscheck_compare_only_with_epoch_and_version, evaluate_at,
+    };
+    use crate::wasm_generator::WasmGenerator;
+    use crate::words::Keyword;
+
+    /// An epoch for each Clarity version.
+    const EPOCHS: [(StacksEpochId, ClarityVersion); 3] = [
+        (StacksEpochId::Epoch2_05, ClarityVersion::Clarity1),
+        (StacksEpochId::Epoch24, ClarityVersion::Clarity2),
+        (StacksEpochId::Epoch30, ClarityVersion::Clarity3),
+    ];
+
+    /// Crosschecks `keyword` for each Clarity version in `since..=until`.
+    fn crosscheck_keyword(keyword: &str, since: ClarityVersion, until: ClarityVersion) {
+        let snippet = format!("(define-read-only (get-keyword) {keyword}) (get-keyword)");
+        for (epoch, version) in EPOCHS
+            .into_iter()
+            .filter(|(_, version)| (since..=until).contains(version))
+        {
+            crosscheck_compare_only_with_epoch_and_version(&snippet, epoch, version);
+        }
+    }
+
+    #[test]
+    fn block_height() {
+        crosscheck_keyword(
+            "block-height",
+            ClarityVersion::Clarity1,
+            ClarityVersion::Clarity2,
+        );
+    }
+
+    #[test]
+    fn block_height_warns_in_epoch_3() {
+        let result = compile_snippet_at(
+            "block-height",
+            StacksEpochId::Epoch30,
+            ClarityVersion::Clarity2,
+        )
+        .unwrap();
+        assert!(result.diagnostics.iter().any(|d| {
+            matches!(d.level, Level::Warning) && d.message.contains("block-height is deprecated")
+        }));
+
+        // It still works.
+        crosscheck_compare_only_with_epoch_and_version(
+            "block-height",
+            StacksEpochId::Epoch30,
+            ClarityVersion::Clarity2,
+        );
+    }
+
+    #[test]
+    fn block_height_does_not_warn_before_epoch_3() {
+        let result = compile_snippet_at(
+            "block-height",
+            StacksEpochId::Epoch25,
+            ClarityVersion::Clarity2,
+        )
+        .unwrap();
+        assert!(result
+            .diagnostics
+            .iter()
+            .all(|d| !matches!(d.level, Level::Warning)));
+    }
+
+    #[test]
+    fn stacks_block_height_fails_in_clarity_2() {
+        assert!(evaluate_at(
+            "stacks-block-height",
+            StacksEpochId::Epoch30,
+            ClarityVersion::Clarity2
+        )
+        .is_err());
+
+        // The handler itself refuses to generate code for older contracts.
+        let contract_analysis =
+            compile_snippet_at("(+ 1 2)", StacksEpochId::Epoch30, ClarityVersion::Clarity2)
+                .unwrap()
+                .contract_analysis;
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        assert!(StacksBlockHeight
+            .visit(
+                &mut generator,
+                &mut func.func_body(),
+                &SymbolicExpression::atom("stacks-block-height".into()),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn burn_block_height() {
+        crosscheck_keyword(
+            "burn-block-height",
+            ClarityVersion::Clarity1,
+            ClarityVersion::Clarity3,
+        );
+    }
+
+    #[test]
+    fn stacks_block_height() {
+        crosscheck_keyword(
+            "stacks-block-height",
+            ClarityVersion::Clarity3,
+            ClarityVersion::Clarity3,
+        );
+    }
+
+    #[test]
+    fn tenure_height() {
+        crosscheck_keyword(
+            "tenure-height",
+            ClarityVersion::Clarity3,
+            ClarityVersion::Clarity3,
+        );
+    }
+
+    #[test]
+    fn chain_id() {
+        crosscheck_keyword(
+            "chain-id",
+            ClarityVersion::Clarity2,
+            ClarityVersion::Clarity3,
+        );
+    }
+
+    #[test]
+    fn stx_liquid_supply() {
+        crosscheck_keyword(
+            "stx-liquid-supply",
+            ClarityVersion::Clarity1,
+            ClarityVersion::Clarity3,
+        );
+    }
+
+    #[test]
+    fn is_in_regtest() {
+        crosscheck_keyword(
+            "is-in-regtest",
+            ClarityVersion::Clarity1,
+            ClarityVersion::Clarity3,
+        );
+    }
+
+    #[test]
+    fn is_in_mainnet() {
+        crosscheck_keyword(
+            "is-in-mainnet",
+            ClarityVersion::Clarity2,
+            ClarityVersion::Clarity3,
+        );
+    }
+
+    #[cfg(any(feature = "test-clarity-v1", feature = "test-clarity-v2"))]
+    #[test]
+    fn test_block_height() {
+        use crate::tools::{crosscheck_with_epoch, evaluate};
+
+        let snpt = "
+            (define-public (block)
+            (ok block-height))
+
+            (define-public (burn-block)
+            (ok burn-block-height))
+        ";
+
+        crosscheck_with_epoch(
+            &format!("{snpt} (block)"),
+            evaluate("(ok u0)"),
+            StacksEpochId::Epoch24,
+        );
+        crosscheck_with_epoch(
+            &format!("{snpt} (burn-block)"),
+            evaluate("(ok u0)"),
+            StacksEpochId::Epoch24,
+        );
+    }
+
+    #[cfg(not(feature = "test-clarity-v1"))]
+    #[test]
+    fn test_chain_id() {
+        use crate::tools::{crosscheck, evaluate};
+
+        crosscheck(
+            "
+(define-public (get-chain-id)
+  (ok chain-id))
+
+(get-chain-id)
+",
+            evaluate("(ok u2147483648)"),
+        );
+    }
+
+    #[cfg(not(feature = "test-clarity-v1"))]
+    #[test]
+    fn chain_id_across_epochs() {
+        use clarity::vm::Value;
+
+        use crate::tools::crosscheck_all_epochs;
+
+        // The test environment always runs on the testnet.
+        crosscheck_all_epochs(
+            "chain-id",
+            &[
+                (StacksEpochId::Epoch21, Ok(Some(Value::UInt(0x8000_0000)))),
+                (StacksEpochId::Epoch24, Ok(Some(Value::UInt(0x8000_0000)))),
+                (StacksEpochId::Epoch25, Ok(Some(Value::UInt(0x8000_0000)))),
+                (StacksEpochId::Epoch30, Ok(Some(Value::UInt(0x8000_0000)))),
+            ],
+        );
+    }
+}
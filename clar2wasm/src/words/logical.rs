@@ -3,6 +3,7 @@ use clarity::vm::ClarityName;
 
 use super::{SimpleWord, Word};
 use crate::wasm_generator::{GeneratorError, WasmGenerator};
+use crate::wasm_utils::Arity;
 
 #[derive(Debug)]
 pub struct Not;
@@ -14,6 +15,10 @@ impl Word for Not {
 }
 
 impl SimpleWord for Not {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -4,7 +4,7 @@ use clarity::vm::{ClarityName, SymbolicExpression};
 use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{ArgumentsExt, GeneratorError, LiteralMemoryEntry, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct MapDefinition;
@@ -16,6 +16,10 @@ impl Word for MapDefinition {
 }
 
 impl ComplexWord for MapDefinition {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -53,9 +57,7 @@ impl ComplexWord for MapDefinition {
 
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.define_map")
+                .try_func_by_name("stdlib.define_map")
                 .ok_or_else(|| {
                     GeneratorError::InternalError("stdlib.define_map not found".to_owned())
                 })?,
@@ -80,6 +82,10 @@ impl Word for MapGet {
 }
 
 impl ComplexWord for MapGet {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -116,7 +122,8 @@ impl ComplexWord for MapGet {
                 GeneratorError::TypeError("map-set value expression must be typed".to_owned())
             })?
             .clone();
-        let (key_offset, key_size) = generator.create_call_stack_local(builder, &ty, true, false);
+        let (key_offset, key_size) =
+            generator.create_call_stack_local(builder, &ty, true, false)?;
 
         // Push the key to the data stack
         generator.traverse_expr(builder, key)?;
@@ -135,7 +142,7 @@ impl ComplexWord for MapGet {
             })?
             .clone();
         let (return_offset, return_size) =
-            generator.create_call_stack_local(builder, &ty, true, true);
+            generator.create_call_stack_local(builder, &ty, true, true)?;
 
         // Push the return value offset and size to the data stack
         builder.local_get(return_offset).i32_const(return_size);
@@ -161,6 +168,10 @@ impl Word for MapSet {
 }
 
 impl ComplexWord for MapSet {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -199,7 +210,8 @@ impl ComplexWord for MapSet {
                 GeneratorError::TypeError("map-set value expression must be typed".to_owned())
             })?
             .clone();
-        let (key_offset, key_size) = generator.create_call_stack_local(builder, &ty, true, false);
+        let (key_offset, key_size) =
+            generator.create_call_stack_local(builder, &ty, true, false)?;
 
         // Push the key to the data stack
         generator.traverse_expr(builder, key)?;
@@ -217,7 +229,8 @@ impl ComplexWord for MapSet {
                 GeneratorError::TypeError("map-set value expression must be typed".to_owned())
             })?
             .clone();
-        let (val_offset, val_size) = generator.create_call_stack_local(builder, &ty, true, false);
+        let (val_offset, val_size) =
+            generator.create_call_stack_local(builder, &ty, true, false)?;
 
         // Push the value to the data stack
         generator.traverse_expr(builder, value)?;
@@ -245,6 +258,10 @@ impl Word for MapInsert {
 }
 
 impl ComplexWord for MapInsert {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -283,7 +300,8 @@ impl ComplexWord for MapInsert {
                 GeneratorError::TypeError("map-set value expression must be typed".to_owned())
             })?
             .clone();
-        let (key_offset, key_size) = generator.create_call_stack_local(builder, &ty, true, false);
+        let (key_offset, key_size) =
+            generator.create_call_stack_local(builder, &ty, true, false)?;
 
         // Push the key to the data stack
         generator.traverse_expr(builder, key)?;
@@ -301,7 +319,8 @@ impl ComplexWord for MapInsert {
                 GeneratorError::TypeError("map-set value expression must be typed".to_owned())
             })?
             .clone();
-        let (val_offset, val_size) = generator.create_call_stack_local(builder, &ty, true, false);
+        let (val_offset, val_size) =
+            generator.create_call_stack_local(builder, &ty, true, false)?;
 
         // Push the value to the data stack
         generator.traverse_expr(builder, value)?;
@@ -329,6 +348,10 @@ impl Word for MapDelete {
 }
 
 impl ComplexWord for MapDelete {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -366,7 +389,8 @@ impl ComplexWord for MapDelete {
                 GeneratorError::TypeError("map-set value expression must be typed".to_owned())
             })?
             .clone();
-        let (key_offset, key_size) = generator.create_call_stack_local(builder, &ty, true, false);
+        let (key_offset, key_size) =
+            generator.create_call_stack_local(builder, &ty, true, false)?;
 
         // Push the key to the data stack
         generator.traverse_expr(builder, key)?;
@@ -380,9 +404,7 @@ impl ComplexWord for MapDelete {
         // Call the host interface function, `map_delete`
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.map_delete")
+                .try_func_by_name("stdlib.map_delete")
                 .ok_or_else(|| {
                     GeneratorError::TypeError("stdlib.map_delete not found".to_owned())
                 })?,
@@ -399,7 +421,7 @@ mod tests {
     use clarity::vm::errors::{CheckErrors, Error};
     use clarity::vm::Value;
 
-    use crate::tools::{crosscheck, crosscheck_expect_failure, evaluate};
+    use crate::tools::{crosscheck, crosscheck_compare_only, crosscheck_expect_failure, evaluate};
 
     //
     // Module with tests that should only be executed
@@ -450,6 +472,45 @@ mod tests {
         crosscheck("(define-map approved-contracts principal bool) (map-insert approved-contracts tx-sender true) (map-get? approved-contracts tx-sender)", Ok(Some(Value::some(Value::Bool(true)).unwrap())));
     }
 
+    #[test]
+    fn map_set_existing_key_returns_true() {
+        crosscheck(
+            "(define-map m int int) (map-set m 1 1) (map-set m 1 2)",
+            Ok(Some(Value::Bool(true))),
+        );
+    }
+
+    #[test]
+    fn map_insert_existing_key_returns_false() {
+        crosscheck(
+            "(define-map m int int) (map-insert m 1 1) (map-insert m 1 2)",
+            Ok(Some(Value::Bool(false))),
+        );
+    }
+
+    #[test]
+    fn map_key_field_order() {
+        crosscheck(
+            "(define-map m {a: int, b: int} int) (map-set m {b: 2, a: 1} 3) (map-get? m {a: 1, b: 2})",
+            Ok(Some(Value::some(Value::Int(3)).unwrap())),
+        );
+    }
+
+    #[test]
+    fn map_complex_value_round_trip() {
+        let snippet = "
+(define-map m {id: uint} {owner: principal, data: (list 4 int)})
+(map-set m {id: u1} {owner: 'ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM, data: (list 1 -2 3)})
+(map-set m {id: u2} {owner: tx-sender, data: (list)})
+(list (map-get? m {id: u1}) (map-get? m {id: u2}) (map-get? m {id: u3}))
+";
+        crosscheck_compare_only(snippet);
+        crosscheck(
+            &format!("{snippet} (get data (unwrap-panic (map-get? m {{id: u1}})))"),
+            evaluate("(list 1 -2 3)"),
+        );
+    }
+
     #[test]
     fn validate_define_map() {
         // Reserved keyword
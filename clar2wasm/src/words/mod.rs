@@ -1,10 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use clarity::vm::functions::define::DefineFunctions;
+use clarity::vm::functions::NativeFunctions;
 use clarity::vm::types::TypeSignature;
-use clarity::vm::{ClarityName, SymbolicExpression};
+use clarity::vm::variables::NativeVariables;
+use clarity::vm::{ClarityName, ClarityVersion, SymbolicExpression};
 use lazy_static::lazy_static;
 use walrus::InstrSeqBuilder;
 
+use crate::wasm_utils::Arity;
 use crate::{GeneratorError, WasmGenerator};
 
 pub mod arithmetic;
@@ -25,6 +29,7 @@ pub mod enums;
 pub mod equal;
 pub mod functions;
 pub mod hashing;
+pub mod keywords;
 pub mod logical;
 pub mod maps;
 pub mod noop;
@@ -44,6 +49,10 @@ pub trait Word: Sync + core::fmt::Debug {
 }
 
 pub trait ComplexWord: Word {
+    /// The number of arguments the word accepts. Missing arguments are
+    /// rejected before the word is traversed, see [`Arity::check_minimum`].
+    fn arity(&self) -> Arity;
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -77,6 +86,8 @@ pub(crate) static COMPLEX_WORDS: &[&'static dyn ComplexWord] = &[
     &control_flow::Begin,
     &control_flow::UnwrapErrPanic,
     &control_flow::UnwrapPanic,
+    &conversion::IntToAscii,
+    &conversion::IntToUtf8,
     &data_vars::DefineDataVar,
     &data_vars::GetDataVar,
     &data_vars::SetDataVar,
@@ -138,6 +149,10 @@ pub(crate) static COMPLEX_WORDS: &[&'static dyn ComplexWord] = &[
 ];
 
 pub trait SimpleWord: Word {
+    /// The number of arguments the word accepts, checked before its
+    /// arguments are traversed.
+    fn arity(&self) -> Arity;
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -169,8 +184,6 @@ pub(crate) static SIMPLE_WORDS: &[&'static dyn SimpleWord] = &[
     &comparison::CmpLess,
     &conditionals::SimpleOr,
     &conditionals::SimpleAnd,
-    &conversion::IntToAscii,
-    &conversion::IntToUtf8,
     &conversion::StringToInt,
     &conversion::StringToUint,
     &hashing::Hash160,
@@ -188,6 +201,27 @@ pub(crate) static SIMPLE_WORDS: &[&'static dyn SimpleWord] = &[
     &stx::StxGetBalance,
 ];
 
+/// A Clarity keyword, like `block-height`, whose value is only known at runtime.
+pub trait Keyword: Word {
+    fn visit(
+        &self,
+        generator: &mut WasmGenerator,
+        builder: &mut InstrSeqBuilder,
+        expr: &SymbolicExpression,
+    ) -> Result<(), GeneratorError>;
+}
+
+pub(crate) static KEYWORDS: &[&'static dyn Keyword] = &[
+    &keywords::BlockHeight,
+    &keywords::BurnBlockHeight,
+    &keywords::ChainId,
+    &keywords::IsInMainnet,
+    &keywords::IsInRegtest,
+    &keywords::StacksBlockHeight,
+    &keywords::StxLiquidSupply,
+    &keywords::TenureHeight,
+];
+
 pub(crate) static SIMPLE_VARIADIC_WORDS: &[&'static dyn SimpleWord] = &[
     &arithmetic::Sub,
     &arithmetic::Div,
@@ -195,34 +229,31 @@ pub(crate) static SIMPLE_VARIADIC_WORDS: &[&'static dyn SimpleWord] = &[
     &arithmetic::Mul,
 ];
 
-lazy_static! {
-    static ref COMPLEX_WORDS_BY_NAME: HashMap<ClarityName, &'static dyn ComplexWord> = {
-        let mut cwbn = HashMap::new();
-
-        for word in COMPLEX_WORDS {
-            cwbn.insert(word.name(), &**word);
-        }
-
-        cwbn
-    };
-    static ref SIMPLE_WORDS_BY_NAME: HashMap<ClarityName, &'static dyn SimpleWord> = {
-        let mut swbn = HashMap::new();
-
-        for word in SIMPLE_WORDS {
-            swbn.insert(word.name(), &**word);
-        }
-
-        swbn
-    };
-    static ref SIMPLE_VARIADIC_WORDS_BY_NAME: HashMap<ClarityName, &'static dyn SimpleWord> = {
-        let mut svwbn = HashMap::new();
-
-        for word in SIMPLE_VARIADIC_WORDS {
-            svwbn.insert(word.name(), &**word);
+/// Indexes `words` by name.
+///
+/// # Panics
+///
+/// Panics if two words have the same name, since the later one would
+/// silently shadow the earlier one.
+fn index_by_name<W: Word + ?Sized>(words: &[&'static W]) -> HashMap<ClarityName, &'static W> {
+    let mut by_name = HashMap::new();
+    for word in words {
+        if by_name.insert(word.name(), *word).is_some() {
+            panic!("duplicate word registration: {}", word.name());
         }
+    }
+    by_name
+}
 
-        svwbn
-    };
+lazy_static! {
+    static ref COMPLEX_WORDS_BY_NAME: HashMap<ClarityName, &'static dyn ComplexWord> =
+        index_by_name(COMPLEX_WORDS);
+    static ref SIMPLE_WORDS_BY_NAME: HashMap<ClarityName, &'static dyn SimpleWord> =
+        index_by_name(SIMPLE_WORDS);
+    static ref SIMPLE_VARIADIC_WORDS_BY_NAME: HashMap<ClarityName, &'static dyn SimpleWord> =
+        index_by_name(SIMPLE_VARIADIC_WORDS);
+    static ref KEYWORDS_BY_NAME: HashMap<ClarityName, &'static dyn Keyword> =
+        index_by_name(KEYWORDS);
 }
 
 pub fn lookup_complex(name: &str) -> Option<&'static dyn ComplexWord> {
@@ -237,12 +268,219 @@ pub fn lookup_variadic_simple(name: &str) -> Option<&'static dyn SimpleWord> {
     SIMPLE_VARIADIC_WORDS_BY_NAME.get(name).copied()
 }
 
+pub fn lookup_keyword(name: &str) -> Option<&'static dyn Keyword> {
+    KEYWORDS_BY_NAME.get(name).copied()
+}
+
+/// A Clarity built-in for which the compiler can generate code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupportedWord {
+    pub name: ClarityName,
+    /// The first Clarity version in which the word is available.
+    pub since: ClarityVersion,
+}
+
+const CLARITY_VERSIONS: [ClarityVersion; 3] = [
+    ClarityVersion::Clarity1,
+    ClarityVersion::Clarity2,
+    ClarityVersion::Clarity3,
+];
+
+/// Returns the first Clarity version in which the built-in `name` exists.
+fn introduced_in(name: &str) -> Option<ClarityVersion> {
+    if DefineFunctions::lookup_by_name(name).is_some() {
+        return Some(ClarityVersion::Clarity1);
+    }
+    CLARITY_VERSIONS.into_iter().find(|version| {
+        NativeFunctions::lookup_by_name_at_version(name, version).is_some()
+            || NativeVariables::lookup_by_name_at_version(name, version).is_some()
+    })
+}
+
+/// The names of the words and keywords in the registries.
+fn registered_names() -> HashSet<ClarityName> {
+    COMPLEX_WORDS
+        .iter()
+        .map(|word| word.name())
+        .chain(SIMPLE_WORDS.iter().map(|word| word.name()))
+        .chain(SIMPLE_VARIADIC_WORDS.iter().map(|word| word.name()))
+        .chain(KEYWORDS.iter().map(|word| word.name()))
+        .collect()
+}
+
+/// Lists every built-in registered in the words registry, sorted by name.
+///
+/// # Panics
+///
+/// Panics if a registered name is not a Clarity built-in.
+pub fn supported_words() -> Vec<SupportedWord> {
+    let mut words: Vec<_> = registered_names()
+        .into_iter()
+        .map(|name| match introduced_in(&name) {
+            Some(since) => SupportedWord { name, since },
+            None => panic!("registered word is not a Clarity built-in: {name}"),
+        })
+        .collect();
+    words.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
+    words
+}
+
+/// Returns the names of the built-ins the compiler supports for contracts
+/// written in Clarity `version`.
+pub fn supported_words_at_version(version: ClarityVersion) -> HashSet<ClarityName> {
+    supported_words()
+        .into_iter()
+        .filter(|word| word.since <= version)
+        .map(|word| word.name)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use clarity::vm::analysis::type_checker::v2_1::TypedNativeFunction;
     use clarity::vm::functions::define::DefineFunctions;
     use clarity::vm::functions::NativeFunctions;
     use clarity::vm::variables::NativeVariables;
+    use clarity::vm::ClarityVersion;
+
+    use super::{
+        introduced_in, registered_names, supported_words, supported_words_at_version,
+        CLARITY_VERSIONS,
+    };
+
+    /// The words exercised by at least one crosscheck test. Add a new word
+    /// here along with its crosscheck tests.
+    const CROSSCHECKED_WORDS: &[&str] = &[
+        "*",
+        "+",
+        "-",
+        "/",
+        "<",
+        "<=",
+        ">",
+        ">=",
+        "and",
+        "append",
+        "as-contract",
+        "as-max-len?",
+        "asserts!",
+        "at-block",
+        "begin",
+        "bit-and",
+        "bit-not",
+        "bit-or",
+        "bit-shift-left",
+        "bit-shift-right",
+        "bit-xor",
+        "block-height",
+        "buff-to-int-be",
+        "buff-to-int-le",
+        "buff-to-uint-be",
+        "buff-to-uint-le",
+        "burn-block-height",
+        "chain-id",
+        "concat",
+        "contract-call?",
+        "contract-of",
+        "default-to",
+        "define-constant",
+        "define-data-var",
+        "define-fungible-token",
+        "define-map",
+        "define-non-fungible-token",
+        "define-private",
+        "define-public",
+        "define-read-only",
+        "define-trait",
+        "element-at",
+        "element-at?",
+        "err",
+        "filter",
+        "fold",
+        "from-consensus-buff?",
+        "ft-burn?",
+        "ft-get-balance",
+        "ft-get-supply",
+        "ft-mint?",
+        "ft-transfer?",
+        "get",
+        "get-block-info?",
+        "get-burn-block-info?",
+        "get-stacks-block-info?",
+        "get-tenure-info?",
+        "hash160",
+        "if",
+        "impl-trait",
+        "index-of",
+        "index-of?",
+        "int-to-ascii",
+        "int-to-utf8",
+        "is-eq",
+        "is-err",
+        "is-in-mainnet",
+        "is-in-regtest",
+        "is-none",
+        "is-ok",
+        "is-some",
+        "is-standard",
+        "keccak256",
+        "len",
+        "let",
+        "list",
+        "log2",
+        "map",
+        "map-delete",
+        "map-get?",
+        "map-insert",
+        "map-set",
+        "match",
+        "merge",
+        "mod",
+        "nft-burn?",
+        "nft-get-owner?",
+        "nft-mint?",
+        "nft-transfer?",
+        "not",
+        "ok",
+        "or",
+        "pow",
+        "principal-construct?",
+        "principal-destruct?",
+        "principal-of?",
+        "print",
+        "replace-at?",
+        "secp256k1-recover?",
+        "secp256k1-verify",
+        "sha256",
+        "sha512",
+        "sha512/256",
+        "slice?",
+        "some",
+        "sqrti",
+        "stacks-block-height",
+        "string-to-int?",
+        "string-to-uint?",
+        "stx-account",
+        "stx-burn?",
+        "stx-get-balance",
+        "stx-liquid-supply",
+        "stx-transfer-memo?",
+        "stx-transfer?",
+        "tenure-height",
+        "to-consensus-buff?",
+        "to-int",
+        "to-uint",
+        "try!",
+        "tuple",
+        "unwrap!",
+        "unwrap-err!",
+        "unwrap-err-panic",
+        "unwrap-panic",
+        "use-trait",
+        "var-get",
+        "var-set",
+        "xor",
+    ];
 
     #[test]
     fn check_for_duplicates() {
@@ -269,6 +507,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn registries_have_unique_names() {
+        assert!(super::lookup_complex("let").is_some());
+        assert!(super::lookup_simple("not").is_some());
+        assert!(super::lookup_variadic_simple("+").is_some());
+        assert!(super::lookup_keyword("block-height").is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate word registration: +")]
+    fn duplicate_registration_panics() {
+        let words: &[&'static dyn super::SimpleWord] =
+            &[&super::arithmetic::Add, &super::arithmetic::Add];
+        super::index_by_name(words);
+    }
+
     #[test]
     fn check_for_non_reserved_words() {
         for word in super::COMPLEX_WORDS {
@@ -317,4 +571,87 @@ mod tests {
             }
         }
     }
+
+    /// The words registered both as complex and variadic simple words.
+    const COMPLEX_AND_SIMPLE_WORDS: [&str; 2] = ["and", "or"];
+
+    #[test]
+    fn supported_words_by_version() {
+        let words = supported_words();
+        let since = |name: &str| {
+            words
+                .iter()
+                .find(|word| word.name.as_str() == name)
+                .map(|word| word.since)
+        };
+        assert_eq!(since("get-block-info?"), Some(ClarityVersion::Clarity1));
+        assert_eq!(
+            since("get-stacks-block-info?"),
+            Some(ClarityVersion::Clarity3)
+        );
+        assert_eq!(since("block-height"), Some(ClarityVersion::Clarity1));
+        assert_eq!(since("chain-id"), Some(ClarityVersion::Clarity2));
+        assert_eq!(since("stacks-block-height"), Some(ClarityVersion::Clarity3));
+        assert_eq!(since("tenure-height"), Some(ClarityVersion::Clarity3));
+
+        // `get-block-info?` is supported from the first version to the last
+        // one, `get-stacks-block-info?` only from the last one.
+        let first = supported_words_at_version(CLARITY_VERSIONS[0]);
+        let before_last = supported_words_at_version(CLARITY_VERSIONS[1]);
+        let last = supported_words_at_version(CLARITY_VERSIONS[2]);
+        assert!(first.contains("get-block-info?"));
+        assert!(last.contains("get-block-info?"));
+        assert!(!first.contains("get-stacks-block-info?"));
+        assert!(!before_last.contains("get-stacks-block-info?"));
+        assert!(last.contains("get-stacks-block-info?"));
+    }
+
+    #[test]
+    fn supported_words_are_registered_once() {
+        for name in COMPLEX_AND_SIMPLE_WORDS {
+            assert!(super::lookup_complex(name).is_some());
+            assert!(super::lookup_variadic_simple(name).is_some());
+        }
+
+        let words = supported_words();
+        let registered = super::COMPLEX_WORDS.len()
+            + super::SIMPLE_WORDS.len()
+            + super::SIMPLE_VARIADIC_WORDS.len()
+            + super::KEYWORDS.len();
+        assert_eq!(words.len(), registered - COMPLEX_AND_SIMPLE_WORDS.len());
+    }
+
+    #[test]
+    fn registered_names_are_built_ins() {
+        for name in registered_names() {
+            assert!(
+                introduced_in(&name).is_some(),
+                "{name} is registered but is not a Clarity built-in"
+            );
+        }
+    }
+
+    #[test]
+    fn every_word_is_crosschecked() {
+        use std::collections::HashSet;
+
+        let registered: HashSet<String> = registered_names()
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect();
+        let covered: HashSet<&str> = CROSSCHECKED_WORDS.iter().copied().collect();
+
+        for name in &registered {
+            assert!(
+                covered.contains(name.as_str()),
+                "{name} is not covered by a crosscheck test; add one and list it in CROSSCHECKED_WORDS"
+            );
+        }
+        for name in CROSSCHECKED_WORDS {
+            assert!(
+                registered.contains(*name),
+                "{name} is listed in CROSSCHECKED_WORDS but is not a registered word"
+            );
+        }
+    }
 }
@@ -4,7 +4,7 @@ use clarity::vm::{ClarityName, SymbolicExpression};
 use super::{ComplexWord, SimpleWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 // Functions below are considered no-op's because they are instructions that does nothing
 // or has no effect when executed.
@@ -20,6 +20,10 @@ impl Word for ToInt {
 }
 
 impl SimpleWord for ToInt {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -44,6 +48,10 @@ impl Word for ToUint {
 }
 
 impl SimpleWord for ToUint {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -68,6 +76,10 @@ impl Word for ContractOf {
 }
 
 impl ComplexWord for ContractOf {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -5,7 +5,7 @@ use walrus::ir::BinaryOp;
 use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{drop_value, ArgumentsExt, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 pub fn traverse_optional(
     generator: &mut WasmGenerator,
@@ -47,6 +47,10 @@ impl Word for IsSome {
 }
 
 impl ComplexWord for IsSome {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -70,6 +74,10 @@ impl Word for IsNone {
 }
 
 impl ComplexWord for IsNone {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -14,7 +14,7 @@ use crate::check_args;
 use crate::wasm_generator::{
     add_placeholder_for_clarity_type, clar2wasm_ty, ArgumentsExt, GeneratorError, WasmGenerator,
 };
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct IsStandard;
@@ -26,6 +26,10 @@ impl Word for IsStandard {
 }
 
 impl SimpleWord for IsStandard {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -97,6 +101,10 @@ impl Word for Construct {
 }
 
 impl ComplexWord for Construct {
+    fn arity(&self) -> Arity {
+        Arity::Between(2, 3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -203,6 +211,10 @@ impl Word for Destruct {
 }
 
 impl SimpleWord for Destruct {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -290,6 +302,10 @@ impl Word for PrincipalOf {
 }
 
 impl ComplexWord for PrincipalOf {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -313,7 +329,7 @@ impl ComplexWord for PrincipalOf {
             &optional_type,
             false, // include_repr
             true,  // include_value
-        );
+        )?;
 
         builder.local_get(result_offset);
         // Call the host interface function, `principal-of?`
@@ -4,7 +4,7 @@ use clarity::vm::{ClarityName, SymbolicExpression};
 use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{ArgumentsExt, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, signature_from_string, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, signature_from_string, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct Print;
@@ -16,6 +16,10 @@ impl Word for Print {
 }
 
 impl ComplexWord for Print {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -63,7 +67,7 @@ impl ComplexWord for Print {
         }
         // Storing expr to memory to pass a reference to `print`
         let (value_offset, value_length) =
-            generator.create_call_stack_local(builder, &ty, false, true);
+            generator.create_call_stack_local(builder, &ty, false, true)?;
         generator.write_to_memory(builder, value_offset, 0, &ty)?;
         // Then load the offset and length onto the stack
         builder.local_get(value_offset).i32_const(value_length);
@@ -178,6 +182,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_nested() {
+        // `crosscheck` also compares the emitted print events with the ones
+        // of the interpreter.
+        let list = Value::cons_list(
+            vec![Value::some(Value::Int(1)).unwrap(), Value::none()],
+            &StacksEpochId::latest(),
+        )
+        .unwrap();
+        crosscheck(
+            "(print {a: (list (some 1) none)})",
+            Ok(Some(Value::Tuple(
+                TupleData::from_data(vec![("a".into(), list.clone())]).unwrap(),
+            ))),
+        );
+        crosscheck(
+            "(print (list (ok {a: (list (some 1) none)}) (err u2)))",
+            Ok(Some(
+                Value::cons_list(
+                    vec![
+                        Value::okay(Value::Tuple(
+                            TupleData::from_data(vec![("a".into(), list)]).unwrap(),
+                        ))
+                        .unwrap(),
+                        Value::err_uint(2),
+                    ],
+                    &StacksEpochId::latest(),
+                )
+                .unwrap(),
+            )),
+        );
+    }
+
     #[test]
     fn test_large_buff() {
         let msg = "a".repeat(1 << 20);
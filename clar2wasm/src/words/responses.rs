@@ -5,7 +5,7 @@ use walrus::ir::BinaryOp;
 use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{drop_value, ArgumentsExt, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 pub fn traverse_response(
     generator: &mut WasmGenerator,
@@ -50,6 +50,10 @@ impl Word for IsOk {
 }
 
 impl ComplexWord for IsOk {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -73,6 +77,10 @@ impl Word for IsErr {
 }
 
 impl ComplexWord for IsErr {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
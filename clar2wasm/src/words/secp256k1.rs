@@ -3,7 +3,7 @@ use clarity::vm::{ClarityName, SymbolicExpression};
 use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{ArgumentsExt, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct Recover;
@@ -15,6 +15,10 @@ impl Word for Recover {
 }
 
 impl ComplexWord for Recover {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -36,15 +40,13 @@ impl ComplexWord for Recover {
             .clone();
 
         let (result_local, result_size) =
-            generator.create_call_stack_local(builder, &ret_ty, true, true);
+            generator.create_call_stack_local(builder, &ret_ty, true, true)?;
         builder.local_get(result_local).i32_const(result_size);
 
         // Call the host interface function, `secp256k1_recover`
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.secp256k1_recover")
+                .try_func_by_name("stdlib.secp256k1_recover")
                 .ok_or_else(|| {
                     GeneratorError::InternalError("stdlib.secp256k1_recover not found".to_owned())
                 })?,
@@ -66,6 +68,10 @@ impl Word for Verify {
 }
 
 impl ComplexWord for Verify {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -82,9 +88,7 @@ impl ComplexWord for Verify {
         // Call the host interface function, `secp256k1_verify`
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.secp256k1_verify")
+                .try_func_by_name("stdlib.secp256k1_verify")
                 .ok_or_else(|| {
                     GeneratorError::InternalError("stdlib.secp256k1_verify not found".to_owned())
                 })?,
@@ -1,3 +1,4 @@
+use clarity::types::StacksEpochId;
 use clarity::vm::clarity_wasm::get_type_size;
 use clarity::vm::types::{
     FunctionType, ListTypeData, SequenceSubtype, StringSubtype, TypeSignature,
@@ -12,7 +13,7 @@ use crate::wasm_generator::{
     add_placeholder_for_clarity_type, clar2wasm_ty, drop_value, type_from_sequence_element,
     ArgumentsExt, GeneratorError, SequenceElementType, WasmGenerator,
 };
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 use crate::words::{self, ComplexWord, Word};
 
 #[derive(Debug)]
@@ -25,6 +26,10 @@ impl Word for ListCons {
 }
 
 impl ComplexWord for ListCons {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(0)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -47,7 +52,7 @@ impl ComplexWord for ListCons {
             };
 
         // Allocate space on the data stack for the entire list
-        let (offset, _size) = generator.create_call_stack_local(builder, &ty, false, true);
+        let (offset, _size) = generator.create_call_stack_local(builder, &ty, false, true)?;
 
         // Loop through the expressions in the list and store them onto the
         // data stack.
@@ -83,6 +88,10 @@ impl Word for Fold {
 }
 
 impl ComplexWord for Fold {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -271,6 +280,10 @@ impl Word for Append {
 }
 
 impl ComplexWord for Append {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
@@ -288,6 +301,10 @@ impl ComplexWord for Append {
         let list = args.get_expr(0)?;
         let elem = args.get_expr(1)?;
 
+        if let Some(list_len) = generator.get_expr_type(list).and_then(sequence_max_len) {
+            check_result_max_len("append", &ty, list_len.saturating_add(1))?;
+        }
+
         // WORKAROUND: setting correct types for arguments
         match &ty {
             TypeSignature::SequenceType(SequenceSubtype::ListType(ltd)) => {
@@ -310,7 +327,7 @@ impl ComplexWord for Append {
         let memory = generator.get_memory()?;
 
         // Allocate stack space for the new list.
-        let (write_ptr, length) = generator.create_call_stack_local(builder, &ty, false, true);
+        let (write_ptr, length) = generator.create_call_stack_local(builder, &ty, false, true)?;
 
         // Push the offset and length of this list to the stack to be returned.
         builder.local_get(write_ptr).i32_const(length);
@@ -327,7 +344,7 @@ impl ComplexWord for Append {
         // list. Save a copy of the length for later.
         let src_length = generator.module.locals.add(ValType::I32);
         builder.local_tee(src_length);
-        builder.memory_copy(memory, memory);
+        generator.emit_memory_copy(builder, memory);
 
         // Increment the write pointer by the length of the source list.
         builder
@@ -362,6 +379,10 @@ impl Word for AsMaxLen {
 }
 
 impl ComplexWord for AsMaxLen {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
@@ -467,6 +488,10 @@ impl Word for Concat {
 }
 
 impl ComplexWord for Concat {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
@@ -483,12 +508,24 @@ impl ComplexWord for Concat {
             .get_expr_type(expr)
             .ok_or_else(|| GeneratorError::TypeError("concat expression must be typed".to_owned()))?
             .clone();
-        let (offset, _) = generator.create_call_stack_local(builder, &ty, false, true);
+        let lhs = args.get_expr(0)?;
+        let rhs = args.get_expr(1)?;
+        if let (Some(lhs_ty), Some(rhs_ty)) =
+            (generator.get_expr_type(lhs), generator.get_expr_type(rhs))
+        {
+            check_concat_types(&generator.contract_analysis.epoch, lhs_ty, rhs_ty)?;
+            if let (Some(lhs_len), Some(rhs_len)) =
+                (sequence_max_len(lhs_ty), sequence_max_len(rhs_ty))
+            {
+                check_result_max_len("concat", &ty, lhs_len.saturating_add(rhs_len))?;
+            }
+        }
+
+        let (offset, _) = generator.create_call_stack_local(builder, &ty, false, true)?;
 
         builder.local_get(offset);
 
         // Traverse the lhs, leaving it on the data stack (offset, size)
-        let lhs = args.get_expr(0)?;
         // WORKAROUND: typechecker issue for lists
         generator.set_expr_type(lhs, ty.clone())?;
         generator.traverse_expr(builder, lhs)?;
@@ -498,7 +535,7 @@ impl ComplexWord for Concat {
         builder.local_tee(lhs_length);
 
         // Copy the lhs to the new sequence
-        builder.memory_copy(memory, memory);
+        generator.emit_memory_copy(builder, memory);
 
         // Load the adjusted destination offset
         builder
@@ -507,7 +544,6 @@ impl ComplexWord for Concat {
             .binop(BinaryOp::I32Add);
 
         // Traverse the rhs, leaving it on the data stack (offset, size)
-        let rhs = args.get_expr(1)?;
         // WORKAROUND: typechecker issue for lists
         generator.set_expr_type(rhs, ty.clone())?;
         generator.traverse_expr(builder, rhs)?;
@@ -517,12 +553,15 @@ impl ComplexWord for Concat {
         builder.local_tee(rhs_length);
 
         // Copy the rhs to the new sequence
-        builder.memory_copy(memory, memory);
+        generator.emit_memory_copy(builder, memory);
 
         // Load the offset of the new sequence
         builder.local_get(offset);
 
         // Total size = lhs_length + rhs_length
+        // A `string-utf8` is stored as 4-byte unicode scalars, so its byte
+        // length is 4 times its length in codepoints, and the copies above
+        // never split a codepoint.
         builder
             .local_get(lhs_length)
             .local_get(rhs_length)
@@ -532,6 +571,72 @@ impl ComplexWord for Concat {
     }
 }
 
+/// Checks that two sequences can be concatenated: they must be the same kind
+/// of sequence, and for lists, their element types must be compatible.
+fn check_concat_types(
+    epoch: &StacksEpochId,
+    lhs_ty: &TypeSignature,
+    rhs_ty: &TypeSignature,
+) -> Result<(), GeneratorError> {
+    match (lhs_ty, rhs_ty) {
+        (
+            TypeSignature::SequenceType(SequenceSubtype::ListType(lhs_list)),
+            TypeSignature::SequenceType(SequenceSubtype::ListType(rhs_list)),
+        ) => {
+            let lhs_elem = lhs_list.get_list_item_type();
+            let rhs_elem = rhs_list.get_list_item_type();
+            TypeSignature::least_supertype(epoch, lhs_elem, rhs_elem)
+                .map(|_| ())
+                .map_err(|_| {
+                    GeneratorError::TypeError(format!(
+                        "concat: incompatible element types {lhs_elem} and {rhs_elem}"
+                    ))
+                })
+        }
+        (
+            TypeSignature::SequenceType(SequenceSubtype::BufferType(_)),
+            TypeSignature::SequenceType(SequenceSubtype::BufferType(_)),
+        )
+        | (
+            TypeSignature::SequenceType(SequenceSubtype::StringType(StringSubtype::ASCII(_))),
+            TypeSignature::SequenceType(SequenceSubtype::StringType(StringSubtype::ASCII(_))),
+        )
+        | (
+            TypeSignature::SequenceType(SequenceSubtype::StringType(StringSubtype::UTF8(_))),
+            TypeSignature::SequenceType(SequenceSubtype::StringType(StringSubtype::UTF8(_))),
+        ) => Ok(()),
+        _ => Err(GeneratorError::TypeError(format!(
+            "concat: incompatible sequence types {lhs_ty} and {rhs_ty}"
+        ))),
+    }
+}
+
+/// Returns the maximum number of elements of a sequence type.
+fn sequence_max_len(ty: &TypeSignature) -> Option<u32> {
+    match ty {
+        TypeSignature::SequenceType(SequenceSubtype::ListType(ltd)) => Some(ltd.get_max_len()),
+        TypeSignature::SequenceType(SequenceSubtype::BufferType(len))
+        | TypeSignature::SequenceType(SequenceSubtype::StringType(StringSubtype::ASCII(len))) => {
+            Some(u32::from(len))
+        }
+        TypeSignature::SequenceType(SequenceSubtype::StringType(StringSubtype::UTF8(len))) => {
+            Some(u32::from(len))
+        }
+        _ => None,
+    }
+}
+
+/// Checks that a result of `len` elements fits in the result type `ty` of
+/// `word`, so that the space reserved for the result cannot be overrun.
+fn check_result_max_len(word: &str, ty: &TypeSignature, len: u32) -> Result<(), GeneratorError> {
+    match sequence_max_len(ty) {
+        Some(max_len) if len > max_len => Err(GeneratorError::TypeError(format!(
+            "{word}: result length {len} exceeds the maximum length {max_len} of {ty}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
 #[derive(Debug)]
 pub struct Map;
 
@@ -542,6 +647,10 @@ impl Word for Map {
 }
 
 impl ComplexWord for Map {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
@@ -559,6 +668,14 @@ impl ComplexWord for Map {
 
         let fname = args.get_name(0)?;
 
+        // The result has as many elements as the shortest input sequence.
+        let min_input_len = args
+            .iter()
+            .skip(1)
+            .map(|arg| generator.get_expr_type(arg).and_then(sequence_max_len))
+            .collect::<Option<Vec<_>>>()
+            .and_then(|lens| lens.into_iter().min());
+
         let seq_ty = generator
             .get_expr_type(args.get_expr(1)?)
             .ok_or_else(|| GeneratorError::TypeError("list expression must be typed".to_owned()))?
@@ -569,7 +686,7 @@ impl ComplexWord for Map {
         // This is a workaround for the typechecker not being able to infer
         // the complete type of initial value.
         if let TypeSignature::SequenceType(SequenceSubtype::ListType(lt)) = &seq_ty {
-            let size = get_type_size(lt.get_list_item_type()) as u32;
+            let max_len = lt.get_max_len();
 
             if let Some(FunctionType::Fixed(fixed)) = generator.get_function_type(fname) {
                 let function_ty = fixed
@@ -581,7 +698,7 @@ impl ComplexWord for Map {
                     .signature
                     .clone();
 
-                match ListTypeData::new_list(function_ty, size) {
+                match ListTypeData::new_list(function_ty, max_len) {
                     Ok(list_type_data) => {
                         generator.set_expr_type(
                             args.get_expr(1)?,
@@ -602,6 +719,10 @@ impl ComplexWord for Map {
             .ok_or_else(|| GeneratorError::TypeError("list expression must be typed".to_owned()))?
             .clone();
 
+        if let Some(len) = min_input_len {
+            check_result_max_len("map", &ty, len)?;
+        }
+
         let return_element_type =
             if let TypeSignature::SequenceType(SequenceSubtype::ListType(list_type)) = &ty {
                 list_type.get_list_item_type()
@@ -693,7 +814,7 @@ impl ComplexWord for Map {
         }
 
         // Allocate worst case size to ensure enough stack space is reserved at compile time
-        let (output_base, _) = generator.create_call_stack_local(builder, &ty, false, true);
+        let (output_base, _) = generator.create_call_stack_local(builder, &ty, false, true)?;
 
         // Allocate space on the call stack for the output list.
         let output_offset = generator.module.locals.add(ValType::I32);
@@ -832,6 +953,10 @@ impl Word for Len {
 }
 
 impl ComplexWord for Len {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
@@ -919,6 +1044,10 @@ impl Word for ElementAt {
 }
 
 impl ComplexWord for ElementAt {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
@@ -932,6 +1061,14 @@ impl ComplexWord for ElementAt {
         let seq = args.get_expr(0)?;
         generator.traverse_expr(builder, seq)?;
 
+        // Keep the sequence, to check the bounds of the result against it.
+        let seq_offset = generator.module.locals.add(ValType::I32);
+        let seq_length = generator.module.locals.add(ValType::I32);
+        builder
+            .local_set(seq_length)
+            .local_tee(seq_offset)
+            .local_get(seq_length);
+
         // Extend the length to 64-bits.
         builder.unop(UnaryOp::I64ExtendUI32);
 
@@ -994,6 +1131,8 @@ impl ComplexWord for ElementAt {
                     _,
                 ))) => {
                     // UTF8 is represented as 32-bit (4 bytes) unicode scalars values.
+                    // Each codepoint has the same width in memory, so the index of a
+                    // codepoint maps directly to its byte-offset, without scanning.
                     // Calculate the total byte length of the list by multiplying the element count by 4
                     // Multiplying by 4 is equivalent to performing a bitwise left shift by 2 bits.
                     builder.i64_const(2);
@@ -1064,6 +1203,22 @@ impl ComplexWord for ElementAt {
             .binop(BinaryOp::I32Add)
             .local_set(offset_local);
 
+        // Make sure that the element is within the sequence.
+        let element_size = match &element_ty {
+            SequenceElementType::Byte => 1,
+            SequenceElementType::UnicodeScalar => 4,
+            SequenceElementType::Other(elem_ty) => get_type_size(elem_ty),
+        };
+        let element_size_local = generator.module.locals.add(ValType::I32);
+        else_.i32_const(element_size).local_set(element_size_local);
+        generator.emit_memory_bounds_check(
+            &mut else_,
+            seq_offset,
+            seq_length,
+            offset_local,
+            element_size_local,
+        );
+
         // Push the `some` indicator
         else_.i32_const(1);
 
@@ -1104,6 +1259,10 @@ impl Word for ReplaceAt {
 }
 
 impl ComplexWord for ReplaceAt {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
@@ -1123,7 +1282,7 @@ impl ComplexWord for ReplaceAt {
 
         // Create a new stack local for a copy of the input list
         let (dest_offset, length) =
-            generator.create_call_stack_local(builder, &seq_ty, false, true);
+            generator.create_call_stack_local(builder, &seq_ty, false, true)?;
 
         // Put the destination offset on the stack
         builder.local_get(dest_offset);
@@ -1134,7 +1293,7 @@ impl ComplexWord for ReplaceAt {
         let memory = generator.get_memory()?;
 
         // Copy the input list to the new stack local
-        builder.memory_copy(memory, memory);
+        generator.emit_memory_copy(builder, memory);
 
         // Extend the sequence length to 64-bits.
         builder.i32_const(length).unop(UnaryOp::I64ExtendUI32);
@@ -1192,6 +1351,8 @@ impl ComplexWord for ReplaceAt {
             }
             TypeSignature::SequenceType(SequenceSubtype::StringType(StringSubtype::UTF8(_))) => {
                 // UTF8 is represented as 32-bit (4 bytes) unicode scalars values.
+                // Each codepoint has the same width in memory, so the index of a
+                // codepoint maps directly to its byte-offset, without scanning.
                 // Calculate the total byte length of the list by multiplying the element count by 4
                 // Multiplying by 4 is equivalent to performing a bitwise left shift by 2 bits.
                 builder.i64_const(2);
@@ -1312,8 +1473,8 @@ impl ComplexWord for ReplaceAt {
                 else_
                     .local_get(offset_local)
                     .local_get(src_local)
-                    .i32_const(1)
-                    .memory_copy(memory, memory);
+                    .i32_const(1);
+                generator.emit_memory_copy(&mut else_, memory);
             }
             SequenceElementType::UnicodeScalar => {
                 // The element is a 32-bit unicode scalar value, so we
@@ -1329,8 +1490,8 @@ impl ComplexWord for ReplaceAt {
                 else_
                     .local_get(offset_local)
                     .local_get(src_local)
-                    .i32_const(4)
-                    .memory_copy(memory, memory);
+                    .i32_const(4);
+                generator.emit_memory_copy(&mut else_, memory);
             }
             SequenceElementType::Other(elem_ty) => {
                 generator.write_to_memory(&mut else_, offset_local, 0, elem_ty)?;
@@ -1359,6 +1520,10 @@ impl Word for Slice {
 }
 
 impl ComplexWord for Slice {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut crate::wasm_generator::WasmGenerator,
@@ -1373,6 +1538,14 @@ impl ComplexWord for Slice {
         // Traverse the sequence, leaving the offset and length on the stack.
         generator.traverse_expr(builder, seq)?;
 
+        // Keep the sequence, to check the bounds of the result against it.
+        let seq_offset = generator.module.locals.add(ValType::I32);
+        let seq_length = generator.module.locals.add(ValType::I32);
+        builder
+            .local_set(seq_length)
+            .local_tee(seq_offset)
+            .local_get(seq_length);
+
         // Extend the sequence length to 64-bits.
         builder.unop(UnaryOp::I64ExtendUI32);
 
@@ -1487,12 +1660,16 @@ impl ComplexWord for Slice {
         // Traverse the right position, leaving it on the stack.
         generator.traverse_expr(builder, args.get_expr(2)?)?;
 
-        // Check if the upper 64-bits are greater than 0.
-        builder.i64_const(0).binop(BinaryOp::I64GtU);
-
-        // Save the overflow indicator to a local.
-        let overflow_local = generator.module.locals.add(ValType::I32);
-        builder.local_set(overflow_local);
+        // Check if the upper 64-bits are greater than 0, and or it with the
+        // overflow indicator of the left bound, which must not be lost: a left
+        // bound equal to the length is out of range even when the right bound
+        // is valid.
+        builder
+            .i64_const(0)
+            .binop(BinaryOp::I64GtU)
+            .local_get(overflow_local)
+            .binop(BinaryOp::I32Or)
+            .local_set(overflow_local);
 
         // Save the lower part of the index, which will ultimately be
         // multiplied by the element size and added to the source offset to be
@@ -1590,6 +1767,37 @@ impl ComplexWord for Slice {
             .binop(BinaryOp::I32Or)
             .local_set(overflow_local);
 
+        // If both bounds are in range, make sure that the slice is within the
+        // sequence.
+        let slice_offset = generator.module.locals.add(ValType::I32);
+        let slice_length = generator.module.locals.add(ValType::I32);
+        let mut check = builder.dangling_instr_seq(None);
+        let check_id = check.id();
+        check
+            .local_get(left_local)
+            .unop(UnaryOp::I32WrapI64)
+            .local_set(slice_offset)
+            .local_get(right_local)
+            .local_get(left_local)
+            .binop(BinaryOp::I64Sub)
+            .unop(UnaryOp::I32WrapI64)
+            .local_set(slice_length);
+        generator.emit_memory_bounds_check(
+            &mut check,
+            seq_offset,
+            seq_length,
+            slice_offset,
+            slice_length,
+        );
+        let out_of_range_id = builder.dangling_instr_seq(None).id();
+        builder
+            .local_get(overflow_local)
+            .unop(UnaryOp::I32Eqz)
+            .instr(ir::IfElse {
+                consequent: check_id,
+                alternative: out_of_range_id,
+            });
+
         // Push a `0` and a `1` to the stack, for none or some, to be selected
         // by the `select` instruction, using the overflow indicator.
         builder.i32_const(0).i32_const(1).local_get(overflow_local);
@@ -1617,9 +1825,15 @@ impl ComplexWord for Slice {
 
 #[cfg(test)]
 mod tests {
+    use clarity::types::StacksEpochId;
+    use clarity::vm::types::{
+        BufferLength, ListTypeData, SequenceSubtype, TupleData, TypeSignature,
+    };
     use clarity::vm::Value;
 
-    use crate::tools::{crosscheck, crosscheck_compare_only, evaluate};
+    use super::{check_concat_types, check_result_max_len};
+    use crate::tools::{crosscheck, crosscheck_compare_only, crosscheck_expect_failure, evaluate};
+    use crate::wasm_generator::GeneratorError;
 
     #[test]
     fn fold_less_than_three_args() {
@@ -1641,6 +1855,59 @@ mod tests {
             .contains("expecting 3 arguments, got 4"));
     }
 
+    #[test]
+    fn list_cons_ints() {
+        crosscheck(
+            "(list 1 2 3)",
+            Ok(Some(
+                Value::cons_list_unsanitized(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
+                    .unwrap(),
+            )),
+        );
+    }
+
+    #[test]
+    fn list_cons_empty() {
+        crosscheck(
+            "(list)",
+            Ok(Some(Value::cons_list_unsanitized(vec![]).unwrap())),
+        );
+    }
+
+    #[test]
+    fn list_cons_tuples() {
+        let tuple = |a: i128, b: bool| {
+            Value::Tuple(
+                TupleData::from_data(vec![
+                    ("a".into(), Value::Int(a)),
+                    ("b".into(), Value::Bool(b)),
+                ])
+                .unwrap(),
+            )
+        };
+        crosscheck(
+            "(list {a: 1, b: true} {b: false, a: 2})",
+            Ok(Some(
+                Value::cons_list_unsanitized(vec![tuple(1, true), tuple(2, false)]).unwrap(),
+            )),
+        );
+    }
+
+    #[test]
+    fn list_cons_unified_element_types() {
+        crosscheck(
+            "(list (some 1) none (some 3))",
+            Ok(Some(
+                Value::cons_list_unsanitized(vec![
+                    Value::some(Value::Int(1)).unwrap(),
+                    Value::none(),
+                    Value::some(Value::Int(3)).unwrap(),
+                ])
+                .unwrap(),
+            )),
+        );
+    }
+
     #[test]
     fn append_less_than_two_args() {
         let result = evaluate("(append (list 1 2 3))");
@@ -1701,6 +1968,106 @@ mod tests {
             .contains("expecting 2 arguments, got 3"));
     }
 
+    #[test]
+    fn concat_incompatible_element_types() {
+        let result = evaluate("(concat (list 1) (list true))");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn check_concat_types_names_element_types() {
+        let list_of = |ty: TypeSignature| {
+            TypeSignature::SequenceType(SequenceSubtype::ListType(
+                ListTypeData::new_list(ty, 1).unwrap(),
+            ))
+        };
+        let err = check_concat_types(
+            &StacksEpochId::latest(),
+            &list_of(TypeSignature::IntType),
+            &list_of(TypeSignature::BoolType),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, GeneratorError::TypeError(msg) if msg.contains("int") && msg.contains("bool")),
+            "unexpected error: {err:?}"
+        );
+
+        assert!(check_concat_types(
+            &StacksEpochId::latest(),
+            &list_of(TypeSignature::IntType),
+            &list_of(TypeSignature::IntType),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn concat_buffer_result_length() {
+        crosscheck(
+            "(define-data-var b (buff 4) 0x) (var-set b (concat 0x0102 0x0304)) (var-get b)",
+            Ok(Some(Value::buff_from(vec![1, 2, 3, 4]).unwrap())),
+        );
+
+        // The lengths of the arguments are only known at runtime, but their
+        // types bound the result to 8 bytes, which a `(buff 4)` can't hold.
+        crosscheck_expect_failure(
+            "
+(define-data-var b (buff 4) 0x)
+(define-public (store (x (buff 4)))
+  (ok (var-set b (concat x x))))",
+        );
+        crosscheck(
+            "
+(define-read-only (fit (x (buff 4)))
+  (as-max-len? (concat x x) u4))
+{ short: (fit 0x01), long: (fit 0x010203) }",
+            evaluate("{ short: (some 0x0101), long: none }"),
+        );
+
+        let buff_4 = TypeSignature::SequenceType(SequenceSubtype::BufferType(
+            BufferLength::try_from(4usize).unwrap(),
+        ));
+        assert!(check_result_max_len("concat", &buff_4, 8).is_err());
+        assert!(check_result_max_len("concat", &buff_4, 4).is_ok());
+    }
+
+    #[test]
+    fn append_exceeding_result_type() {
+        // `(append (list 1 2 3) 4)` has 4 elements, targeted at a `(list 3 int)`.
+        let list_3_int = TypeSignature::SequenceType(SequenceSubtype::ListType(
+            ListTypeData::new_list(TypeSignature::IntType, 3).unwrap(),
+        ));
+        let err = check_result_max_len("append", &list_3_int, 4).unwrap_err();
+        assert!(
+            matches!(&err, GeneratorError::TypeError(msg) if msg.contains("exceeds the maximum length 3")),
+            "unexpected error: {err:?}"
+        );
+        assert!(check_result_max_len("append", &list_3_int, 3).is_ok());
+    }
+
+    #[test]
+    fn append_within_result_type() {
+        crosscheck(
+            "(define-data-var l (list 10 int) (list 1 2 3)) (var-set l (append (var-get l) 4)) (var-get l)",
+            Ok(Some(
+                Value::cons_list_unsanitized(vec![
+                    Value::Int(1),
+                    Value::Int(2),
+                    Value::Int(3),
+                    Value::Int(4),
+                ])
+                .unwrap(),
+            )),
+        );
+    }
+
+    #[test]
+    fn map_over_appended_list() {
+        crosscheck_compare_only(
+            "(define-private (double (x int)) (* x 2)) \
+             (map double (append (list 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20) 21))",
+        );
+    }
+
     #[test]
     fn map_less_than_two_args() {
         let result = evaluate("(map +)");
@@ -1771,6 +2138,58 @@ mod tests {
             .contains("expecting 3 arguments, got 4"));
     }
 
+    #[test]
+    fn element_at_string_utf8() {
+        // The second codepoint of "aéb" takes two bytes in UTF-8.
+        crosscheck(
+            r#"(element-at? u"a\u{e9}b" u1)"#,
+            evaluate(r#"(some u"\u{e9}")"#),
+        );
+        crosscheck(
+            r#"(element-at? u"a\u{e9}b" u2)"#,
+            evaluate(r#"(some u"b")"#),
+        );
+        crosscheck(r#"(element-at? u"a\u{e9}b" u3)"#, evaluate("none"));
+    }
+
+    #[test]
+    fn string_utf8_literal_with_escape() {
+        // Each codepoint is stored as one 4-byte scalar, whatever its UTF-8
+        // length.
+        crosscheck(r#"(len u"caf\u{e9}")"#, Ok(Some(Value::UInt(4))));
+        crosscheck(
+            r#"(is-eq u"caf\u{e9}" (concat u"caf" u"\u{E9}"))"#,
+            Ok(Some(Value::Bool(true))),
+        );
+        crosscheck(
+            r#"(is-eq u"caf\u{e9}" u"cafe")"#,
+            Ok(Some(Value::Bool(false))),
+        );
+    }
+
+    #[test]
+    fn concat_string_utf8() {
+        crosscheck(r#"(concat u"a" u"\u{e9}")"#, evaluate(r#"u"a\u{e9}""#));
+        crosscheck(r#"(len (concat u"a" u"\u{e9}"))"#, Ok(Some(Value::UInt(2))));
+        crosscheck(
+            r#"(concat u"\u{1F98A}\u{e9}" u"b")"#,
+            evaluate(r#"u"\u{1F98A}\u{e9}b""#),
+        );
+    }
+
+    #[test]
+    fn replace_at_string_utf8() {
+        crosscheck(
+            r#"(replace-at? u"a\u{e9}b" u1 u"\u{1F98A}")"#,
+            evaluate(r#"(some u"a\u{1F98A}b")"#),
+        );
+        crosscheck(
+            r#"(replace-at? u"a\u{e9}b" u2 u"\u{e9}")"#,
+            evaluate(r#"(some u"a\u{e9}\u{e9}")"#),
+        );
+        crosscheck(r#"(replace-at? u"a\u{e9}b" u3 u"c")"#, evaluate("none"));
+    }
+
     #[test]
     fn slice_less_than_three_args() {
         let result = evaluate("(slice? (list 1 2 3) u1)");
@@ -1809,6 +2228,27 @@ mod tests {
         crosscheck(r#"(fold + (list 1 2 3 4) 0)"#, Ok(Some(Value::Int(10))))
     }
 
+    #[test]
+    fn fold_long_list_does_not_grow_call_stack() {
+        // The fold body is a wasm loop, so the number of frames doesn't depend
+        // on the length of the list.
+        let elements = (1..=5000)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        crosscheck(
+            &format!(
+                r#"
+(define-private (add (x int) (acc int))
+    (+ x acc)
+)
+(fold add (list {elements}) 0)
+    "#
+            ),
+            Ok(Some(Value::Int(5000 * 5001 / 2))),
+        )
+    }
+
     #[test]
     fn test_fold_sub_empty() {
         crosscheck(
@@ -1913,6 +2353,18 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_fold_buffer_bytes() {
+        // Each element is a single byte.
+        crosscheck(
+            "
+(define-private (add-byte (byte (buff 1)) (acc uint)) (+ acc (buff-to-uint-be byte)))
+(fold add-byte 0x0102ff u0)
+",
+            Ok(Some(Value::UInt(258))),
+        )
+    }
+
     #[test]
     fn test_fold_buffer_empty() {
         crosscheck(
@@ -1926,6 +2378,46 @@ mod tests {
         )
     }
 
+    #[test]
+    fn fold_uints_into_buffer() {
+        crosscheck(
+            "
+(define-private (append-byte (n uint) (acc (buff 8)))
+    (unwrap-panic (as-max-len? (concat acc (unwrap-panic (element-at 0x00112233 n))) u8))
+)
+(fold append-byte (list u3 u0 u2 u1) 0x)
+",
+            Ok(Some(
+                Value::buff_from(vec![0x33, 0x00, 0x22, 0x11]).unwrap(),
+            )),
+        )
+    }
+
+    #[test]
+    fn fold_uints_into_tuple_with_buffer() {
+        crosscheck(
+            "
+(define-private (step (n uint) (acc { bytes: (buff 8), total: uint }))
+    {
+        bytes: (unwrap-panic (as-max-len? (concat (get bytes acc) (unwrap-panic (element-at 0x00112233 n))) u8)),
+        total: (+ n (get total acc))
+    }
+)
+(fold step (list u1 u2 u3) { bytes: 0x, total: u0 })
+",
+            Ok(Some(Value::from(
+                TupleData::from_data(vec![
+                    (
+                        "bytes".into(),
+                        Value::buff_from(vec![0x11, 0x22, 0x33]).unwrap(),
+                    ),
+                    ("total".into(), Value::UInt(6)),
+                ])
+                .unwrap(),
+            ))),
+        )
+    }
+
     #[test]
     fn fold_init() {
         crosscheck(
@@ -2010,6 +2502,18 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_map_string_utf8_codepoints() {
+        // Each element is a codepoint, whatever its UTF-8 length.
+        crosscheck(
+            r#"
+(define-private (is-e-acute (char (string-utf8 1))) (is-eq char u"\u{e9}"))
+(map is-e-acute u"a\u{e9}\u{1F98A}\u{e9}")
+        "#,
+            evaluate("(list false true false true)"),
+        )
+    }
+
     #[test]
     fn test_map() {
         const MAP_FNS: &str = "
@@ -2453,6 +2957,53 @@ mod tests {
             );
         }
 
+        const EMPTY_LIST: &str = "(define-data-var l (list 4 int) (list))";
+
+        #[test]
+        fn element_at_empty_list() {
+            crosscheck(
+                &format!("{EMPTY_LIST} (element-at? (var-get l) u0)"),
+                evaluate("none"),
+            );
+            crosscheck("(element-at? (list) u0)", evaluate("none"));
+        }
+
+        #[test]
+        fn element_at_list_of_responses() {
+            const RESPONSES: &str =
+                "(define-data-var l (list 2 (response int uint)) (list (ok 1) (err u2)))";
+
+            crosscheck(
+                "(element-at? (list (ok 1) (err u2)) u0)",
+                evaluate("(some (ok 1))"),
+            );
+            crosscheck(
+                "(element-at? (list (ok 1) (err u2)) u1)",
+                evaluate("(some (err u2))"),
+            );
+            crosscheck(
+                &format!("{RESPONSES} (element-at? (var-get l) u1)"),
+                evaluate("(some (err u2))"),
+            );
+            crosscheck(
+                &format!("{RESPONSES} (element-at? (var-get l) u2)"),
+                evaluate("none"),
+            );
+        }
+
+        #[test]
+        fn slice_empty_list() {
+            crosscheck(
+                &format!("{EMPTY_LIST} (len (unwrap-panic (slice? (var-get l) u0 u0)))"),
+                Ok(Some(Value::UInt(0))),
+            );
+            crosscheck(
+                &format!("{EMPTY_LIST} (slice? (var-get l) u0 u1)"),
+                evaluate("none"),
+            );
+            crosscheck_compare_only("(slice? (list) u0 u0)");
+        }
+
         #[test]
         fn slice_right_lt_left() {
             crosscheck("(slice? \"abc\" u1 u0)", evaluate("none"));
@@ -2481,6 +3032,75 @@ mod tests {
             crosscheck("(slice? \"abc\" u0 u3)", evaluate("(some \"abc\")"));
         }
 
+        #[test]
+        fn slice_boundaries_match_interpreter() {
+            for seq in ["\"abc\"", "u\"abc\"", "0x010203", "(list 1 2 3)"] {
+                // left=0, right=len
+                crosscheck_compare_only(&format!("(slice? {seq} u0 u3)"));
+                // left=len, right=len
+                crosscheck_compare_only(&format!("(slice? {seq} u3 u3)"));
+                // right>len
+                crosscheck_compare_only(&format!("(slice? {seq} u1 u4)"));
+                crosscheck_compare_only(&format!(
+                    "(slice? {seq} u0 u340282366920938463463374607431768211455)"
+                ));
+                // left>right
+                crosscheck_compare_only(&format!("(slice? {seq} u2 u1)"));
+                crosscheck_compare_only(&format!(
+                    "(slice? {seq} u340282366920938463463374607431768211455 u1)"
+                ));
+            }
+        }
+
+        #[test]
+        fn slice_left_at_length() {
+            // The left bound must be strictly less than the length.
+            crosscheck("(slice? \"abc\" u3 u3)", Ok(Some(Value::none())));
+            crosscheck("(slice? (list 1 2 3) u3 u3)", Ok(Some(Value::none())));
+            crosscheck("(slice? 0x u0 u0)", Ok(Some(Value::none())));
+        }
+
+        #[test]
+        fn slice_buffer() {
+            crosscheck("(slice? 0x00112233 u1 u3)", evaluate("(some 0x1122)"));
+            crosscheck("(slice? 0x00112233 u2 u4)", evaluate("(some 0x2233)"));
+            crosscheck("(slice? 0x00112233 u3 u5)", evaluate("none"));
+        }
+
+        #[test]
+        fn element_at_and_slice_past_sequence_end() {
+            // The memory after the first sequence holds the second one, so
+            // the indices below are in the memory but past the sequence.
+            const SEQUENCES: &str = "(define-data-var a (list 4 int) (list 1 2)) \
+                 (define-data-var b (list 4 int) (list 3 4))";
+
+            crosscheck(
+                &format!("{SEQUENCES} (element-at? (var-get a) u2)"),
+                evaluate("none"),
+            );
+            crosscheck(
+                &format!("{SEQUENCES} (slice? (var-get a) u1 u3)"),
+                evaluate("none"),
+            );
+            crosscheck(
+                &format!("{SEQUENCES} (slice? (var-get a) u1 u2)"),
+                evaluate("(some (list 2))"),
+            );
+        }
+
+        #[test]
+        fn replace_at_buffer() {
+            crosscheck(
+                "(replace-at? 0x000000 u1 0xff)",
+                evaluate("(some 0x00ff00)"),
+            );
+            crosscheck(
+                "(replace-at? 0x000000 u2 0xff)",
+                evaluate("(some 0x0000ff)"),
+            );
+            crosscheck("(replace-at? 0x000000 u3 0xff)", evaluate("none"));
+        }
+
         #[test]
         fn replace_element_cannot_be_empty_buff() {
             let snippet = r#"(replace-at? 0x12345678 u0 0x)"#;
@@ -1,10 +1,10 @@
-use clarity::vm::types::TypeSignature;
+use clarity::vm::types::{SequenceSubtype, TypeSignature};
 use clarity::vm::{ClarityName, SymbolicExpression};
 
 use super::{ComplexWord, SimpleWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{ArgumentsExt, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct StxBurn;
@@ -16,6 +16,10 @@ impl Word for StxBurn {
 }
 
 impl SimpleWord for StxBurn {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -41,6 +45,10 @@ impl Word for StxGetBalance {
 }
 
 impl SimpleWord for StxGetBalance {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -63,6 +71,10 @@ impl Word for StxTransfer {
 }
 
 impl ComplexWord for StxTransfer {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -87,6 +99,9 @@ impl ComplexWord for StxTransfer {
     }
 }
 
+/// The maximum length of the memo of `stx-transfer-memo?`.
+const MAX_MEMO_LENGTH: u32 = 34;
+
 #[derive(Debug)]
 pub struct StxTransferMemo;
 
@@ -97,6 +112,10 @@ impl Word for StxTransferMemo {
 }
 
 impl ComplexWord for StxTransferMemo {
+    fn arity(&self) -> Arity {
+        Arity::Exact(4)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -111,6 +130,23 @@ impl ComplexWord for StxTransferMemo {
         let recipient = args.get_expr(2)?;
         let memo = args.get_expr(3)?;
 
+        // The memo is passed as-is to the host, so it must fit in the event.
+        match generator.get_expr_type(memo) {
+            Some(TypeSignature::SequenceType(SequenceSubtype::BufferType(len)))
+                if u32::from(len) <= MAX_MEMO_LENGTH => {}
+            Some(ty) => {
+                return Err(GeneratorError::TypeError(format!(
+                    "stx-transfer-memo? memo must be a buffer of at most {MAX_MEMO_LENGTH} bytes, \
+                     got {ty}"
+                )))
+            }
+            None => {
+                return Err(GeneratorError::TypeError(
+                    "stx-transfer-memo? memo must be typed".to_owned(),
+                ))
+            }
+        }
+
         generator.traverse_expr(builder, amount)?;
         generator.traverse_expr(builder, sender)?;
         generator.traverse_expr(builder, recipient)?;
@@ -131,6 +167,10 @@ impl Word for StxGetAccount {
 }
 
 impl SimpleWord for StxGetAccount {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn visit(
         &self,
         generator: &mut WasmGenerator,
@@ -284,10 +324,15 @@ mod tests {
     #[cfg(not(feature = "test-clarity-v1"))]
     #[cfg(test)]
     mod clarity_v2_v3 {
+        use clarity::vm::types::{SequenceSubtype, TypeSignature};
         use clarity::vm::Value;
+        use walrus::FunctionBuilder;
 
         use super::*;
-        use crate::tools::crosscheck_validate;
+        use crate::tools::{compile_analysis, crosscheck_validate};
+        use crate::wasm_generator::{GeneratorError, WasmGenerator};
+        use crate::words::stx::StxTransferMemo;
+        use crate::words::ComplexWord;
 
         #[test]
         fn stx_account() {
@@ -311,6 +356,46 @@ mod tests {
             )
         }
 
+        #[test]
+        fn stx_transfer_memo_max_length() {
+            let memo = "ab".repeat(34);
+            crosscheck(
+                &format!("(stx-transfer-memo? u100 'S1G2081040G2081040G2081040G208105NK8PE5 'ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM 0x{memo})"),
+                evaluate("(ok true)"),
+            )
+        }
+
+        #[test]
+        fn stx_transfer_memo_too_long() {
+            // The typechecker rejects memos longer than 34 bytes, so the word
+            // is visited directly with a longer memo type.
+            let snippet = "(stx-transfer-memo? u100 'S1G2081040G2081040G2081040G208105NK8PE5 'ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM 0x12)";
+            let contract_analysis = compile_analysis(snippet);
+            let expr = contract_analysis.expressions[0].clone();
+            let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+            let args = &expr.match_list().unwrap()[1..];
+            generator
+                .set_expr_type(
+                    &args[3],
+                    TypeSignature::SequenceType(SequenceSubtype::BufferType(
+                        35u32.try_into().unwrap(),
+                    )),
+                )
+                .unwrap();
+
+            let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+            let err = StxTransferMemo
+                .traverse(&mut generator, &mut func.func_body(), &expr, args)
+                .unwrap_err();
+            let GeneratorError::TypeError(msg) = err else {
+                panic!("expected a type error, got {err:?}");
+            };
+            assert_eq!(
+                msg,
+                "stx-transfer-memo? memo must be a buffer of at most 34 bytes, got (buff 35)"
+            );
+        }
+
         #[test]
         fn stx_transfer_memo_ok() {
             //
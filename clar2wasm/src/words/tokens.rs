@@ -4,7 +4,7 @@ use clarity::vm::{ClarityName, SymbolicExpression};
 use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{ArgumentsExt, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct DefineFungibleToken;
@@ -16,6 +16,10 @@ impl Word for DefineFungibleToken {
 }
 
 impl ComplexWord for DefineFungibleToken {
+    fn arity(&self) -> Arity {
+        Arity::Between(1, 2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -81,6 +85,10 @@ impl Word for BurnFungibleToken {
 }
 
 impl ComplexWord for BurnFungibleToken {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -121,6 +129,10 @@ impl Word for TransferFungibleToken {
 }
 
 impl ComplexWord for TransferFungibleToken {
+    fn arity(&self) -> Arity {
+        Arity::Exact(4)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -163,6 +175,10 @@ impl Word for MintFungibleToken {
 }
 
 impl ComplexWord for MintFungibleToken {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -202,6 +218,10 @@ impl Word for GetSupplyOfFungibleToken {
 }
 
 impl ComplexWord for GetSupplyOfFungibleToken {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -234,6 +254,10 @@ impl Word for GetBalanceOfFungibleToken {
 }
 
 impl ComplexWord for GetBalanceOfFungibleToken {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -274,6 +298,10 @@ impl Word for DefineNonFungibleToken {
 }
 
 impl ComplexWord for DefineNonFungibleToken {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -313,9 +341,7 @@ impl ComplexWord for DefineNonFungibleToken {
 
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.define_nft")
+                .try_func_by_name("stdlib.define_nft")
                 .ok_or_else(|| {
                     GeneratorError::InternalError("stdlib.define_nft not found".to_owned())
                 })?,
@@ -334,6 +360,10 @@ impl Word for BurnNonFungibleToken {
 }
 
 impl ComplexWord for BurnNonFungibleToken {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -362,7 +392,7 @@ impl ComplexWord for BurnNonFungibleToken {
 
         // Allocate space on the stack for the identifier
         let (id_offset, id_size) =
-            generator.create_call_stack_local(builder, &identifier_ty, true, false);
+            generator.create_call_stack_local(builder, &identifier_ty, true, false)?;
 
         // Write the identifier to the stack (since the host needs to handle generic types)
         generator.write_to_memory(builder, id_offset, 0, &identifier_ty)?;
@@ -390,6 +420,10 @@ impl Word for TransferNonFungibleToken {
 }
 
 impl ComplexWord for TransferNonFungibleToken {
+    fn arity(&self) -> Arity {
+        Arity::Exact(4)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -419,7 +453,7 @@ impl ComplexWord for TransferNonFungibleToken {
 
         // Allocate space on the stack for the identifier
         let (id_offset, id_size) =
-            generator.create_call_stack_local(builder, &identifier_ty, true, false);
+            generator.create_call_stack_local(builder, &identifier_ty, true, false)?;
 
         // Write the identifier to the stack (since the host needs to handle generic types)
         generator.write_to_memory(builder, id_offset, 0, &identifier_ty)?;
@@ -450,6 +484,10 @@ impl Word for MintNonFungibleToken {
 }
 
 impl ComplexWord for MintNonFungibleToken {
+    fn arity(&self) -> Arity {
+        Arity::Exact(3)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -478,7 +516,7 @@ impl ComplexWord for MintNonFungibleToken {
 
         // Allocate space on the stack for the identifier
         let (id_offset, id_size) =
-            generator.create_call_stack_local(builder, &identifier_ty, true, false);
+            generator.create_call_stack_local(builder, &identifier_ty, true, false)?;
 
         // Write the identifier to the stack (since the host needs to handle generic types)
         generator.write_to_memory(builder, id_offset, 0, &identifier_ty)?;
@@ -506,6 +544,10 @@ impl Word for GetOwnerOfNonFungibleToken {
 }
 
 impl ComplexWord for GetOwnerOfNonFungibleToken {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -533,7 +575,7 @@ impl ComplexWord for GetOwnerOfNonFungibleToken {
 
         // Allocate space on the stack for the identifier
         let (id_offset, id_size) =
-            generator.create_call_stack_local(builder, &identifier_ty, true, false);
+            generator.create_call_stack_local(builder, &identifier_ty, true, false)?;
 
         // Write the identifier to the stack (since the host needs to handle generic types)
         generator.write_to_memory(builder, id_offset, 0, &identifier_ty)?;
@@ -544,8 +586,12 @@ impl ComplexWord for GetOwnerOfNonFungibleToken {
         // Reserve stack space for the return value, a principal
         let return_offset;
         let return_size;
-        (return_offset, return_size) =
-            generator.create_call_stack_local(builder, &TypeSignature::PrincipalType, false, true);
+        (return_offset, return_size) = generator.create_call_stack_local(
+            builder,
+            &TypeSignature::PrincipalType,
+            false,
+            true,
+        )?;
 
         // Push the offset and size to the data stack
         builder.local_get(return_offset).i32_const(return_size);
@@ -837,6 +883,39 @@ mod tests {
         crosscheck_expect_failure(ERR);
     }
 
+    #[test]
+    fn capped_ft_mint_up_to_supply() {
+        let snippet = "
+(define-fungible-token capped u100)
+{
+    first: (ft-mint? capped u60 tx-sender),
+    second: (ft-mint? capped u40 tx-sender),
+    supply: (ft-get-supply capped),
+}";
+        crosscheck(
+            snippet,
+            evaluate("{first: (ok true), second: (ok true), supply: u100}"),
+        );
+    }
+
+    #[test]
+    fn capped_ft_mint_beyond_supply() {
+        // The interpreter fails with a supply overflow, and does not return
+        // `(err u1)`, which is only for a zero amount.
+        crosscheck_expect_failure(
+            "
+(define-fungible-token capped u100)
+(unwrap-panic (ft-mint? capped u100 tx-sender))
+(ft-mint? capped u1 tx-sender)",
+        );
+        crosscheck(
+            "
+(define-fungible-token capped u100)
+(ft-mint? capped u0 tx-sender)",
+            evaluate("(err u1)"),
+        );
+    }
+
     #[test]
     fn validate_define_fungible_tokens() {
         // Reserved keyword
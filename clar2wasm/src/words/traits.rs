@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
+
+use clarity::vm::types::{FunctionSignature, FunctionType, TraitIdentifier};
 use clarity::vm::{ClarityName, SymbolicExpression, SymbolicExpressionType};
 
 use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{ArgumentsExt, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct DefineTrait;
@@ -15,6 +18,10 @@ impl Word for DefineTrait {
 }
 
 impl ComplexWord for DefineTrait {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -43,13 +50,23 @@ impl ComplexWord for DefineTrait {
 
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.define_trait")
+                .try_func_by_name("stdlib.define_trait")
                 .ok_or_else(|| {
                     GeneratorError::InternalError("stdlib.define_trait not found".to_owned())
                 })?,
         );
+
+        // Record the method signatures, needed to serialize the arguments of
+        // dynamic calls through this trait.
+        let methods = generator
+            .contract_analysis
+            .get_defined_trait(name.as_str())
+            .ok_or_else(|| {
+                GeneratorError::TypeError(format!("trait {name} has no recorded signature"))
+            })?
+            .clone();
+        generator.traits_types.insert(name.clone(), methods);
+
         Ok(())
     }
 }
@@ -64,6 +81,10 @@ impl Word for UseTrait {
 }
 
 impl ComplexWord for UseTrait {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -100,6 +121,10 @@ impl Word for ImplTrait {
 }
 
 impl ComplexWord for ImplTrait {
+    fn arity(&self) -> Arity {
+        Arity::Exact(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -118,6 +143,12 @@ impl ComplexWord for ImplTrait {
             }
         };
 
+        // The definition of the trait is only available if it was loaded from
+        // the analysis database.
+        if let Some(methods) = generator.implemented_traits.get(trait_identifier) {
+            check_trait_conformance(generator, trait_identifier, methods)?;
+        }
+
         // Store the trait identifier as a string literal in the memory
         let (trait_offset, trait_length) =
             generator.add_string_literal(&trait_identifier.to_string())?;
@@ -129,9 +160,7 @@ impl ComplexWord for ImplTrait {
 
         builder.call(
             generator
-                .module
-                .funcs
-                .by_name("stdlib.impl_trait")
+                .try_func_by_name("stdlib.impl_trait")
                 .ok_or_else(|| {
                     GeneratorError::InternalError("stdlib.impl_trait not found".to_owned())
                 })?,
@@ -140,17 +169,78 @@ impl ComplexWord for ImplTrait {
     }
 }
 
+/// Checks that the public and read-only functions of the contract match the
+/// method signatures of the trait `trait_identifier`.
+fn check_trait_conformance(
+    generator: &WasmGenerator,
+    trait_identifier: &TraitIdentifier,
+    methods: &BTreeMap<ClarityName, FunctionSignature>,
+) -> Result<(), GeneratorError> {
+    let epoch = &generator.contract_analysis.epoch;
+    let mut errors = vec![];
+    for (name, expected) in methods {
+        // Like in Clarity, a read-only function can implement a method.
+        let Some(FunctionType::Fixed(func)) = generator
+            .contract_analysis
+            .get_public_function_type(name.as_str())
+            .or_else(|| {
+                generator
+                    .contract_analysis
+                    .get_read_only_function_type(name.as_str())
+            })
+        else {
+            errors.push(format!("missing method {name}"));
+            continue;
+        };
+
+        // The function should accept at least the arguments of the trait method,
+        // and return a value admitted by the trait method.
+        let args_match =
+            func.args.len() == expected.args.len()
+                && func.args.iter().zip(&expected.args).all(|(arg, expected)| {
+                    arg.signature.admits_type(epoch, expected).unwrap_or(false)
+                });
+        let returns_match = expected
+            .returns
+            .admits_type(epoch, &func.returns)
+            .unwrap_or(false);
+        if !args_match || !returns_match {
+            errors.push(format!("mismatched signature for method {name}"));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(GeneratorError::TypeError(format!(
+            "contract does not conform to trait {trait_identifier}: {}",
+            errors.join(", ")
+        )))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use clarity::types::StacksEpochId;
+    use clarity::vm::analysis::AnalysisDatabase;
+    use clarity::vm::costs::LimitedCostTracker;
+    use clarity::vm::database::MemoryBackingStore;
     use clarity::vm::types::{
-        CallableData, QualifiedContractIdentifier, StandardPrincipalData, TraitIdentifier,
+        CallableData, FunctionSignature, QualifiedContractIdentifier, StandardPrincipalData,
+        TraitIdentifier, TypeSignature,
     };
-    use clarity::vm::Value;
+    use clarity::vm::{ClarityName, ClarityVersion, SymbolicExpression, Value};
+    use walrus::FunctionBuilder;
 
+    use super::{DefineTrait, ImplTrait};
+    use crate::compile;
     use crate::tools::{
         crosscheck, crosscheck_expect_failure, crosscheck_multi_contract, TestEnvironment,
     };
+    use crate::wasm_generator::{GeneratorError, WasmGenerator};
+    use crate::words::ComplexWord;
 
     //
     // Module with tests that should only be executed
@@ -202,6 +292,62 @@ mod tests {
         assert_eq!(token_trait.len(), 2);
     }
 
+    #[test]
+    fn define_trait_records_signatures() {
+        let contract_analysis = compile(
+            r#"
+(define-trait token-trait
+    ((transfer? (principal principal uint) (response uint uint))
+        (get-balance (principal) (response uint uint))))
+            "#,
+            &QualifiedContractIdentifier::transient(),
+            LimitedCostTracker::new_free(),
+            ClarityVersion::Clarity2,
+            StacksEpochId::Epoch25,
+            &mut AnalysisDatabase::new(&mut MemoryBackingStore::new()),
+        )
+        .unwrap()
+        .contract_analysis;
+        let define_trait = contract_analysis.expressions[0].clone();
+        let args = define_trait.match_list().unwrap();
+
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        DefineTrait
+            .traverse(
+                &mut generator,
+                &mut func.func_body(),
+                &define_trait,
+                &args[1..],
+            )
+            .unwrap();
+
+        let transfer = generator
+            .trait_method_signature("token-trait", "transfer?")
+            .unwrap();
+        assert_eq!(
+            transfer.args,
+            vec![
+                TypeSignature::PrincipalType,
+                TypeSignature::PrincipalType,
+                TypeSignature::UIntType
+            ]
+        );
+        assert_eq!(
+            transfer.returns,
+            TypeSignature::new_response(TypeSignature::UIntType, TypeSignature::UIntType).unwrap()
+        );
+
+        let get_balance = generator
+            .trait_method_signature("token-trait", "get-balance")
+            .unwrap();
+        assert_eq!(get_balance.args, vec![TypeSignature::PrincipalType]);
+
+        assert!(generator
+            .trait_method_signature("token-trait", "mint")
+            .is_none());
+    }
+
     #[test]
     fn use_trait_eval() {
         let mut env = TestEnvironment::default();
@@ -286,6 +432,131 @@ mod tests {
             .is_some());
     }
 
+    fn token_trait() -> (TraitIdentifier, BTreeMap<ClarityName, FunctionSignature>) {
+        let trait_id = TraitIdentifier::new(
+            StandardPrincipalData::transient(),
+            "token".into(),
+            "token-trait".into(),
+        );
+        let methods = BTreeMap::from([
+            (
+                "transfer?".into(),
+                FunctionSignature {
+                    args: vec![TypeSignature::PrincipalType, TypeSignature::UIntType],
+                    returns: TypeSignature::new_response(
+                        TypeSignature::BoolType,
+                        TypeSignature::UIntType,
+                    )
+                    .unwrap(),
+                },
+            ),
+            (
+                "get-balance".into(),
+                FunctionSignature {
+                    args: vec![TypeSignature::PrincipalType],
+                    returns: TypeSignature::new_response(
+                        TypeSignature::UIntType,
+                        TypeSignature::UIntType,
+                    )
+                    .unwrap(),
+                },
+            ),
+        ]);
+        (trait_id, methods)
+    }
+
+    /// Traverses `(impl-trait <token-trait>)` in a contract defined by `snippet`.
+    fn impl_token_trait(snippet: &str) -> Result<(), GeneratorError> {
+        let contract_analysis = compile(
+            snippet,
+            &QualifiedContractIdentifier::transient(),
+            LimitedCostTracker::new_free(),
+            ClarityVersion::Clarity2,
+            StacksEpochId::Epoch25,
+            &mut AnalysisDatabase::new(&mut MemoryBackingStore::new()),
+        )
+        .unwrap()
+        .contract_analysis;
+
+        let (trait_id, methods) = token_trait();
+        let mut generator = WasmGenerator::new(contract_analysis).unwrap();
+        generator
+            .implemented_traits
+            .insert(trait_id.clone(), methods);
+
+        let mut func = FunctionBuilder::new(&mut generator.module.types, &[], &[]);
+        let impl_trait = SymbolicExpression::field(trait_id);
+        ImplTrait.traverse(
+            &mut generator,
+            &mut func.func_body(),
+            &impl_trait,
+            &[impl_trait.clone()],
+        )
+    }
+
+    #[test]
+    fn impl_trait_conforming_contract() {
+        impl_token_trait(
+            r#"
+(define-public (transfer? (to principal) (amount uint)) (ok true))
+(define-public (get-balance (who principal)) (ok u0))
+            "#,
+        )
+        .expect("contract should conform to the trait");
+    }
+
+    #[test]
+    fn impl_trait_read_only_method() {
+        impl_token_trait(
+            r#"
+(define-public (transfer? (to principal) (amount uint)) (ok true))
+(define-read-only (get-balance (who principal)) (ok u0))
+            "#,
+        )
+        .expect("a read-only function should implement the method");
+
+        crosscheck_multi_contract(
+            &[
+                (
+                    "token-trait".into(),
+                    "(define-trait token-trait ((get-balance (principal) (response uint uint))))",
+                ),
+                (
+                    "token".into(),
+                    "
+(impl-trait .token-trait.token-trait)
+(define-read-only (get-balance (who principal)) (ok u0))",
+                ),
+            ],
+            Ok(None),
+        );
+    }
+
+    #[test]
+    fn impl_trait_missing_method() {
+        let Err(GeneratorError::TypeError(message)) =
+            impl_token_trait("(define-public (get-balance (who principal)) (ok u0))")
+        else {
+            panic!("contract should not conform to the trait");
+        };
+        assert!(message.contains("does not conform to trait"));
+        assert!(message.contains("missing method transfer?"));
+    }
+
+    #[test]
+    fn impl_trait_mismatched_method() {
+        let Err(GeneratorError::TypeError(message)) = impl_token_trait(
+            r#"
+(define-public (transfer? (to principal) (amount int)) (ok true))
+(define-public (get-balance (who principal)) (ok u0))
+            "#,
+        ) else {
+            panic!("contract should not conform to the trait");
+        };
+        assert!(message.contains("mismatched signature for method transfer?"));
+        assert!(!message.contains("get-balance"));
+    }
+
     #[test]
     fn trait_list() {
         // NOTE: this also tests `print` of `Callable`
@@ -6,7 +6,7 @@ use clarity::vm::{ClarityName, SymbolicExpression};
 use super::{ComplexWord, Word};
 use crate::check_args;
 use crate::wasm_generator::{clar2wasm_ty, drop_value, GeneratorError, WasmGenerator};
-use crate::wasm_utils::{check_argument_count, ArgumentCountCheck};
+use crate::wasm_utils::{check_argument_count, ArgumentCountCheck, Arity};
 
 #[derive(Debug)]
 pub struct TupleCons;
@@ -18,6 +18,10 @@ impl Word for TupleCons {
 }
 
 impl ComplexWord for TupleCons {
+    fn arity(&self) -> Arity {
+        Arity::AtLeast(1)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -109,6 +113,10 @@ impl Word for TupleGet {
 }
 
 impl ComplexWord for TupleGet {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -185,6 +193,10 @@ impl Word for TupleMerge {
 }
 
 impl ComplexWord for TupleMerge {
+    fn arity(&self) -> Arity {
+        Arity::Exact(2)
+    }
+
     fn traverse(
         &self,
         generator: &mut WasmGenerator,
@@ -381,6 +393,78 @@ mod tests {
         crosscheck(snippet, Ok(Some(expected)));
     }
 
+    #[test]
+    fn tuple_field_order_independent() {
+        let expected = Value::from(
+            TupleData::from_data(vec![
+                (ClarityName::from("a"), Value::Int(1)),
+                (ClarityName::from("b"), Value::Int(2)),
+            ])
+            .unwrap(),
+        );
+
+        for snippet in ["{b: 2, a: 1}", "{a: 1, b: 2}", "(tuple (b 2) (a 1))"] {
+            crosscheck(snippet, Ok(Some(expected.clone())));
+        }
+    }
+
+    #[test]
+    fn tuple_field_order_same_memory_layout() {
+        // Elements of a list are written to memory.
+        crosscheck(
+            "(is-eq (list {b: 2, a: 1}) (list {a: 1, b: 2}))",
+            Ok(Some(Value::Bool(true))),
+        );
+    }
+
+    #[test]
+    fn tuple_with_forty_fields() {
+        // Field names sort differently lexicographically ("f10" < "f2") and
+        // numerically, and are declared in reverse order.
+        let field = |i: usize| {
+            if i % 2 == 0 {
+                (format!("f{i}"), format!("{i}"), Value::Int(i as i128))
+            } else {
+                (format!("f{i}"), format!("u{i}"), Value::UInt(i as u128))
+            }
+        };
+        let tuple = format!(
+            "{{ {} }}",
+            (0..40)
+                .rev()
+                .map(|i| {
+                    let (name, literal, _) = field(i);
+                    format!("{name}: {literal}")
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let expected = Value::from(
+            TupleData::from_data(
+                (0..40)
+                    .map(|i| {
+                        let (name, _, value) = field(i);
+                        (ClarityName::from(name.as_str()), value)
+                    })
+                    .collect(),
+            )
+            .unwrap(),
+        );
+
+        crosscheck(&tuple, Ok(Some(expected.clone())));
+        // Round-trip through memory as a list element.
+        crosscheck(
+            &format!("(unwrap-panic (element-at (list {tuple}) u0))"),
+            Ok(Some(expected)),
+        );
+        for i in [0, 2, 10, 17, 39] {
+            crosscheck(
+                &format!("(get f{i} (unwrap-panic (element-at (list {tuple}) u0)))"),
+                Ok(Some(field(i).2)),
+            );
+        }
+    }
+
     //
     // Module with tests that should only be executed
     // when running Clarity::V2 or Clarity::v3.
@@ -406,6 +490,14 @@ mod tests {
 
             crosscheck(snippet, Ok(None));
         }
+
+        #[test]
+        fn tuple_field_order_same_serialization() {
+            crosscheck(
+                "(is-eq (to-consensus-buff? {b: 2, a: 1}) (to-consensus-buff? {a: 1, b: 2}))",
+                Ok(Some(Value::Bool(true))),
+            );
+        }
     }
 
     #[test]